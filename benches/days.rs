@@ -0,0 +1,33 @@
+//! Each day's part 1 and part 2 on the real inputs in `inputs/`.
+//!
+//! Run with `cargo bench --bench days`; filter with e.g.
+//! `cargo bench --bench days -- day08`. Days marked `slow` in
+//! `answers.toml` are skipped unless `AOC_BENCH_SLOW=1` is set, and days
+//! without an input file are skipped.
+
+use aoc25::days;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn bench_days(c: &mut Criterion) {
+    let answers = days::answers::load().unwrap_or_default();
+    let include_slow = std::env::var("AOC_BENCH_SLOW").as_deref() == Ok("1");
+    for &day in days::DAYS {
+        if answers.get(day).is_some_and(|a| a.slow) && !include_slow {
+            continue;
+        }
+        let Ok(input) = days::util::read_input(day) else {
+            continue;
+        };
+        let solver = days::solver(day).expect("registered day");
+        let mut g = c.benchmark_group(day);
+        g.sample_size(10);
+        g.bench_function("part1", |b| b.iter(|| solver.part1(black_box(&input))));
+        if solver.part2(&input).is_some() {
+            g.bench_function("part2", |b| b.iter(|| solver.part2(black_box(&input))));
+        }
+        g.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+//! Delta-stepping vs. `dijkstra_indexed` on a large random grid graph.
+//!
+//! Run with `cargo bench --features rayon --bench delta_stepping`.
+
+use aoc25::{delta_stepping, dijkstra_indexed};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// `side x side` 4-connected grid with pseudo-random weights in 1..=9.
+fn grid_graph(side: usize) -> Vec<Vec<(usize, i64)>> {
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+    let mut next_w = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % 9) as i64 + 1
+    };
+    let mut adj = vec![Vec::with_capacity(4); side * side];
+    for y in 0..side {
+        for x in 0..side {
+            let u = y * side + x;
+            if x + 1 < side {
+                adj[u].push((u + 1, next_w()));
+                adj[u + 1].push((u, next_w()));
+            }
+            if y + 1 < side {
+                adj[u].push((u + side, next_w()));
+                adj[u + side].push((u, next_w()));
+            }
+        }
+    }
+    adj
+}
+
+fn bench_shortest_paths(c: &mut Criterion) {
+    let side = 1000;
+    let adj = grid_graph(side);
+    let n = adj.len();
+    let mut g = c.benchmark_group("sssp_grid_1000x1000");
+    g.sample_size(10);
+    g.bench_function("dijkstra_indexed", |b| {
+        b.iter(|| dijkstra_indexed(n, black_box(&adj), 0))
+    });
+    for delta in [3, 10] {
+        g.bench_function(format!("delta_stepping_{delta}"), |b| {
+            b.iter(|| delta_stepping(n, black_box(&adj), 0, delta))
+        });
+    }
+    g.finish();
+}
+
+criterion_group!(benches, bench_shortest_paths);
+criterion_main!(benches);
@@ -0,0 +1,179 @@
+//! Core data-structure operations against their `std` counterparts, plus
+//! `RectilinearRegion::largest_rect` against the all-pairs scan it replaces.
+//!
+//! Run with `cargo bench --bench ds`.
+
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+
+use aoc25::{BstMap, MinHeap, Point, RectilinearRegion, SimpleHashMap};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const N: u64 = 10_000;
+
+/// `n` pseudo-random keys (xorshift), so the unbalanced BST stays shallow.
+fn keys(n: u64) -> Vec<u64> {
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+    (0..n)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        })
+        .collect()
+}
+
+fn bench_hash_maps(c: &mut Criterion) {
+    let keys = keys(N);
+    let mut g = c.benchmark_group("hash_map_10k");
+    g.bench_function("SimpleHashMap/insert", |b| {
+        b.iter(|| {
+            let mut m = SimpleHashMap::new();
+            for &k in &keys {
+                m.insert(k, k);
+            }
+            m
+        })
+    });
+    g.bench_function("std::HashMap/insert", |b| {
+        b.iter(|| {
+            let mut m = HashMap::new();
+            for &k in &keys {
+                m.insert(k, k);
+            }
+            m
+        })
+    });
+    let mut simple = SimpleHashMap::new();
+    let mut std_map = HashMap::new();
+    for &k in &keys {
+        simple.insert(k, k);
+        std_map.insert(k, k);
+    }
+    g.bench_function("SimpleHashMap/get", |b| {
+        b.iter(|| keys.iter().filter_map(|k| simple.get(black_box(k))).count())
+    });
+    g.bench_function("std::HashMap/get", |b| {
+        b.iter(|| {
+            keys.iter()
+                .filter_map(|k| std_map.get(black_box(k)))
+                .count()
+        })
+    });
+    g.finish();
+}
+
+fn bench_ordered_maps(c: &mut Criterion) {
+    let keys = keys(N);
+    let mut g = c.benchmark_group("ordered_map_10k");
+    g.bench_function("BstMap/insert", |b| {
+        b.iter(|| {
+            let mut m = BstMap::new();
+            for &k in &keys {
+                m.insert(k, k);
+            }
+            m
+        })
+    });
+    g.bench_function("std::BTreeMap/insert", |b| {
+        b.iter(|| {
+            let mut m = BTreeMap::new();
+            for &k in &keys {
+                m.insert(k, k);
+            }
+            m
+        })
+    });
+    let mut bst = BstMap::new();
+    let mut btree = BTreeMap::new();
+    for &k in &keys {
+        bst.insert(k, k);
+        btree.insert(k, k);
+    }
+    g.bench_function("BstMap/get", |b| {
+        b.iter(|| keys.iter().filter_map(|k| bst.get(black_box(k))).count())
+    });
+    g.bench_function("std::BTreeMap/get", |b| {
+        b.iter(|| keys.iter().filter_map(|k| btree.get(black_box(k))).count())
+    });
+    g.finish();
+}
+
+fn bench_heaps(c: &mut Criterion) {
+    let keys = keys(N);
+    let mut g = c.benchmark_group("heap_10k_push_pop");
+    g.bench_function("MinHeap", |b| {
+        b.iter(|| {
+            let mut h = MinHeap::new();
+            for &k in &keys {
+                h.push(k);
+            }
+            let mut sum = 0u64;
+            while let Some(x) = h.pop() {
+                sum = sum.wrapping_add(x);
+            }
+            sum
+        })
+    });
+    g.bench_function("std::BinaryHeap", |b| {
+        b.iter(|| {
+            let mut h = BinaryHeap::new();
+            for &k in &keys {
+                h.push(k);
+            }
+            let mut sum = 0u64;
+            while let Some(x) = h.pop() {
+                sum = sum.wrapping_add(x);
+            }
+            sum
+        })
+    });
+    g.finish();
+}
+
+/// Corner tiles of a staircase loop around a circle (day 09's shape): `n`
+/// points on the circle, each joined to the next by an axis-aligned corner.
+fn staircase_loop(n: usize, radius: f64) -> Vec<Point> {
+    let on_circle = |i: usize| {
+        let t = i as f64 / n as f64 * std::f64::consts::TAU;
+        Point::new((radius * t.cos()) as i64, (radius * t.sin()) as i64)
+    };
+    (0..n)
+        .flat_map(|i| {
+            let (a, b) = (on_circle(i), on_circle((i + 1) % n));
+            [a, Point::new(b.x, a.y)]
+        })
+        .collect()
+}
+
+fn bench_largest_rect(c: &mut Criterion) {
+    let corners = staircase_loop(250, 50_000.0);
+    let region = RectilinearRegion::new(&corners);
+    let mut g = c.benchmark_group("largest_rect_500_corners");
+    g.bench_function("largest_rect", |b| {
+        b.iter(|| region.largest_rect(black_box(&corners)))
+    });
+    g.bench_function("all_pairs", |b| {
+        b.iter(|| {
+            let mut best = 0i64;
+            for (i, &p) in corners.iter().enumerate() {
+                for &q in &corners[i + 1..] {
+                    if region.contains_rect(p, q) {
+                        best = best.max(((p.x - q.x).abs() + 1) * ((p.y - q.y).abs() + 1));
+                    }
+                }
+            }
+            best
+        })
+    });
+    g.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_hash_maps,
+    bench_ordered_maps,
+    bench_heaps,
+    bench_largest_rect
+);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+//! `IntervalSet::add` over arbitrary intervals (including the i64 extremes)
+//! keeps the set sorted, disjoint and non-adjacent, and covers every
+//! interval added.
+#![no_main]
+
+use aoc25::{Interval, IntervalSet};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|ends: Vec<(i64, i64)>| {
+    let mut set = IntervalSet::new();
+    let added: Vec<Interval> = ends
+        .into_iter()
+        .map(|(a, b)| Interval::new(a.min(b), a.max(b)))
+        .collect();
+    for &iv in &added {
+        set.add(iv);
+    }
+    for pair in set.intervals().windows(2) {
+        // A gap of at least one point between neighbors, else they'd merge.
+        assert!(i128::from(pair[1].start) - i128::from(pair[0].end) > 1);
+    }
+    for iv in &added {
+        assert!(set
+            .intervals()
+            .iter()
+            .any(|s| s.start <= iv.start && iv.end <= s.end));
+    }
+});
@@ -0,0 +1,12 @@
+//! Day 10's machine line parser must reject bad lines with an error, never
+//! panic (e.g. on button indices past the diagram or huge joltage values).
+#![no_main]
+
+use aoc25::days;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for (i, line) in input.lines().enumerate() {
+        let _ = days::day10::parse_machine(line, i + 1);
+    }
+});
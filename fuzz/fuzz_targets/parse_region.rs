@@ -0,0 +1,12 @@
+//! Day 12's `WxH: n n ...` region parser must return an error on bad lines,
+//! never panic.
+#![no_main]
+
+use aoc25::days;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for (i, line) in input.lines().enumerate() {
+        let _ = days::day12::parse_region(line, i + 1);
+    }
+});
@@ -0,0 +1,45 @@
+//! Random operation sequences on `SimpleHashMap`, checked step by step
+//! against `std::collections::HashMap`. Keys are a single byte so buckets
+//! collide and resizes happen early.
+#![no_main]
+
+use std::collections::HashMap;
+
+use aoc25::SimpleHashMap;
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Insert(u8, u32),
+    Get(u8),
+    Update(u8, u32),
+    Remove(u8),
+    Clear,
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut map = SimpleHashMap::new();
+    let mut model = HashMap::new();
+    for op in ops {
+        match op {
+            Op::Insert(k, v) => assert_eq!(map.insert(k, v), model.insert(k, v)),
+            Op::Get(k) => assert_eq!(map.get(&k), model.get(&k)),
+            Op::Update(k, v) => {
+                if let (Some(a), Some(b)) = (map.get_mut(&k), model.get_mut(&k)) {
+                    *a = v;
+                    *b = v;
+                } else {
+                    assert_eq!(map.contains_key(&k), model.contains_key(&k));
+                }
+            }
+            Op::Remove(k) => assert_eq!(map.remove(&k), model.remove(&k)),
+            Op::Clear => {
+                map.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(map.len(), model.len());
+        assert_eq!(map.is_empty(), model.is_empty());
+    }
+});
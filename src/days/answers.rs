@@ -0,0 +1,137 @@
+//! Recorded answers (`answers.toml`) and checking day results against them.
+//!
+//! The file is a small TOML subset: one `[dayXX]` table per day holding
+//! string `part1`/`part2` keys and an optional boolean `slow`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+
+use super::DayResult;
+
+/// Path of the answers file, relative to the crate root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Expected answers for one day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// Too slow for the default test run (see `AOC_SLOW_TESTS`).
+    pub slow: bool,
+}
+
+/// Parse the contents of an answers file into day id → expected answers.
+pub fn parse_answers(src: &str) -> Result<BTreeMap<String, Expected>, String> {
+    let mut out = BTreeMap::new();
+    let mut current: Option<String> = None;
+    for (i, raw) in src.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: &str| format!("{}:{}: {}", ANSWERS_FILE, i + 1, msg);
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let day = name.trim().to_string();
+            out.insert(day.clone(), Expected::default());
+            current = Some(day);
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value`"))?;
+        let day = current
+            .as_ref()
+            .ok_or_else(|| err("key outside a [dayXX] table"))?;
+        let entry = out.get_mut(day).expect("table inserted above");
+        let value = value.trim();
+        let string = || {
+            value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(str::to_string)
+                .ok_or_else(|| err("expected a quoted string"))
+        };
+        match key.trim() {
+            "part1" => entry.part1 = Some(string()?),
+            "part2" => entry.part2 = Some(string()?),
+            "slow" => entry.slow = value.parse().map_err(|_| err("expected true or false"))?,
+            other => return Err(err(&format!("unknown key `{}`", other))),
+        }
+    }
+    Ok(out)
+}
+
+/// Load and parse [`ANSWERS_FILE`].
+pub fn load() -> Result<BTreeMap<String, Expected>, Box<dyn Error>> {
+    let src = fs::read_to_string(ANSWERS_FILE)
+        .map_err(|e| format!("cannot read {}: {}", ANSWERS_FILE, e))?;
+    Ok(parse_answers(&src)?)
+}
+
+/// Describe every part of `result` that disagrees with `expected`; empty
+/// when all recorded answers match. Parts that were not run are ignored.
+pub fn mismatches(result: &DayResult, expected: &Expected) -> Vec<String> {
+    let parts = [
+        (1, &result.part1, &expected.part1),
+        (2, &result.part2, &expected.part2),
+    ];
+    parts
+        .into_iter()
+        .filter_map(|(n, got, want)| match (got, want) {
+            (Some(got), Some(want)) if got != want => {
+                Some(format!("part {}: expected {}, got {}", n, want, got))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Test body shared by the generated per-day answer tests: run `day` on its
+/// real input and assert it matches `answers.toml`. Days without recorded
+/// answers or input are skipped, as are `slow` days unless
+/// `AOC_SLOW_TESTS=1`.
+#[cfg(test)]
+pub fn assert_day(day: &str) {
+    let answers = load().expect("answers file");
+    let Some(expected) = answers.get(day) else {
+        eprintln!("{day}: no recorded answers, skipping");
+        return;
+    };
+    if expected.slow && std::env::var("AOC_SLOW_TESTS").as_deref() != Ok("1") {
+        eprintln!("{day}: slow, skipping (set AOC_SLOW_TESTS=1 to run)");
+        return;
+    }
+    if !std::path::Path::new(&format!("inputs/{day}.txt")).exists() {
+        eprintln!("{day}: no input, skipping");
+        return;
+    }
+    let result = super::run_day(day, &Default::default()).expect("day runs");
+    let wrong = mismatches(&result, expected);
+    assert!(wrong.is_empty(), "{day}: {}", wrong.join("; "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_and_reports_mismatches() {
+        let src = "# comment\n[day01]\npart1 = \"3\"\npart2 = \"14\" # trailing\n\n[day12]\npart1 = \"440\"\nslow = true\n";
+        let answers = parse_answers(src).unwrap();
+        assert_eq!(answers["day12"].part2, None);
+        assert!(answers["day12"].slow);
+
+        let result = DayResult {
+            day: "day01".into(),
+            part1: Some("3".into()),
+            part2: Some("15".into()),
+            ..DayResult::default()
+        };
+        assert_eq!(
+            mismatches(&result, &answers["day01"]),
+            ["part 2: expected 14, got 15"]
+        );
+        assert!(parse_answers("part1 = \"1\"").is_err());
+    }
+}
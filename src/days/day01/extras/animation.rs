@@ -7,7 +7,7 @@
 //! # Example
 //! ```no_run
 //! use std::time::Duration;
-//! use crate::days::day01::extras::animation::{self, AnimationOptions};
+//! use aoc25::days::day01::extras::animation::{self, AnimationOptions};
 //!
 //! # fn demo(input: &str) -> std::io::Result<()> {
 //! let options = AnimationOptions {
@@ -25,6 +25,7 @@ use std::thread;
 use std::time::Duration;
 
 use super::super::{parse_rotations, DIAL_SIZE, START_POS};
+use crate::{ModRing, Turn};
 
 /// Configuration knobs for the console animation.
 #[derive(Clone, Copy, Debug)]
@@ -49,22 +50,23 @@ impl Default for AnimationOptions {
 
 /// Animate the dial using raw puzzle input (one rotation per line).
 pub fn animate_from_input(input: &str, options: AnimationOptions) -> io::Result<()> {
-    let rotations = parse_rotations(input);
+    let rotations =
+        parse_rotations(input).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
     animate(&rotations, options)
 }
 
 /// Animate a prepared sequence of rotations.
-pub fn animate(rotations: &[(char, i64)], options: AnimationOptions) -> io::Result<()> {
+pub fn animate(rotations: &[(Turn, u64)], options: AnimationOptions) -> io::Result<()> {
     let mut stdout = io::stdout();
     run_animation(rotations, options, &mut stdout)
 }
 
 fn run_animation<W: Write>(
-    rotations: &[(char, i64)],
+    rotations: &[(Turn, u64)],
     options: AnimationOptions,
     writer: &mut W,
 ) -> io::Result<()> {
-    let mut pos = START_POS;
+    let mut dial = ModRing::new(DIAL_SIZE, START_POS);
     let mut zero_hits = 0usize;
     let mut zero_clicks = 0usize;
     let mut frames = 0usize;
@@ -72,7 +74,7 @@ fn run_animation<W: Write>(
     emit_frame(
         writer,
         frames,
-        pos,
+        dial.position(),
         "Start position",
         zero_hits,
         zero_clicks,
@@ -90,11 +92,10 @@ fn run_animation<W: Write>(
             continue;
         }
         for step_idx in 0..steps {
-            pos = advance(pos, dir);
-            if pos == 0 {
+            if dial.rotate(dir, 1) == 0 {
                 zero_clicks += 1;
             }
-            if step_idx + 1 == steps && pos == 0 {
+            if step_idx + 1 == steps && dial.is_at(0) {
                 zero_hits += 1;
             }
 
@@ -111,7 +112,7 @@ fn run_animation<W: Write>(
             emit_frame(
                 writer,
                 frames,
-                pos,
+                dial.position(),
                 &desc,
                 zero_hits,
                 zero_clicks,
@@ -132,14 +133,6 @@ fn run_animation<W: Write>(
     Ok(())
 }
 
-fn advance(pos: i64, dir: char) -> i64 {
-    match dir {
-        'L' => (pos - 1).rem_euclid(DIAL_SIZE),
-        'R' => (pos + 1).rem_euclid(DIAL_SIZE),
-        other => panic!("unknown direction in animation: {other}"),
-    }
-}
-
 fn reached_limit(current: usize, limit: Option<usize>) -> bool {
     limit.map_or(false, |max| current >= max)
 }
@@ -147,7 +140,7 @@ fn reached_limit(current: usize, limit: Option<usize>) -> bool {
 fn emit_frame<W: Write>(
     writer: &mut W,
     frame_idx: usize,
-    pos: i64,
+    pos: u64,
     desc: &str,
     zero_hits: usize,
     zero_clicks: usize,
@@ -175,7 +168,7 @@ const GRID_SIZE: usize = 21;
 const CENTER: f64 = (GRID_SIZE as f64 - 1.0) / 2.0;
 const DIAL_RADIUS: f64 = (GRID_SIZE as f64 - 3.0) / 2.0;
 
-fn render_dial(pos: i64) -> Vec<String> {
+fn render_dial(pos: u64) -> Vec<String> {
     let mut grid = vec![vec![' '; GRID_SIZE]; GRID_SIZE];
 
     // Draw circular outline.
@@ -192,7 +185,7 @@ fn render_dial(pos: i64) -> Vec<String> {
 
     // Mark tick marks every 10 / 5 units.
     for value in (0..DIAL_SIZE).step_by(5) {
-        let (row, col) = coords_for_value(value as i64);
+        let (row, col) = coords_for_value(value);
         grid[row][col] = if value % 10 == 0 { '+' } else { '.' };
     }
 
@@ -208,8 +201,8 @@ fn render_dial(pos: i64) -> Vec<String> {
         .collect()
 }
 
-fn coords_for_value(value: i64) -> (usize, usize) {
-    let normalized = value.rem_euclid(DIAL_SIZE) as f64;
+fn coords_for_value(value: u64) -> (usize, usize) {
+    let normalized = (value % DIAL_SIZE) as f64;
     let angle = FRAC_PI_2 - 2.0 * PI * (normalized / DIAL_SIZE as f64);
     let x = CENTER + DIAL_RADIUS * angle.cos();
     let y = CENTER - DIAL_RADIUS * angle.sin();
@@ -231,7 +224,7 @@ mod tests {
     #[test]
     fn respect_frame_cap() {
         let input = "R3";
-        let rotations = parse_rotations(input);
+        let rotations = parse_rotations(input).unwrap();
         let mut buf = Vec::new();
         run_animation(
             &rotations,
@@ -8,10 +8,12 @@ use std::fs;
 use std::path::Path;
 
 use super::super::{parse_rotations, DIAL_SIZE, START_POS};
+use crate::Turn;
 
 /// Generate an HTML file containing the dial animation.
 pub fn write_animation_html<P: AsRef<Path>>(path: P, input: &str) -> std::io::Result<()> {
-    let rotations = parse_rotations(input);
+    let rotations = parse_rotations(input)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
     let html = build_html(&rotations);
     let path_ref = path.as_ref();
     if let Some(parent) = path_ref.parent() {
@@ -22,7 +24,7 @@ pub fn write_animation_html<P: AsRef<Path>>(path: P, input: &str) -> std::io::Re
     fs::write(path_ref, html)
 }
 
-fn build_html(rotations: &[(char, i64)]) -> String {
+fn build_html(rotations: &[(Turn, u64)]) -> String {
     let rotations_js = rotations
         .iter()
         .map(|(dir, steps)| format!(r#"{{dir:"{}",steps:{}}}"#, dir, steps))
@@ -231,7 +233,7 @@ mod tests {
 
     #[test]
     fn emits_html() {
-        let html = build_html(&[('R', 2)]);
+        let html = build_html(&[(Turn::Right, 2)]);
         assert!(html.contains("<canvas"));
         assert!(html.contains("rotations"));
     }
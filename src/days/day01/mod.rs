@@ -4,99 +4,89 @@ pub mod extras;
 
 use std::time::Duration;
 
-use super::util;
+use crate::{ModRing, ParseError, Turn};
 
-const START_POS: i64 = 50;
-const DIAL_SIZE: i64 = 100;
+use super::util::{field_error, parse_field, PartResult};
+use super::Solver;
 
-pub fn part1(input: &str) -> String {
-    let rotations = parse_rotations(input);
-    let (zero_hits, _, _) = simulate(&rotations);
-    zero_hits.to_string()
+const START_POS: u64 = 50;
+const DIAL_SIZE: u64 = 100;
+
+pub fn part1(input: &str) -> PartResult {
+    let rotations = parse_rotations(input)?;
+    let (zero_hits, _) = simulate(&rotations);
+    Ok(zero_hits.to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let rotations = parse_rotations(input);
-    let (_, _, zero_clicks) = simulate(&rotations);
-    zero_clicks.to_string()
+pub fn part2(input: &str) -> PartResult {
+    let rotations = parse_rotations(input)?;
+    let (_, zero_clicks) = simulate(&rotations);
+    Ok(zero_clicks.to_string())
 }
 
-fn parse_rotations(input: &str) -> Vec<(char, i64)> {
-    input
-        .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                return None;
-            }
-            let (dir_ch, rest) = line.split_at(1);
-            let dir = dir_ch
-                .chars()
-                .next()
-                .map(|c| c.to_ascii_uppercase())
-                .expect("direction char");
-            let steps: i64 = rest.trim().parse().unwrap_or_else(|_| {
-                panic!("invalid rotation distance: {line}");
-            });
-            if steps < 0 {
-                panic!("rotation distance must be non-negative: {line}");
-            }
-            Some((dir, steps))
-        })
-        .collect()
+fn parse_rotations(input: &str) -> Result<Vec<(Turn, u64)>, ParseError> {
+    let mut rotations = Vec::new();
+    for (i, raw) in input.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let split = line.chars().next().map_or(0, char::len_utf8);
+        let (dir_ch, rest) = line.split_at(split);
+        let turn = dir_ch
+            .chars()
+            .next()
+            .and_then(Turn::from_char)
+            .ok_or_else(|| field_error(raw, i + 1, dir_ch, "direction must be L or R"))?;
+        let steps: i64 = parse_field(raw, i + 1, rest, "rotation distance")?;
+        if steps < 0 {
+            return Err(field_error(
+                raw,
+                i + 1,
+                rest.trim(),
+                "rotation distance must be non-negative",
+            ));
+        }
+        rotations.push((turn, steps as u64));
+    }
+    Ok(rotations)
 }
 
-fn simulate(rotations: &[(char, i64)]) -> (usize, i64, usize) {
-    let mut pos = START_POS;
+/// Times the dial ends a rotation on 0, and clicks that pass over 0.
+fn simulate(rotations: &[(Turn, u64)]) -> (usize, u64) {
+    let mut dial = ModRing::new(DIAL_SIZE, START_POS);
     let mut zero_hits = 0usize;
-    let mut zero_clicks = 0usize;
-    for &(dir, steps) in rotations {
-        zero_clicks += zero_hits_during_rotation(pos, dir, steps);
-        pos = match dir {
-            'L' => (pos - steps).rem_euclid(DIAL_SIZE),
-            'R' => (pos + steps).rem_euclid(DIAL_SIZE),
-            other => panic!("unknown rotation direction: {other}"),
-        };
-        if pos == 0 {
+    let mut zero_clicks = 0u64;
+    for &(turn, steps) in rotations {
+        zero_clicks += dial.crossings_of(0, turn, steps);
+        if dial.rotate(turn, steps) == 0 {
             zero_hits += 1;
         }
     }
-    (zero_hits, pos, zero_clicks)
+    (zero_hits, zero_clicks)
 }
 
-fn zero_hits_during_rotation(start_pos: i64, dir: char, steps: i64) -> usize {
-    if steps == 0 {
-        return 0;
-    }
-    let offset = match dir {
-        'L' => start_pos.rem_euclid(DIAL_SIZE),
-        'R' => (DIAL_SIZE - start_pos.rem_euclid(DIAL_SIZE)).rem_euclid(DIAL_SIZE),
-        other => panic!("unknown rotation direction: {other}"),
-    };
-    let first_hit = if offset == 0 { DIAL_SIZE } else { offset };
-    if steps < first_hit {
-        0
-    } else {
-        let remaining = steps - first_hit;
-        1 + (remaining / DIAL_SIZE) as usize
+pub struct Day01;
+
+impl Solver for Day01 {
+    fn prepare(&self, input: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(options) = animation_options_from_env() {
+            extras::animation::animate_from_input(input, options)?;
+        }
+        if let Some(path) = web_animation_path_from_env() {
+            extras::web::write_animation_html(&path, input)?;
+            eprintln!("Day01 web animation written to {}", path);
+        }
+        Ok(())
     }
-}
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day01")?;
-    if let Some(options) = animation_options_from_env() {
-        extras::animation::animate_from_input(&input, options)?;
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
     }
-    if let Some(path) = web_animation_path_from_env() {
-        extras::web::write_animation_html(&path, &input)?;
-        eprintln!("Day01 web animation written to {}", path);
+
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
     }
-    println!(
-        "Day 01\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
 }
 
 /// Inspect `DAY01_ANIMATE` (and optional tuning vars) to decide whether to
@@ -155,7 +145,7 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         } else {
@@ -166,7 +156,7 @@ mod tests {
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         } else {
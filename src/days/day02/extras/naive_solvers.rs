@@ -3,11 +3,13 @@
 //! or explanatory purposes.
 #![allow(dead_code)]
 
+use crate::is_repetition;
+
 use super::super::parse_ranges;
 
 /// Sum all Part 1-invalid IDs by manual string splitting.
 pub fn sum_with_strings_part1(input: &str) -> u128 {
-    let ranges = parse_ranges(input);
+    let ranges = parse_ranges(input).expect("valid day 02 ranges");
     let mut total = 0u128;
     for (start, end) in ranges {
         if start > end {
@@ -28,30 +30,17 @@ pub fn sum_with_strings_part1(input: &str) -> u128 {
     total
 }
 
-/// Sum all Part 2-invalid IDs by checking every possible block length.
+/// Sum all Part 2-invalid IDs by asking whether each ID string is a repetition.
 pub fn sum_with_strings_part2(input: &str) -> u128 {
-    let ranges = parse_ranges(input);
+    let ranges = parse_ranges(input).expect("valid day 02 ranges");
     let mut total = 0u128;
     for (start, end) in ranges {
         if start > end {
             continue;
         }
-        'outer: for value in start..=end {
-            let s = value.to_string();
-            let len = s.len();
-            for block_len in 1..=(len / 2) {
-                if len % block_len != 0 {
-                    continue;
-                }
-                let repeats = len / block_len;
-                if repeats < 2 {
-                    continue;
-                }
-                let block = &s[..block_len];
-                if block.repeat(repeats) == s {
-                    total += value;
-                    continue 'outer;
-                }
+        for value in start..=end {
+            if is_repetition(&value.to_string()).is_some() {
+                total += value;
             }
         }
     }
@@ -81,7 +70,10 @@ mod tests {
     #[ignore]
     fn strings_solver_real_input() {
         let input = std::fs::read_to_string("inputs/day02.txt").expect("day02 input");
-        let expected: u128 = crate::days::day02::part1(&input).parse().expect("numeric");
+        let expected: u128 = crate::days::day02::part1(&input)
+            .unwrap()
+            .parse()
+            .expect("numeric");
         assert_eq!(sum_with_strings_part1(&input), expected);
     }
 
@@ -89,7 +81,10 @@ mod tests {
     #[ignore]
     fn strings_part2_real_input() {
         let input = std::fs::read_to_string("inputs/day02.txt").expect("day02 input");
-        let expected: u128 = crate::days::day02::part2(&input).parse().expect("numeric");
+        let expected: u128 = crate::days::day02::part2(&input)
+            .unwrap()
+            .parse()
+            .expect("numeric");
         assert_eq!(sum_with_strings_part2(&input), expected);
     }
 }
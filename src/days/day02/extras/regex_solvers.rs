@@ -10,11 +10,12 @@
 
 use regex::Regex;
 
-use super::super::{num_digits, parse_ranges};
+use super::super::parse_ranges;
+use crate::num_digits;
 
 /// Sum all Part 1-invalid IDs (duplicate halves) using regex matching.
 pub fn sum_with_regex(input: &str) -> u128 {
-    let ranges = parse_ranges(input);
+    let ranges = parse_ranges(input).expect("valid day 02 ranges");
     let max_end = ranges.iter().map(|&(_, hi)| hi).max().unwrap_or(0);
     if max_end == 0 {
         return 0;
@@ -56,7 +57,7 @@ pub fn sum_with_regex(input: &str) -> u128 {
 /// whether the entire string is captured by one block repeated two or more
 /// times. Use for explanations or small inputs only.
 pub fn sum_with_regex_part2(input: &str) -> u128 {
-    let ranges = parse_ranges(input);
+    let ranges = parse_ranges(input).expect("valid day 02 ranges");
     let max_end = ranges.iter().map(|&(_, hi)| hi).max().unwrap_or(0);
     if max_end == 0 {
         return 0;
@@ -128,6 +129,7 @@ mod tests {
         let input = fs::read_to_string("inputs/day02.txt").expect("day02 input");
         let regex_sum = sum_with_regex(&input);
         let fast_sum: u128 = crate::days::day02::part1(&input)
+            .unwrap()
             .parse()
             .expect("part1 numeric result");
         assert_eq!(regex_sum, fast_sum);
@@ -139,7 +141,10 @@ mod tests {
     fn regex_part2_real_input() {
         let input = fs::read_to_string("inputs/day02.txt").expect("day02 input");
         let regex_sum = sum_with_regex_part2(&input);
-        let fast_sum: u128 = part2(&input).parse().expect("part2 numeric result");
+        let fast_sum: u128 = part2(&input)
+            .unwrap()
+            .parse()
+            .expect("part2 numeric result");
         assert_eq!(regex_sum, fast_sum);
     }
 }
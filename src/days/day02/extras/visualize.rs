@@ -3,12 +3,12 @@
 //! The visualization focuses on how an input range contributes invalid IDs for
 //! each half-length `k`. For a textual overview, call `render_part1_report`.
 
-use crate::days::day02::{ceil_div, num_digits};
+use crate::{ceil_div, num_digits};
 
 /// Render a multiline string explaining which `x` values (half-blocks) fall
 /// inside each range for every valid `k`.
 pub fn render_part1_report(input: &str) -> String {
-    let ranges = super::super::parse_ranges(input);
+    let ranges = super::super::parse_ranges(input).expect("valid day 02 ranges");
     let mut out = String::new();
     for (idx, (start, end)) in ranges.iter().enumerate() {
         let header = format!("Range {}: {}-{}\n", idx + 1, start, end);
@@ -5,10 +5,11 @@
 //! blocks that are not multiples of a smaller repeating unit) are included,
 //! mirroring the main solver logic.
 
-use crate::days::day02::{ceil_div, num_digits, primitive_sum_for_len, sum_range};
+use crate::days::day02::primitive_sum_for_len;
+use crate::{ceil_div, num_digits, sum_range};
 
 pub fn render_part2_report(input: &str) -> String {
-    let ranges = super::super::parse_ranges(input);
+    let ranges = super::super::parse_ranges(input).expect("valid day 02 ranges");
     let max_end = ranges.iter().map(|&(_, hi)| hi).max().unwrap_or(0);
     if max_end == 0 {
         return "No ranges provided.".to_string();
@@ -20,56 +20,76 @@
 
 pub mod extras;
 
-use super::util;
+use super::util::{field_error, parse_field, PartResult};
+use super::Solver;
+use crate::{
+    ceil_div, digit_len_bounds, num_digits, pow10_table, repeat_multiplier, scaled_range,
+    sum_range, ParseError,
+};
 
-pub fn part1(input: &str) -> String {
-    let ranges = parse_ranges(input);
-    sum_invalid_ids(&ranges).to_string()
+pub fn part1(input: &str) -> PartResult {
+    let ranges = parse_ranges(input)?;
+    Ok(sum_invalid_ids(&ranges).to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let ranges = parse_ranges(input);
-    sum_invalid_ids_any_repeat(&ranges).to_string()
+pub fn part2(input: &str) -> PartResult {
+    let ranges = parse_ranges(input)?;
+    Ok(sum_invalid_ids_any_repeat(&ranges).to_string())
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day02")?;
-    if should_render_part1_visual() {
-        let report = extras::visualize::render_part1_report(&input);
-        println!("Day 02 Part 1 visualization:\n{}", report);
+pub struct Day02;
+
+impl Solver for Day02 {
+    fn prepare(&self, input: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if should_render_part1_visual() || should_render_part2_visual() {
+            // The visualizations assume well-formed ranges.
+            parse_ranges(input)?;
+        }
+        if should_render_part1_visual() {
+            let report = extras::visualize::render_part1_report(input);
+            println!("Day 02 Part 1 visualization:\n{}", report);
+        }
+        if should_render_part2_visual() {
+            let report = extras::visualize_part2::render_part2_report(input);
+            println!("Day 02 Part 2 visualization:\n{}", report);
+        }
+        Ok(())
     }
-    if should_render_part2_visual() {
-        let report = extras::visualize_part2::render_part2_report(&input);
-        println!("Day 02 Part 2 visualization:\n{}", report);
+
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
     }
-    println!(
-        "Day 02\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
 }
 
-fn parse_ranges(input: &str) -> Vec<(u128, u128)> {
-    input
-        .split(',')
-        .filter_map(|chunk| {
+fn parse_ranges(input: &str) -> Result<Vec<(u128, u128)>, ParseError> {
+    let mut ranges = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        for chunk in line.split(',') {
             let chunk = chunk.trim();
             if chunk.is_empty() {
-                return None;
+                continue;
             }
             let (lo, hi) = chunk
                 .split_once('-')
-                .unwrap_or_else(|| panic!("invalid range segment: {chunk}"));
-            let start: u128 = lo.trim().parse().expect("start id");
-            let end: u128 = hi.trim().parse().expect("end id");
-            assert!(
-                start <= end,
-                "range start must be <= end (found {start}-{end})"
-            );
-            Some((start, end))
-        })
-        .collect()
+                .ok_or_else(|| field_error(line, i + 1, chunk, "expected a `start-end` range"))?;
+            let start: u128 = parse_field(line, i + 1, lo, "start id")?;
+            let end: u128 = parse_field(line, i + 1, hi, "end id")?;
+            if start > end {
+                return Err(field_error(
+                    line,
+                    i + 1,
+                    chunk,
+                    "range start must be <= end",
+                ));
+            }
+            ranges.push((start, end));
+        }
+    }
+    Ok(ranges)
 }
 
 fn sum_invalid_ids(ranges: &[(u128, u128)]) -> u128 {
@@ -80,33 +100,20 @@ fn sum_invalid_ids(ranges: &[(u128, u128)]) -> u128 {
         return 0;
     }
 
-    let mut pow10 = vec![1u128; max_k + 1];
-    for i in 1..=max_k {
-        pow10[i] = pow10[i - 1] * 10;
-    }
-
     ranges
         .iter()
-        .map(|&(start, end)| sum_invalid_in_range(start, end, max_k, &pow10))
+        .map(|&(start, end)| sum_invalid_in_range(start, end, max_k))
         .sum()
 }
 
-fn sum_invalid_in_range(start: u128, end: u128, max_k: usize, pow10: &[u128]) -> u128 {
+fn sum_invalid_in_range(start: u128, end: u128, max_k: usize) -> u128 {
     let mut total = 0u128;
     for k in 1..=max_k {
-        let pow = pow10[k];
-        let mult = pow + 1;
-        let min_x = if k == 1 { 1 } else { pow10[k - 1] };
-        let max_x = pow - 1;
-
-        let x_lo = min_x.max(ceil_div(start, mult));
-        let x_hi = max_x.min(end / mult);
-        if x_lo > x_hi {
-            continue;
+        let mult = repeat_multiplier(k, 2);
+        let (min_x, max_x) = digit_len_bounds(k);
+        if let Some((x_lo, x_hi)) = scaled_range(mult, start, end, min_x, max_x) {
+            total += sum_range(x_lo, x_hi) * mult;
         }
-        let count = x_hi - x_lo + 1;
-        let sum_x = (x_lo + x_hi) * count / 2;
-        total += sum_x * mult;
     }
     total
 }
@@ -117,10 +124,7 @@ fn sum_invalid_ids_any_repeat(ranges: &[(u128, u128)]) -> u128 {
         return 0;
     }
     let max_digits = num_digits(max_end);
-    let mut pow10 = vec![1u128; max_digits + 1];
-    for i in 1..pow10.len() {
-        pow10[i] = pow10[i - 1] * 10;
-    }
+    let pow10 = pow10_table(max_digits);
     let mut divisors = vec![Vec::new(); max_digits + 1];
     for len in 1..=max_digits {
         for d in 1..len {
@@ -211,31 +215,6 @@ fn primitive_sum_for_len(
     total_sum - subtract
 }
 
-fn sum_range(lo: u128, hi: u128) -> u128 {
-    let count = hi - lo + 1;
-    (lo + hi) * count / 2
-}
-
-fn ceil_div(a: u128, b: u128) -> u128 {
-    if a == 0 {
-        0
-    } else {
-        ((a - 1) / b) + 1
-    }
-}
-
-fn num_digits(mut n: u128) -> usize {
-    if n == 0 {
-        return 1;
-    }
-    let mut digits = 0;
-    while n > 0 {
-        n /= 10;
-        digits += 1;
-    }
-    digits
-}
-
 fn should_render_part1_visual() -> bool {
     std::env::var("DAY02_PART1_VIS")
         .map(|v| {
@@ -271,7 +250,7 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         } else {
@@ -281,17 +260,9 @@ mod tests {
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
     }
-
-    #[test]
-    fn ceil_div_handles_zero() {
-        assert_eq!(ceil_div(0, 5), 0);
-        assert_eq!(ceil_div(1, 5), 1);
-        assert_eq!(ceil_div(9, 5), 2);
-        assert_eq!(ceil_div(10, 5), 2);
-    }
 }
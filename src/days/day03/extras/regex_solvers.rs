@@ -59,7 +59,7 @@ mod tests {
     fn regex_part1_real_input() {
         let input = fs::read_to_string("inputs/day03.txt").expect("day03 input");
         let regex_sum = sum_with_regex_part1(&input);
-        let fast: u64 = crate::days::day03::part1(&input).parse().unwrap();
+        let fast: u64 = crate::days::day03::part1(&input).unwrap().parse().unwrap();
         assert_eq!(regex_sum, fast);
     }
 }
@@ -12,24 +12,43 @@
 
 pub mod extras;
 
-use super::util;
+use super::util::{field_error, PartResult};
+use super::Solver;
+use crate::ParseError;
 
-pub fn part1(input: &str) -> String {
-    input
-        .lines()
-        .filter(|line| !line.trim().is_empty())
+pub fn part1(input: &str) -> PartResult {
+    let banks = parse_banks(input)?;
+    Ok(banks
+        .into_iter()
         .map(max_pair_value)
         .sum::<u64>()
-        .to_string()
+        .to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    input
-        .lines()
-        .filter(|line| !line.trim().is_empty())
+pub fn part2(input: &str) -> PartResult {
+    let banks = parse_banks(input)?;
+    Ok(banks
+        .into_iter()
         .map(max_subsequence_value)
         .sum::<u64>()
-        .to_string()
+        .to_string())
+}
+
+/// Non-blank lines, checked to contain only digits.
+fn parse_banks(input: &str) -> Result<Vec<&str>, ParseError> {
+    let mut banks = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let bank = line.trim();
+        if bank.is_empty() {
+            continue;
+        }
+        if let Some(pos) = bank.find(|c: char| !c.is_ascii_digit()) {
+            let bad = &bank[pos..pos + bank[pos..].chars().next().map_or(1, char::len_utf8)];
+            return Err(field_error(line, i + 1, bad, "expected a digit"));
+        }
+        banks.push(bank);
+    }
+    Ok(banks)
 }
 
 fn max_pair_value(line: &str) -> u64 {
@@ -71,14 +90,16 @@ fn max_subsequence_value(line: &str) -> u64 {
     keep.iter().fold(0u64, |acc, &d| acc * 10 + d as u64)
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day03")?;
-    println!(
-        "Day 03\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
+pub struct Day03;
+
+impl Solver for Day03 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -92,14 +113,14 @@ mod tests {
     ));
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
     }
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
@@ -1,135 +1,59 @@
 //! AoC Day 04 scaffold
 
-use std::collections::VecDeque;
-
-use super::util;
-
-fn parse_grid(input: &str) -> Vec<Vec<bool>> {
-    input
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .map(|line| line.chars().map(|c| c == '@').collect())
-        .collect()
-}
-
-fn count_neighbors(grid: &[Vec<bool>], y: usize, x: usize) -> u8 {
-    let mut neighbors = 0;
-    for dy in -1isize..=1 {
-        for dx in -1isize..=1 {
-            if dx == 0 && dy == 0 {
-                continue;
-            }
-            let ny = y as isize + dy;
-            let nx = x as isize + dx;
-            if ny < 0 || nx < 0 {
-                continue;
-            }
-            let ny = ny as usize;
-            let nx = nx as usize;
-            if ny >= grid.len() || nx >= grid[ny].len() {
-                continue;
-            }
-            if grid[ny][nx] {
-                neighbors += 1;
+use super::util::{field_error, PartResult};
+use super::Solver;
+use crate::{GridAutomaton, ParseError};
+
+/// `@` marks a roll of paper, `.` an empty spot.
+fn parse_grid(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let mut grid = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let row = line.trim();
+        if row.is_empty() {
+            continue;
+        }
+        if let Some(pos) = row.find(|c| c != '@' && c != '.') {
+            let bad = &row[pos..pos + row[pos..].chars().next().map_or(1, char::len_utf8)];
+            return Err(field_error(line, i + 1, bad, "expected '@' or '.'"));
+        }
+        let cells: Vec<bool> = row.chars().map(|c| c == '@').collect();
+        if let Some(first) = grid.first().map(Vec::len) {
+            if cells.len() != first {
+                let message = format!("row has {} cells, expected {}", cells.len(), first);
+                return Err(field_error(line, i + 1, row, message));
             }
         }
+        grid.push(cells);
     }
-    neighbors
+    Ok(grid)
 }
 
-pub fn part1(input: &str) -> String {
-    let grid = parse_grid(input);
-    if grid.is_empty() {
-        return "0".into();
-    }
-
-    let mut accessible = 0usize;
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if grid[y][x] && count_neighbors(&grid, y, x) < 4 {
-                accessible += 1;
-            }
-        }
-    }
+/// A roll is accessible (and removable) with fewer than 4 rolls around it.
+fn remove_accessible(roll: bool, neighbors: usize) -> bool {
+    roll && neighbors >= 4
+}
 
-    accessible.to_string()
+pub fn part1(input: &str) -> PartResult {
+    let mut rolls = GridAutomaton::new(parse_grid(input)?, |&roll| roll);
+    Ok(rolls.step(remove_accessible).to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let mut grid = parse_grid(input);
-    if grid.is_empty() {
-        return "0".into();
-    }
+pub fn part2(input: &str) -> PartResult {
+    let mut rolls = GridAutomaton::new(parse_grid(input)?, |&roll| roll);
+    let (_, removed) = rolls.run_until_stable(remove_accessible);
+    Ok(removed.to_string())
+}
 
-    let mut neighbor_counts: Vec<Vec<u8>> = (0..grid.len())
-        .map(|y| {
-            (0..grid[y].len())
-                .map(|x| {
-                    if grid[y][x] {
-                        count_neighbors(&grid, y, x)
-                    } else {
-                        0
-                    }
-                })
-                .collect()
-        })
-        .collect();
+pub struct Day04;
 
-    let mut queue = VecDeque::new();
-    for y in 0..grid.len() {
-        for x in 0..grid[y].len() {
-            if grid[y][x] && neighbor_counts[y][x] < 4 {
-                queue.push_back((y, x));
-            }
-        }
+impl Solver for Day04 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
     }
 
-    let mut removed = 0usize;
-    while let Some((y, x)) = queue.pop_front() {
-        if !grid[y][x] || neighbor_counts[y][x] >= 4 {
-            continue;
-        }
-
-        grid[y][x] = false;
-        removed += 1;
-
-        for dy in -1isize..=1 {
-            for dx in -1isize..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-                let ny = y as isize + dy;
-                let nx = x as isize + dx;
-                if ny < 0 || nx < 0 {
-                    continue;
-                }
-                let ny = ny as usize;
-                let nx = nx as usize;
-                if ny >= grid.len() || nx >= grid[ny].len() {
-                    continue;
-                }
-                if grid[ny][nx] {
-                    // Safe because neighbor counts only track cells with rolls.
-                    neighbor_counts[ny][nx] -= 1;
-                    if neighbor_counts[ny][nx] < 4 {
-                        queue.push_back((ny, nx));
-                    }
-                }
-            }
-        }
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
     }
-
-    removed.to_string()
-}
-
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day04")?;
-    println!(
-        "Day 04\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
 }
 
 #[cfg(test)]
@@ -143,14 +67,14 @@ mod tests {
     ));
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
     }
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
@@ -1,40 +1,35 @@
 //! AoC Day 05
 
-use super::util;
+use super::util::{field_error, parse_field, PartResult};
+use super::Solver;
+use crate::ParseError;
 
-fn parse_database(input: &str) -> (Vec<(u64, u64)>, Vec<u64>) {
+/// The fresh-ID ranges (first blank-line-separated block) and the
+/// available ingredient IDs (the rest).
+type Database = (Vec<(u64, u64)>, Vec<u64>);
+
+fn parse_database(input: &str) -> Result<Database, ParseError> {
     let mut ranges = Vec::new();
     let mut ids = Vec::new();
-    let mut reading_ranges = true;
-
-    for line in input.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            if reading_ranges && !ranges.is_empty() {
-                reading_ranges = false;
-            }
+    let mut in_ranges = true;
+    for (i, line) in input.lines().enumerate() {
+        let text = line.trim();
+        if text.is_empty() {
+            in_ranges &= ranges.is_empty();
             continue;
         }
-
-        if reading_ranges {
-            let (start, end) = trimmed
+        if in_ranges {
+            let (start, end) = text
                 .split_once('-')
-                .expect("Range line must contain a '-' separator");
-            let start = start.trim().parse::<u64>().expect("Invalid range start");
-            let end = end.trim().parse::<u64>().expect("Invalid range end");
-            let (start, end) = if start <= end {
-                (start, end)
-            } else {
-                (end, start)
-            };
-            ranges.push((start, end));
+                .ok_or_else(|| field_error(line, i + 1, text, "expected a `start-end` range"))?;
+            let start: u64 = parse_field(line, i + 1, start, "range start")?;
+            let end: u64 = parse_field(line, i + 1, end, "range end")?;
+            ranges.push((start.min(end), start.max(end)));
         } else {
-            let id = trimmed.parse::<u64>().expect("Invalid ingredient ID");
-            ids.push(id);
+            ids.push(parse_field(line, i + 1, text, "ingredient ID")?);
         }
     }
-
-    (ranges, ids)
+    Ok((ranges, ids))
 }
 
 fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
@@ -61,10 +56,10 @@ fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
     merged
 }
 
-pub fn part1(input: &str) -> String {
-    let (ranges, mut ids) = parse_database(input);
+pub fn part1(input: &str) -> PartResult {
+    let (ranges, mut ids) = parse_database(input)?;
     if ranges.is_empty() || ids.is_empty() {
-        return "0".into();
+        return Ok("0".into());
     }
 
     let ranges = merge_ranges(ranges);
@@ -85,13 +80,13 @@ pub fn part1(input: &str) -> String {
         }
     }
 
-    fresh.to_string()
+    Ok(fresh.to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let (ranges, _) = parse_database(input);
+pub fn part2(input: &str) -> PartResult {
+    let (ranges, _) = parse_database(input)?;
     if ranges.is_empty() {
-        return "0".into();
+        return Ok("0".into());
     }
 
     let merged = merge_ranges(ranges);
@@ -99,17 +94,19 @@ pub fn part2(input: &str) -> String {
         .into_iter()
         .map(|(start, end)| end.saturating_sub(start).saturating_add(1))
         .sum();
-    total.to_string()
+    Ok(total.to_string())
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day05")?;
-    println!(
-        "Day 05\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
+pub struct Day05;
+
+impl Solver for Day05 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -124,7 +121,7 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
@@ -132,7 +129,7 @@ mod tests {
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
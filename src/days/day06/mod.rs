@@ -1,192 +1,191 @@
 //! AoC Day 06 — Trash Compactor
-use super::util;
+use std::ops::Range;
 
-pub fn part1(input: &str) -> String {
-    let Some(grid) = build_grid(input) else {
-        return "0".into();
+use super::util::PartResult;
+use super::Solver;
+use crate::{column_blocks, normalize_grid, ParseError};
+
+/// Tab stops used when a worksheet was saved with tabs instead of spaces.
+const TAB_WIDTH: usize = 8;
+
+pub fn part1(input: &str) -> PartResult {
+    let Some(sheet) = Worksheet::parse(input) else {
+        return Ok("0".into());
     };
-    solve_row_major(&grid).to_string()
+    Ok(sheet.solve_row_major()?.to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let Some(grid) = build_grid(input) else {
-        return "0".into();
+pub fn part2(input: &str) -> PartResult {
+    let Some(sheet) = Worksheet::parse(input) else {
+        return Ok("0".into());
     };
-    solve_column_major(&grid).to_string()
+    Ok(sheet.solve_column_major()?.to_string())
 }
 
-fn solve_row_major(grid: &[Vec<char>]) -> u128 {
-    let width = grid[0].len();
-    let last_row = grid.len() - 1;
-    let mut total: u128 = 0;
-    let mut col = 0;
-    while col < width {
-        while col < width && is_blank_column(grid, col) {
-            col += 1;
-        }
-        if col == width {
-            break;
-        }
+/// Which row holds the operators; `DAY06_OPERATOR_ROW=first` for
+/// worksheets that put them above the numbers instead of below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OperatorRow {
+    First,
+    Last,
+}
 
-        let start = col;
-        while col < width && !is_blank_column(grid, col) {
-            col += 1;
-        }
-        let end = col;
+fn operator_row() -> Result<OperatorRow, String> {
+    let Ok(value) = std::env::var("DAY06_OPERATOR_ROW") else {
+        return Ok(OperatorRow::Last);
+    };
+    match value.trim().to_ascii_lowercase().as_str() {
+        "" | "last" => Ok(OperatorRow::Last),
+        "first" => Ok(OperatorRow::First),
+        other => Err(format!(
+            "DAY06_OPERATOR_ROW must be `first` or `last`, not `{other}`"
+        )),
+    }
+}
 
-        let mut operands: Vec<u128> = Vec::new();
-        for row in 0..last_row {
-            let mut slice = String::new();
-            for c in start..end {
-                slice.push(grid[row][c]);
-            }
-            let trimmed = slice.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let value = trimmed
-                .parse::<u128>()
-                .expect("invalid number in worksheet");
-            operands.push(value);
-        }
+/// Error at 0-based worksheet `row`/`col`.
+fn grid_error(row: usize, col: usize, token: &str, message: impl Into<String>) -> ParseError {
+    ParseError {
+        line: row + 1,
+        column: col + 1,
+        token: token.to_string(),
+        message: message.into(),
+    }
+}
 
-        assert!(!operands.is_empty(), "problem missing operands");
+/// A normalized worksheet: a rectangular grid, the index of its operator
+/// row and the column range of each problem.
+struct Worksheet {
+    grid: Vec<Vec<char>>,
+    op_row: usize,
+    problems: Vec<Range<usize>>,
+}
 
-        let op_char = (start..end)
-            .find_map(|c| {
-                let ch = grid[last_row][c];
-                if ch.is_ascii_whitespace() {
-                    None
-                } else {
-                    Some(ch)
-                }
-            })
-            .expect("missing operator");
+impl Worksheet {
+    /// `None` when the input has no non-blank line.
+    fn parse(input: &str) -> Option<Self> {
+        Self::with_operator_row(input, operator_row().unwrap_or(OperatorRow::Last))
+    }
 
-        let problem_total = match op_char {
-            '+' => operands.iter().sum::<u128>(),
-            '*' => operands.iter().product::<u128>(),
-            other => panic!("unexpected operator: {other}"),
+    fn with_operator_row(input: &str, position: OperatorRow) -> Option<Self> {
+        let grid = normalize_grid(input, TAB_WIDTH);
+        let is_blank = |row: &Vec<char>| row.iter().all(|c| c.is_whitespace());
+        let op_row = match position {
+            OperatorRow::First => grid.iter().position(|row| !is_blank(row))?,
+            OperatorRow::Last => grid.len().checked_sub(1)?,
         };
-        total += problem_total;
+        let problems = column_blocks(&grid);
+        Some(Worksheet {
+            grid,
+            op_row,
+            problems,
+        })
     }
 
-    total
-}
-
-fn solve_column_major(grid: &[Vec<char>]) -> u128 {
-    let width = grid[0].len();
-    let last_row = grid.len() - 1;
-    let mut total: u128 = 0;
-    let mut col = width;
+    /// Indices of the rows holding operand digits, top to bottom.
+    fn operand_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.grid.len()).filter(move |&r| r != self.op_row)
+    }
 
-    while col > 0 {
-        col -= 1;
-        if is_blank_column(grid, col) {
-            continue;
+    /// Operator of the problem spanning `cols`.
+    fn operator(&self, cols: &Range<usize>) -> Result<char, ParseError> {
+        let row = self.op_row;
+        let (col, op) = cols
+            .clone()
+            .map(|c| (c, self.grid[row][c]))
+            .find(|(_, ch)| !ch.is_whitespace())
+            .ok_or_else(|| grid_error(row, cols.start, "", "problem is missing its operator"))?;
+        match op {
+            '+' | '*' => Ok(op),
+            other => Err(grid_error(
+                row,
+                col,
+                &other.to_string(),
+                "operator must be '+' or '*'",
+            )),
         }
+    }
 
-        let mut start = col;
-        while start > 0 {
-            let prev = start - 1;
-            if is_blank_column(grid, prev) {
-                break;
+    /// Each problem's numbers are written across the rows.
+    fn solve_row_major(&self) -> Result<u128, ParseError> {
+        let mut total: u128 = 0;
+        for cols in &self.problems {
+            let mut operands: Vec<u128> = Vec::new();
+            for row in self.operand_rows() {
+                let slice: String = self.grid[row][cols.clone()].iter().collect();
+                if slice.trim().is_empty() {
+                    continue;
+                }
+                operands.push(operand(&slice, row, cols.start)?);
+            }
+            if operands.is_empty() {
+                return Err(grid_error(0, cols.start, "", "problem has no operands"));
             }
-            start = prev;
+            total += apply(self.operator(cols)?, &operands);
         }
-        let end = col + 1;
-
-        let op_char = (start..end)
-            .find_map(|c| {
-                let ch = grid[last_row][c];
-                if ch.is_ascii_whitespace() {
-                    None
-                } else {
-                    Some(ch)
+        Ok(total)
+    }
+
+    /// Each problem's numbers are written down the columns, read right to left.
+    fn solve_column_major(&self) -> Result<u128, ParseError> {
+        let mut total: u128 = 0;
+        for cols in self.problems.iter().rev() {
+            let op = self.operator(cols)?;
+            let mut operands: Vec<u128> = Vec::new();
+            for column in cols.clone().rev() {
+                let digits: String = self.operand_rows().map(|r| self.grid[r][column]).collect();
+                if digits.trim().is_empty() {
+                    continue;
                 }
-            })
-            .expect("missing operator");
-
-        let mut operands: Vec<u128> = Vec::new();
-        for column in (start..end).rev() {
-            let mut digits = String::new();
-            for row in 0..last_row {
-                digits.push(grid[row][column]);
+                // Digits run down the column, so a bad one is reported by row.
+                let trimmed = digits.trim();
+                let value = trimmed.parse().map_err(|e| {
+                    let lead = digits.len() - digits.trim_start().len();
+                    let row = self.operand_rows().nth(lead).unwrap_or(0);
+                    grid_error(row, column, trimmed, format!("invalid number: {e}"))
+                })?;
+                operands.push(value);
             }
-            let trimmed = digits.trim();
-            if trimmed.is_empty() {
-                continue;
+            if operands.is_empty() {
+                return Err(grid_error(0, cols.start, "", "problem has no operands"));
             }
-            let value = trimmed
-                .parse::<u128>()
-                .expect("invalid number in worksheet column");
-            operands.push(value);
+            total += apply(op, &operands);
         }
-
-        assert!(!operands.is_empty(), "problem missing operands");
-
-        let problem_total = match op_char {
-            '+' => operands.iter().sum::<u128>(),
-            '*' => operands.iter().product::<u128>(),
-            other => panic!("unexpected operator: {other}"),
-        };
-        total += problem_total;
-
-        col = start;
+        Ok(total)
     }
-
-    total
 }
 
-fn is_blank_column(rows: &[Vec<char>], col: usize) -> bool {
-    rows.iter()
-        .all(|row| row.get(col).map_or(true, |ch| ch.is_ascii_whitespace()))
+/// Parse one operand read from `digits`, which starts at `row`/`col`.
+fn operand(digits: &str, row: usize, col: usize) -> Result<u128, ParseError> {
+    let lead = digits.len() - digits.trim_start().len();
+    let trimmed = digits.trim();
+    trimmed
+        .parse()
+        .map_err(|e| grid_error(row, col + lead, trimmed, format!("invalid number: {e}")))
 }
 
-fn build_grid(input: &str) -> Option<Vec<Vec<char>>> {
-    if input.trim().is_empty() {
-        return None;
+fn apply(op: char, operands: &[u128]) -> u128 {
+    match op {
+        '+' => operands.iter().sum(),
+        _ => operands.iter().product(),
     }
+}
 
-    let mut rows: Vec<&str> = input.lines().collect();
-    while rows
-        .last()
-        .map(|line| line.trim_end().is_empty())
-        .unwrap_or(false)
-    {
-        rows.pop();
-    }
+pub struct Day06;
 
-    if rows.is_empty() {
-        return None;
+impl Solver for Day06 {
+    fn prepare(&self, _input: &str) -> Result<(), Box<dyn std::error::Error>> {
+        operator_row()?;
+        Ok(())
     }
 
-    let width = rows.iter().map(|line| line.len()).max().unwrap_or(0);
-    if width == 0 {
-        return None;
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
     }
 
-    Some(
-        rows.into_iter()
-            .map(|line| {
-                let mut chars: Vec<char> = line.chars().collect();
-                while chars.len() < width {
-                    chars.push(' ');
-                }
-                chars
-            })
-            .collect(),
-    )
-}
-
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day06")?;
-    println!(
-        "Day 06\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -202,7 +201,7 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
@@ -210,9 +209,28 @@ mod tests {
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
     }
+
+    #[test]
+    fn tabs_trimmed_lines_and_operators_on_top() {
+        // The example indented by a tab, with trailing spaces trimmed.
+        let tabbed: String = EXAMPLE
+            .lines()
+            .map(|line| format!("\t{}\n", line.trim_end()))
+            .collect();
+        let sheet = Worksheet::with_operator_row(&tabbed, OperatorRow::Last).unwrap();
+        assert_eq!(sheet.problems.len(), 4);
+        assert_eq!(sheet.solve_row_major().unwrap(), 4277556);
+
+        let lines: Vec<&str> = EXAMPLE.lines().collect();
+        let (ops, numbers) = lines.split_last().unwrap();
+        let flipped = format!("{}\n{}\n", ops, numbers.join("\n"));
+        let sheet = Worksheet::with_operator_row(&flipped, OperatorRow::First).unwrap();
+        assert_eq!(sheet.solve_row_major().unwrap(), 4277556);
+        assert_eq!(sheet.solve_column_major().unwrap(), 3263827);
+    }
 }
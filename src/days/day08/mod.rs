@@ -1,36 +1,62 @@
 //! AoC Day 08 — Playground
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 
-use super::util;
+use super::util::{parse_field, PartResult};
+use super::Solver;
+use crate::{parse_points3, profile, ParseError, Point3, SpatialHash3};
 
+/// Pairs part 1 connects unless the input's first line says otherwise.
 const PAIRS_TO_CONNECT: usize = 1000;
 
-pub fn part1(input: &str) -> String {
-    let points = parse_points(input);
+pub fn part1(input: &str) -> PartResult {
+    let pairs = pairs_to_connect(input)?;
+    let points = {
+        let _span = profile::span("parse");
+        parse_points(input)?
+    };
     if points.is_empty() {
-        return "0".into();
+        return Ok("0".into());
     }
-    solve(&points, PAIRS_TO_CONNECT).to_string()
+    Ok(solve(&points, pairs).to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let points = parse_points(input);
+/// Pairs to connect in part 1: an optional `# pairs: N` first line (the
+/// example uses 10), else [`PAIRS_TO_CONNECT`]. Being a `#` comment, the
+/// line is otherwise ignored by the point parser.
+fn pairs_to_connect(input: &str) -> Result<usize, ParseError> {
+    let first = input.lines().next().unwrap_or("");
+    let directive = first
+        .trim()
+        .strip_prefix('#')
+        .and_then(|c| c.trim().strip_prefix("pairs:"));
+    match directive {
+        Some(n) => parse_field(first, 1, n, "pair count"),
+        None => Ok(PAIRS_TO_CONNECT),
+    }
+}
+
+pub fn part2(input: &str) -> PartResult {
+    let points = {
+        let _span = profile::span("parse");
+        parse_points(input)?
+    };
     if points.is_empty() {
-        return "0".into();
+        return Ok("0".into());
     }
-    last_connection_product(&points).to_string()
+    Ok(last_connection_product(&points).to_string())
 }
 
-fn solve(points: &[[i64; 3]], pairs_to_connect: usize) -> u128 {
+/// Part 1 for any pair count: connect the `pairs_to_connect` closest pairs
+/// and multiply the sizes of the three largest circuits.
+pub fn solve(points: &[[i64; 3]], pairs_to_connect: usize) -> u128 {
     let n = points.len();
     if n == 0 {
         return 0;
     }
     let max_edges = n.saturating_sub(1) * n / 2;
-    let mut edges = shortest_edges(points, pairs_to_connect.min(max_edges));
-    edges.sort_unstable_by(|a, b| a.dist.cmp(&b.dist));
+    let edges = shortest_edges(points, pairs_to_connect.min(max_edges));
 
+    let _span = profile::span("circuits");
     let mut dsu = DisjointSet::new(n);
     for edge in edges {
         dsu.union(edge.a, edge.b);
@@ -51,56 +77,64 @@ struct Edge {
     b: usize,
 }
 
+/// The `limit` shortest pairs, shortest first (ties by index). Only pairs
+/// within a search radius are generated; the radius doubles until at least
+/// `limit` pairs fall inside it, at which point no pair left out can beat them.
 fn shortest_edges(points: &[[i64; 3]], limit: usize) -> Vec<Edge> {
     if limit == 0 {
         return Vec::new();
     }
-    let mut heap: BinaryHeap<HeapEdge> = BinaryHeap::new();
-    let n = points.len();
-    for i in 0..n {
-        for j in (i + 1)..n {
-            let dist = distance_sq(points[i], points[j]);
-            let candidate = HeapEdge { dist, a: i, b: j };
-            if heap.len() < limit {
-                heap.push(candidate);
-            } else if let Some(top) = heap.peek() {
-                if candidate.dist < top.dist {
-                    heap.pop();
-                    heap.push(candidate);
-                }
-            }
+    let _span = profile::span("shortest edges");
+    // Aim for twice the pairs needed so one round usually suffices.
+    let mut r = initial_radius(points, 2 * limit);
+    let mut edges = loop {
+        let edges = edges_within(points, r);
+        if edges.len() >= limit {
+            break edges;
         }
-    }
-    heap.into_sorted_vec()
-        .into_iter()
-        .map(|h| Edge {
-            dist: h.dist,
-            a: h.a,
-            b: h.b,
-        })
-        .collect()
-}
-
-#[derive(Clone, Copy, Eq, PartialEq)]
-struct HeapEdge {
-    dist: i128,
-    a: usize,
-    b: usize,
+        r *= 2;
+    };
+    edges.sort_unstable_by(|x, y| (x.dist, x.a, x.b).cmp(&(y.dist, y.a, y.b)));
+    edges.truncate(limit);
+    edges
 }
 
-impl Ord for HeapEdge {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.dist.cmp(&other.dist) {
-            Ordering::Equal => self.a.cmp(&other.a).then(self.b.cmp(&other.b)),
-            ord => ord,
+/// Every pair at distance at most `r`, found by bucketing the points into a
+/// spatial hash with cells of side `r`.
+fn edges_within(points: &[[i64; 3]], r: i64) -> Vec<Edge> {
+    let at = |p: [i64; 3]| Point3::new(p[0], p[1], p[2]);
+    let mut hash = SpatialHash3::new(r);
+    for (i, &p) in points.iter().enumerate() {
+        hash.insert(at(p), i);
+    }
+    let mut edges = Vec::new();
+    for (a, &p) in points.iter().enumerate() {
+        for (_, &b) in hash.within(at(p), r) {
+            if b > a {
+                edges.push(Edge {
+                    dist: distance_sq(p, points[b]),
+                    a,
+                    b,
+                });
+            }
         }
     }
+    edges
 }
 
-impl PartialOrd for HeapEdge {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+/// Radius at which about `pairs` pairs would be expected if the points were
+/// spread evenly over their bounding box; a starting guess for the search.
+fn initial_radius(points: &[[i64; 3]], pairs: usize) -> i64 {
+    let extent = |k: usize| {
+        let (lo, hi) = points.iter().fold((i64::MAX, i64::MIN), |(lo, hi), p| {
+            (lo.min(p[k]), hi.max(p[k]))
+        });
+        (hi - lo + 1) as f64
+    };
+    let volume = extent(0) * extent(1) * extent(2);
+    let n = points.len() as f64;
+    let ball = 2.0 * pairs as f64 / (n * n) * volume;
+    ((ball * 3.0 / (4.0 * std::f64::consts::PI)).cbrt().ceil() as i64).max(1)
 }
 
 fn distance_sq(a: [i64; 3], b: [i64; 3]) -> i128 {
@@ -155,65 +189,50 @@ impl DisjointSet {
     }
 }
 
-fn parse_points(input: &str) -> Vec<[i64; 3]> {
-    input
-        .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| {
-            let parts: Vec<_> = line.split(',').map(str::trim).collect();
-            assert!(parts.len() == 3, "invalid coordinate: {line}");
-            let x = parts[0].parse().expect("invalid x");
-            let y = parts[1].parse().expect("invalid y");
-            let z = parts[2].parse().expect("invalid z");
-            [x, y, z]
-        })
-        .collect()
+/// Junction box positions, one `x,y,z` per line.
+pub fn parse_points(input: &str) -> Result<Vec<[i64; 3]>, ParseError> {
+    Ok(parse_points3(input)?
+        .into_iter()
+        .map(|p| [p.x, p.y, p.z])
+        .collect())
 }
 
+/// Product of the x coordinates joined by the edge that finally connects
+/// every point (Kruskal). Like [`shortest_edges`] it only looks at pairs
+/// within a radius, doubling it until those pairs alone connect everything.
 fn last_connection_product(points: &[[i64; 3]]) -> i128 {
-    let mut edges = all_edges(points);
-    edges.sort_unstable_by(|a, b| a.dist.cmp(&b.dist));
-    let mut dsu = DisjointSet::new(points.len());
-    let mut last_edge: Option<&Edge> = None;
-    let mut components = points.len();
-    for edge in edges.iter() {
-        if dsu.union(edge.a, edge.b) {
-            last_edge = Some(edge);
-            components -= 1;
-            if components == 1 {
-                break;
+    let mut r = initial_radius(points, 2 * points.len());
+    loop {
+        let mut edges = edges_within(points, r);
+        edges.sort_unstable_by(|x, y| (x.dist, x.a, x.b).cmp(&(y.dist, y.a, y.b)));
+        let mut dsu = DisjointSet::new(points.len());
+        let mut components = points.len();
+        for edge in &edges {
+            if dsu.union(edge.a, edge.b) {
+                components -= 1;
+                if components == 1 {
+                    return (points[edge.a][0] as i128) * (points[edge.b][0] as i128);
+                }
             }
         }
+        assert!(
+            edges.len() < points.len() * (points.len() - 1) / 2,
+            "points should form a circuit"
+        );
+        r *= 2;
     }
-    let edge = last_edge.expect("points should form a circuit");
-    let product = (points[edge.a][0] as i128) * (points[edge.b][0] as i128);
-    product
 }
 
-fn all_edges(points: &[[i64; 3]]) -> Vec<Edge> {
-    let n = points.len();
-    let mut edges = Vec::with_capacity(n * (n - 1) / 2);
-    for i in 0..n {
-        for j in (i + 1)..n {
-            edges.push(Edge {
-                dist: distance_sq(points[i], points[j]),
-                a: i,
-                b: j,
-            });
-        }
+pub struct Day08;
+
+impl Solver for Day08 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
     }
-    edges
-}
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day08")?;
-    println!(
-        "Day 08\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
+    }
 }
 
 #[cfg(test)]
@@ -229,16 +248,17 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let points = parse_points(EXAMPLE);
-        let got = solve(&points, 10);
+        // The example's `# pairs: 10` header picks the pair count.
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
-            assert_eq!(got.to_string(), exp);
+            assert_eq!(got, exp);
         }
+        assert!(part1("# pairs: ten\n1,2,3\n").is_err());
     }
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE);
+        let got = part2(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART2 {
             assert_eq!(got, exp);
         }
@@ -1,126 +1,64 @@
 //! AoC Day 11 — Reactor
-use std::collections::{HashMap, HashSet};
+use super::util::{field_error, PartResult};
+use super::Solver;
+use crate::{count_paths_dag, parse_key_list, Adjacency, ParseError};
 
-use super::util;
-
-pub fn part1(input: &str) -> String {
-    let graph = parse_graph(input);
-    let mut memo = HashMap::new();
-    let mut visiting = HashSet::new();
-    count_paths("you", "out", &graph, &mut memo, &mut visiting).to_string()
+pub fn part1(input: &str) -> PartResult {
+    let graph = parse_graph(input)?;
+    Ok(count_paths(&graph, "you", "out", &[]).to_string())
 }
 
-pub fn part2(input: &str) -> String {
-    let graph = parse_graph(input);
-    let mut memo = HashMap::new();
-    let mut visiting = HashSet::new();
-    let counts = count_paths_with_requirements(
-        "svr",
-        "out",
-        &graph,
-        &mut memo,
-        &mut visiting,
-        required_mask,
-    );
-    counts[3].to_string()
+pub fn part2(input: &str) -> PartResult {
+    let graph = parse_graph(input)?;
+    Ok(count_paths(&graph, "svr", "out", &["dac", "fft"]).to_string())
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day11")?;
-    println!(
-        "Day 11\nPart 1: {}\nPart 2: {}",
-        part1(&input),
-        part2(&input)
-    );
-    Ok(())
+/// Paths from `src` to `dst` that pass through every device in `required`.
+fn count_paths(graph: &Adjacency<String>, src: &str, dst: &str, required: &[&str]) -> u128 {
+    let (adj, names, ids) = graph.to_indexed();
+    let (Some(&src), Some(&dst)) = (ids.get(src), ids.get(dst)) else {
+        return 0;
+    };
+    let labels: Vec<u32> = names
+        .iter()
+        .map(|name| match required.iter().position(|r| r == name) {
+            Some(bit) => 1 << bit,
+            None => 0,
+        })
+        .collect();
+    let all = (1usize << required.len()) - 1;
+    let counts = count_paths_dag(&adj, src, dst, &labels)
+        .unwrap_or_else(|u| panic!("cycle detected involving node '{}'", names[u]));
+    counts.get(all).copied().unwrap_or(0)
 }
 
-fn parse_graph(input: &str) -> HashMap<String, Vec<String>> {
-    let mut graph: HashMap<String, Vec<String>> = HashMap::new();
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let (src, dests) = line
-            .split_once(':')
-            .unwrap_or_else(|| panic!("invalid line (missing colon): {line}"));
-        let src = src.trim().to_string();
-        let neighbors = dests
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect::<Vec<_>>();
-        graph.insert(src, neighbors);
-    }
-    graph
-}
+pub struct Day11;
 
-fn count_paths(
-    node: &str,
-    target: &str,
-    graph: &HashMap<String, Vec<String>>,
-    memo: &mut HashMap<String, u128>,
-    visiting: &mut HashSet<String>,
-) -> u128 {
-    if node == target {
-        return 1;
-    }
-    if let Some(&cached) = memo.get(node) {
-        return cached;
+impl Solver for Day11 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
     }
-    if !visiting.insert(node.to_string()) {
-        panic!("cycle detected involving node '{node}'");
-    }
-    let mut total = 0u128;
-    if let Some(neighbors) = graph.get(node) {
-        for dest in neighbors {
-            total += count_paths(dest, target, graph, memo, visiting);
-        }
-    }
-    visiting.remove(node);
-    memo.insert(node.to_string(), total);
-    total
-}
 
-fn required_mask(node: &str) -> u8 {
-    match node {
-        "dac" => 1,
-        "fft" => 2,
-        _ => 0,
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
     }
 }
 
-fn count_paths_with_requirements(
-    node: &str,
-    target: &str,
-    graph: &HashMap<String, Vec<String>>,
-    memo: &mut HashMap<String, [u128; 4]>,
-    visiting: &mut HashSet<String>,
-    mask_fn: fn(&str) -> u8,
-) -> [u128; 4] {
-    if let Some(result) = memo.get(node) {
-        return *result;
-    }
-    if !visiting.insert(node.to_string()) {
-        panic!("cycle detected involving node '{node}'");
-    }
-    let self_mask = mask_fn(node) as usize;
-    let mut totals = [0u128; 4];
-    if node == target {
-        totals[self_mask] = 1;
-    } else if let Some(neighbors) = graph.get(node) {
+fn parse_graph(input: &str) -> Result<Adjacency<String>, ParseError> {
+    let mut graph = Adjacency::new();
+    for (i, raw) in input.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (src, neighbors): (String, Vec<String>) = parse_key_list(line, ':')
+            .ok_or_else(|| field_error(raw, i + 1, line, "missing ':' after the device name"))?;
+        graph.add_node(src.clone());
         for dest in neighbors {
-            let child_counts =
-                count_paths_with_requirements(dest, target, graph, memo, visiting, mask_fn);
-            for mask in 0..4 {
-                let combined = mask | self_mask;
-                totals[combined] += child_counts[mask];
-            }
+            graph.add_edge(src.clone(), dest);
         }
     }
-    visiting.remove(node);
-    memo.insert(node.to_string(), totals);
-    totals
+    Ok(graph)
 }
 
 #[cfg(test)]
@@ -140,7 +78,7 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
@@ -148,7 +86,7 @@ mod tests {
 
     #[test]
     fn example_part2() {
-        let got = part2(EXAMPLE_PART2);
+        let got = part2(EXAMPLE_PART2).unwrap();
         assert_eq!(got, EXPECTED_PART2_REQUIRED);
     }
 }
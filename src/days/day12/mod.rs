@@ -1,24 +1,46 @@
 //! AoC Day 12 — Christmas Tree Farm
 use std::collections::HashSet;
 
-use super::util;
+use super::util::{field_error, parse_field, PartResult};
+use crate::{profile, Dlx, ParseError};
+
+pub fn part1(input: &str) -> PartResult {
+    let puzzle = {
+        let _span = profile::span("parse");
+        parse_input(input)?
+    };
+    let solver = {
+        let _span = profile::span("placements");
+        Solver::new(&puzzle.shapes)
+    };
+    let _span = profile::span("fit regions");
+    let count = region_fits(&solver, &puzzle.regions)
+        .into_iter()
+        .filter(|&fits| fits)
+        .count();
+    Ok(count.to_string())
+}
 
-pub fn part1(input: &str) -> String {
-    let puzzle = parse_input(input);
-    let solver = Solver::new(&puzzle.shapes);
-    let mut count = 0u64;
-    for region in &puzzle.regions {
-        if solver.can_fit(region) {
-            count += 1;
-        }
-    }
-    count.to_string()
+/// Whether each region fits, in input order. Regions are independent, so with
+/// the `rayon` feature they are checked in parallel; collecting in order keeps
+/// the result identical to the sequential run.
+#[cfg(feature = "rayon")]
+fn region_fits(solver: &Solver, regions: &[Region]) -> Vec<bool> {
+    use rayon::prelude::*;
+    regions.par_iter().map(|r| solver.can_fit(r)).collect()
 }
 
-pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let input = util::read_input("day12")?;
-    println!("Day 12\nPart 1: {}", part1(&input));
-    Ok(())
+#[cfg(not(feature = "rayon"))]
+fn region_fits(solver: &Solver, regions: &[Region]) -> Vec<bool> {
+    regions.iter().map(|r| solver.can_fit(r)).collect()
+}
+
+pub struct Day12;
+
+impl super::Solver for Day12 {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -36,7 +58,7 @@ struct Variant {
 }
 
 #[derive(Debug)]
-struct Region {
+pub struct Region {
     width: usize,
     height: usize,
     requirements: Vec<u16>,
@@ -48,71 +70,98 @@ struct PuzzleInput {
     regions: Vec<Region>,
 }
 
-fn parse_input(input: &str) -> PuzzleInput {
+fn parse_input(input: &str) -> Result<PuzzleInput, ParseError> {
     let mut shapes = Vec::new();
     let mut regions = Vec::new();
+    // Source line of each shape header and region, for errors found after parsing.
+    let mut shape_lines = Vec::new();
+    let mut region_lines = Vec::new();
     let mut reading_shapes = true;
-    let mut current_shape_id = None;
+    let mut current_shape = None;
     let mut current_rows: Vec<String> = Vec::new();
 
-    for raw_line in input.lines() {
+    for (i, raw_line) in input.lines().enumerate() {
+        let line_no = i + 1;
         let trimmed = raw_line.trim();
         if trimmed.is_empty() {
             continue;
         }
         if reading_shapes {
             if let Some(id_str) = trimmed.strip_suffix(':') {
-                if let Some(id) = current_shape_id.take() {
-                    shapes.push(build_shape(id, &current_rows));
+                if let Some(header) = current_shape.take() {
+                    shapes.push(build_shape(header, &current_rows)?);
                     current_rows.clear();
                 }
-                let id = id_str
-                    .trim()
-                    .parse::<usize>()
-                    .unwrap_or_else(|_| panic!("invalid shape index: {trimmed}"));
-                current_shape_id = Some(id);
+                let id: usize = parse_field(raw_line, line_no, id_str.trim(), "shape index")?;
+                current_shape = Some((id, raw_line, line_no));
+                shape_lines.push((raw_line, line_no));
                 continue;
             }
             if trimmed.contains('x') {
-                if let Some(id) = current_shape_id.take() {
-                    shapes.push(build_shape(id, &current_rows));
+                if let Some(header) = current_shape.take() {
+                    shapes.push(build_shape(header, &current_rows)?);
                     current_rows.clear();
                 }
                 reading_shapes = false;
-                regions.push(parse_region(trimmed));
+                regions.push(parse_region(raw_line, line_no)?);
+                region_lines.push((raw_line, line_no));
                 continue;
             }
             current_rows.push(trimmed.to_string());
         } else {
-            regions.push(parse_region(trimmed));
+            regions.push(parse_region(raw_line, line_no)?);
+            region_lines.push((raw_line, line_no));
         }
     }
-    if let Some(id) = current_shape_id {
-        shapes.push(build_shape(id, &current_rows));
+    if let Some(header) = current_shape {
+        shapes.push(build_shape(header, &current_rows)?);
     }
 
-    shapes.sort_by_key(|s| s.id);
-    for (expected, shape) in shapes.iter().enumerate() {
-        assert_eq!(
-            shape.id, expected,
-            "shape indices must be contiguous starting at 0"
-        );
+    let mut ids: Vec<usize> = shapes.iter().map(|s| s.id).collect();
+    ids.sort_unstable();
+    if let Some(pos) = ids.iter().enumerate().position(|(i, &id)| i != id) {
+        let (raw, line_no) = shape_lines
+            .iter()
+            .zip(&shapes)
+            .find(|(_, s)| s.id == ids[pos])
+            .map(|(&l, _)| l)
+            .expect("every shape has a header line");
+        return Err(field_error(
+            raw,
+            line_no,
+            raw.trim(),
+            "shape indices must be contiguous starting at 0",
+        ));
     }
+    shapes.sort_by_key(|s| s.id);
     let shape_count = shapes.len();
-    for region in &mut regions {
-        if region.requirements.len() < shape_count {
-            region.requirements.resize(shape_count, 0);
-        } else if region.requirements.len() > shape_count {
-            panic!("region requirement list longer than number of shapes");
+    for (region, &(raw, line_no)) in regions.iter_mut().zip(&region_lines) {
+        if region.requirements.len() > shape_count {
+            return Err(field_error(
+                raw,
+                line_no,
+                raw.trim(),
+                format!("region requirement list longer than number of shapes ({shape_count})"),
+            ));
         }
+        region.requirements.resize(shape_count, 0);
     }
 
-    PuzzleInput { shapes, regions }
+    Ok(PuzzleInput { shapes, regions })
 }
 
-fn build_shape(id: usize, rows: &[String]) -> Shape {
+/// Builds shape `id` from its rows; `raw`/`line_no` locate its header line.
+fn build_shape(
+    (id, raw, line_no): (usize, &str, usize),
+    rows: &[String],
+) -> Result<Shape, ParseError> {
     if rows.is_empty() {
-        panic!("shape {id} has no rows");
+        return Err(field_error(
+            raw,
+            line_no,
+            raw.trim(),
+            format!("shape {id} has no rows"),
+        ));
     }
     let mut cells = Vec::new();
     for (y, row) in rows.iter().enumerate() {
@@ -123,7 +172,12 @@ fn build_shape(id: usize, rows: &[String]) -> Shape {
         }
     }
     if cells.is_empty() {
-        panic!("shape {id} must have at least one cell");
+        return Err(field_error(
+            raw,
+            line_no,
+            raw.trim(),
+            format!("shape {id} must have at least one cell"),
+        ));
     }
     let mut variants = generate_variants(&cells);
     variants.sort_by(|a, b| {
@@ -133,11 +187,11 @@ fn build_shape(id: usize, rows: &[String]) -> Shape {
             .then_with(|| a.cells.cmp(&b.cells))
     });
     variants.dedup_by(|a, b| a.cells == b.cells);
-    Shape {
+    Ok(Shape {
         id,
         cells,
         variants,
-    }
+    })
 }
 
 fn normalize(cells: &[(i32, i32)]) -> Vec<(i32, i32)> {
@@ -188,24 +242,38 @@ fn generate_variants(base: &[(i32, i32)]) -> Vec<Variant> {
     variants
 }
 
-fn parse_region(line: &str) -> Region {
-    let (size, rest) = line
+/// Parses one `WxH: n n ...` region line. Public so the
+/// `parse_region` fuzz target can call it directly.
+///
+/// Each side must fit in `i32` (placement offsets) and `W * H` in `usize`
+/// (cell indices); larger regions are rejected.
+pub fn parse_region(line: &str, line_no: usize) -> Result<Region, ParseError> {
+    let trimmed = line.trim();
+    let (size, rest) = trimmed
         .split_once(':')
-        .unwrap_or_else(|| panic!("invalid region line: {line}"));
+        .ok_or_else(|| field_error(line, line_no, trimmed, "missing ':' after region size"))?;
     let (width, height) = size
         .split_once('x')
-        .unwrap_or_else(|| panic!("invalid region size: {size}"));
-    let width: usize = width.trim().parse().expect("invalid width");
-    let height: usize = height.trim().parse().expect("invalid height");
+        .ok_or_else(|| field_error(line, line_no, size, "region size must be WxH"))?;
+    let width: usize = parse_field(line, line_no, width.trim(), "width")?;
+    let height: usize = parse_field(line, line_no, height.trim(), "height")?;
+    if width.checked_mul(height).is_none() || i32::try_from(width.max(height)).is_err() {
+        return Err(field_error(
+            line,
+            line_no,
+            size,
+            format!("region {}x{} is too large", width, height),
+        ));
+    }
     let requirements = rest
         .split_whitespace()
-        .map(|v| v.parse::<u16>().expect("invalid requirement"))
-        .collect();
-    Region {
+        .map(|v| parse_field(line, line_no, v, "requirement"))
+        .collect::<Result<_, _>>()?;
+    Ok(Region {
         width,
         height,
         requirements,
-    }
+    })
 }
 
 struct Solver<'a> {
@@ -217,6 +285,8 @@ impl<'a> Solver<'a> {
         Solver { shapes }
     }
 
+    /// Packs the region as an exact cover: each shape placed as many times as
+    /// required (primary columns), every cell used at most once (secondary).
     fn can_fit(&self, region: &Region) -> bool {
         let mut pieces = Vec::new();
         for (idx, &count) in region.requirements.iter().enumerate() {
@@ -227,23 +297,91 @@ impl<'a> Solver<'a> {
         if pieces.is_empty() {
             return true;
         }
+        // `parse_region` bounds the area, so this only fails for regions
+        // built some other way; those are too large to index anyway.
+        let Some(area) = region.width.checked_mul(region.height) else {
+            return false;
+        };
         let total_cells: usize = pieces.iter().map(|&idx| self.shapes[idx].cells.len()).sum();
-        if total_cells > region.width * region.height {
+        if total_cells > area {
+            log::debug!(
+                "{}x{}: {} cells of pieces cannot fit, rejected by area",
+                region.width,
+                region.height,
+                total_cells
+            );
             return false;
         }
+        // Any shape fits in a `side`-square block, so if the region tiles into
+        // enough blocks there is nothing to search.
+        let side = pieces
+            .iter()
+            .map(|&idx| {
+                let v = &self.shapes[idx].variants[0];
+                v.width.max(v.height) as usize
+            })
+            .max()
+            .unwrap_or(1);
+        if pieces.len() <= (region.width / side) * (region.height / side) {
+            log::debug!(
+                "{}x{}: {} pieces fit in {side}x{side} blocks",
+                region.width,
+                region.height,
+                pieces.len()
+            );
+            return true;
+        }
         let placements = self.compute_placements(region);
-        for &idx in &pieces {
-            if placements[idx].is_empty() {
-                return false;
+        if let Some(&idx) = pieces.iter().find(|&&idx| placements[idx].is_empty()) {
+            log::debug!(
+                "{}x{}: shape {} has no placement",
+                region.width,
+                region.height,
+                self.shapes[idx].id
+            );
+            return false;
+        }
+        let shapes = self.shapes.len();
+        let mut dlx = Dlx::new(shapes, area);
+        let mut row = Vec::new();
+        // (shape, placement) of each DLX row, for tracing the packing found.
+        let mut row_placement = Vec::new();
+        for (idx, &count) in region.requirements.iter().enumerate() {
+            dlx.set_multiplicity(idx, count as usize);
+            for (p, cells) in placements[idx].iter().enumerate() {
+                row.clear();
+                row.push(idx);
+                row.extend(cells.iter().map(|&cell| shapes + cell));
+                dlx.add_row(&row);
+                row_placement.push((idx, p));
             }
         }
-        pieces.sort_by_key(|&idx| placements[idx].len());
-        let mut board = BitBoard::new(region.width * region.height);
-        self.search(&pieces, 0, &placements, &mut board)
+        let solution = dlx.solve();
+        log::debug!(
+            "{}x{}: exact cover over {} placements {}",
+            region.width,
+            region.height,
+            dlx.rows(),
+            if solution.is_some() {
+                "found a packing"
+            } else {
+                "found none"
+            }
+        );
+        for &r in solution.iter().flatten() {
+            let (idx, p) = row_placement[r];
+            log::trace!(
+                "  placed shape {} on cells {:?}",
+                self.shapes[idx].id,
+                placements[idx][p]
+            );
+        }
+        solution.is_some()
     }
 
-    fn compute_placements(&self, region: &Region) -> Vec<Vec<Vec<u64>>> {
-        let bit_len = BitBoard::bits_len(region.width * region.height);
+    /// Every distinct placement of each required shape, as the cell indices
+    /// (`y * width + x`) it covers.
+    fn compute_placements(&self, region: &Region) -> Vec<Vec<Vec<usize>>> {
         let mut placements = vec![Vec::new(); self.shapes.len()];
         for (idx, shape) in self.shapes.iter().enumerate() {
             if region
@@ -265,15 +403,14 @@ impl<'a> Solver<'a> {
                 let max_y = region.height as i32 - variant.height;
                 for oy in 0..=max_y {
                     for ox in 0..=max_x {
-                        let mut bits = vec![0u64; bit_len];
-                        for &(dx, dy) in &variant.cells {
-                            let x = (ox + dx) as usize;
-                            let y = (oy + dy) as usize;
-                            let idx_bit = y * region.width + x;
-                            bits[idx_bit / 64] |= 1u64 << (idx_bit % 64);
-                        }
-                        if seen.insert(bits.clone()) {
-                            placements[idx].push(bits);
+                        let mut cells: Vec<usize> = variant
+                            .cells
+                            .iter()
+                            .map(|&(dx, dy)| (oy + dy) as usize * region.width + (ox + dx) as usize)
+                            .collect();
+                        cells.sort_unstable();
+                        if seen.insert(cells.clone()) {
+                            placements[idx].push(cells);
                         }
                     }
                 }
@@ -281,66 +418,6 @@ impl<'a> Solver<'a> {
         }
         placements
     }
-
-    fn search(
-        &self,
-        pieces: &[usize],
-        piece_idx: usize,
-        placements: &[Vec<Vec<u64>>],
-        board: &mut BitBoard,
-    ) -> bool {
-        if piece_idx == pieces.len() {
-            return true;
-        }
-        let shape_idx = pieces[piece_idx];
-        for placement in &placements[shape_idx] {
-            if board.can_place(placement) {
-                board.apply(placement);
-                if self.search(pieces, piece_idx + 1, placements, board) {
-                    board.remove(placement);
-                    return true;
-                }
-                board.remove(placement);
-            }
-        }
-        false
-    }
-}
-
-#[derive(Clone)]
-struct BitBoard {
-    bits: Vec<u64>,
-}
-
-impl BitBoard {
-    fn new(cells: usize) -> Self {
-        Self {
-            bits: vec![0u64; Self::bits_len(cells)],
-        }
-    }
-
-    fn bits_len(cells: usize) -> usize {
-        (cells + 63) / 64
-    }
-
-    fn can_place(&self, placement: &[u64]) -> bool {
-        self.bits
-            .iter()
-            .zip(placement.iter())
-            .all(|(a, b)| (a & b) == 0)
-    }
-
-    fn apply(&mut self, placement: &[u64]) {
-        for (a, b) in self.bits.iter_mut().zip(placement.iter()) {
-            *a |= *b;
-        }
-    }
-
-    fn remove(&mut self, placement: &[u64]) {
-        for (a, b) in self.bits.iter_mut().zip(placement.iter()) {
-            *a &= !*b;
-        }
-    }
 }
 
 
@@ -356,9 +433,19 @@ mod tests {
 
     #[test]
     fn example_part1() {
-        let got = part1(EXAMPLE);
+        let got = part1(EXAMPLE).unwrap();
         if let Some(exp) = EXPECTED_PART1 {
             assert_eq!(got, exp);
         }
     }
+
+    #[test]
+    fn oversized_region_is_a_parse_error() {
+        let line = "99999999999x99999999999: 1";
+        let e = parse_region(line, 3).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        assert!(e.message.contains("too large"), "{}", e.message);
+        assert!(parse_region("3000000000x1: 1", 1).is_err());
+        assert!(parse_region("4x4: 0 2", 1).is_ok());
+    }
 }
@@ -1,33 +1,242 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
+pub mod answers;
 pub mod util;
 
-/// Run the selected day by id (e.g., "day01" or "1").
-pub fn run_day(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+use std::time::Duration;
+
+use crate::{profile, ParseError};
+
+/// A day's puzzle solution.
+///
+/// Implement this on a unit struct in the day's module and list the module
+/// in [`register_days!`] below; the runner, `all` and any other tooling pick
+/// it up from [`solver`] and [`DAYS`].
+pub trait Solver: Sync {
+    /// Answer for part 1, or the first malformed line of `input`.
+    fn part1(&self, input: &str) -> util::PartResult;
+
+    /// Answer for part 2, or `None` for days that only have one part.
+    fn part2(&self, _input: &str) -> Option<util::PartResult> {
+        None
+    }
+
+    /// Optional side work (visualizations, exports) run before the parts.
+    fn prepare(&self, _input: &str) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
+/// Declare each `dayNN` module together with its [`Solver`] type, and
+/// generate [`DAYS`], [`solver`] and one `answer_tests::dayNN` test per day
+/// (checked against `answers.toml`) from the list.
+macro_rules! register_days {
+    ($($day:ident: $solver:ident,)*) => {
+        $(pub mod $day;)*
+
+        /// Every registered day, in order.
+        pub const DAYS: &[&str] = &[$(stringify!($day)),*];
+
+        /// Look up the solver for a canonical day id (e.g., "day05").
+        pub fn solver(day: &str) -> Option<&'static dyn Solver> {
+            match day {
+                $(stringify!($day) => Some(&$day::$solver),)*
+                _ => None,
+            }
+        }
+
+        #[cfg(test)]
+        mod answer_tests {
+            $(
+                #[test]
+                fn $day() {
+                    super::answers::assert_day(stringify!($day));
+                }
+            )*
+        }
+    };
+}
+
+register_days! {
+    day01: Day01,
+    day02: Day02,
+    day03: Day03,
+    day04: Day04,
+    day05: Day05,
+    day06: Day06,
+    day07: Day07,
+    day08: Day08,
+    day09: Day09,
+    day10: Day10,
+    day11: Day11,
+    day12: Day12,
+}
+
+/// Wall-clock time spent on each part; `None` for parts that did not run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timings {
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Answers produced by one day's run.
+///
+/// `part1` is `None` only when skipped via `--part 2`; `part2` is also
+/// `None` for days without a second part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayResult {
+    /// Canonical day id, e.g. "day05".
+    pub day: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: Timings,
+}
+
+/// Solve the parts selected by `opts` on `input`, timing each one. Stops at
+/// the first part that rejects the input.
+pub fn solve(
+    day: &str,
+    solver: &dyn Solver,
+    input: &str,
+    opts: &util::RunOptions,
+) -> Result<DayResult, ParseError> {
+    let mut result = DayResult {
+        day: day.to_string(),
+        ..DayResult::default()
+    };
+    if opts.wants(1) {
+        let _span = profile::span(format!("{day} part1"));
+        let (answer, elapsed) = util::timed(|| solver.part1(input));
+        result.part1 = Some(answer?);
+        result.timings.part1 = Some(elapsed);
+    }
+    if opts.wants(2) {
+        let _span = profile::span(format!("{day} part2"));
+        let (answer, elapsed) = util::timed(|| solver.part2(input));
+        if let Some(answer) = answer {
+            result.part2 = Some(answer?);
+            result.timings.part2 = Some(elapsed);
+        }
+    }
+    Ok(result)
+}
+
+/// Run the selected day by id (e.g., "day01" or "1") on its real input,
+/// or on the example input when `opts.example` is set. Days whose part 2
+/// has its own example (`dayXX_part2_example.txt`) use it for that part.
+///
+/// Missing files and malformed input are reported as errors prefixed with
+/// the file path (and line/column for parse errors) rather than panics.
+///
+/// With `opts.timeout` the day runs on its own thread and a
+/// [`util::TimedOut`] error is returned if it overruns. A panicking solver is
+/// reported as an error either way, so `all` carries on with the next day.
+pub fn run_day(id: &str, opts: &util::RunOptions) -> Result<DayResult, Box<dyn std::error::Error>> {
+    let (id, opts) = (id.to_string(), *opts);
+    util::with_timeout(opts.timeout, move || {
+        run_day_untimed(&id, &opts).map_err(|e| e.to_string())
+    })
+}
+
+fn run_day_untimed(
+    id: &str,
+    opts: &util::RunOptions,
+) -> Result<DayResult, Box<dyn std::error::Error>> {
     let norm = util::normalize_day(id);
-    match norm.as_str() {
-        "day01" => day01::run(),
-        "day02" => day02::run(),
-        "day03" => day03::run(),
-        "day04" => day04::run(),
-        "day05" => day05::run(),
-        "day06" => day06::run(),
-        "day07" => day07::run(),
-        "day08" => day08::run(),
-        "day09" => day09::run(),
-        "day10" => day10::run(),
-        "day11" => day11::run(),
-        "day12" => day12::run(),
-        _ => Err(format!("Unknown day: {}", id).into()),
+    let solver = solver(&norm).ok_or_else(|| format!("Unknown day: {}", id))?;
+    let _span = profile::span(norm.clone());
+    let path = util::input_path(&norm, if opts.example { "_example" } else { "" });
+    let read = if opts.example {
+        util::read_example
+    } else {
+        util::read_input
+    };
+    let input = {
+        let _span = profile::span("read input");
+        read(&norm).map_err(|e| format!("{}: {}", path, e))?
+    };
+    let in_file = |path: &str| {
+        let path = path.to_string();
+        move |e: ParseError| format!("{}:{}", path, e)
+    };
+    {
+        let _span = profile::span("prepare");
+        solver.prepare(&input)?;
+    }
+    let part2_path = util::input_path(&norm, "_part2_example");
+    let part2_example = if opts.example && opts.wants(2) {
+        util::read_example_part2(&norm)
+    } else {
+        None
+    };
+    let Some(part2_input) = part2_example else {
+        return Ok(solve(&norm, solver, &input, opts).map_err(in_file(&path))?);
+    };
+    let only = |part| util::RunOptions {
+        part: Some(part),
+        ..*opts
+    };
+    let mut result = if opts.wants(1) {
+        solve(&norm, solver, &input, &only(1)).map_err(in_file(&path))?
+    } else {
+        DayResult {
+            day: norm.clone(),
+            ..DayResult::default()
+        }
+    };
+    let part2 = solve(&norm, solver, &part2_input, &only(2)).map_err(in_file(&part2_path))?;
+    result.part2 = part2.part2;
+    result.timings.part2 = part2.timings.part2;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_respects_part_selection() {
+        let example = include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/inputs/day05_example.txt"
+        ));
+        let solver = solver("day05").unwrap();
+        let both = solve("day05", solver, example, &util::RunOptions::default()).unwrap();
+        assert_eq!(both.part1.as_deref(), Some("3"));
+        assert_eq!(both.part2.as_deref(), Some("14"));
+        assert!(both.timings.part1.is_some() && both.timings.part2.is_some());
+
+        let opts = util::RunOptions {
+            part: Some(2),
+            ..Default::default()
+        };
+        let only2 = solve("day05", solver, example, &opts).unwrap();
+        assert_eq!(only2.part1, None);
+        assert_eq!(only2.timings.part1, None);
+        assert_eq!(only2.part2.as_deref(), Some("14"));
+    }
+
+    #[test]
+    fn solve_reports_malformed_line() {
+        let solver = solver("day05").unwrap();
+        let err = solve("day05", solver, "3-5\n10-x\n\n1\n", &Default::default()).unwrap_err();
+        assert_eq!((err.line, err.column, err.token.as_str()), (2, 4, "x"));
+    }
+
+    #[test]
+    fn timeouts_abandon_slow_work() {
+        let limit = Some(Duration::from_millis(20));
+        let slow = util::with_timeout(limit, || {
+            std::thread::sleep(Duration::from_secs(5));
+            Ok(())
+        });
+        let err = slow.unwrap_err();
+        assert!(err.is::<util::TimedOut>());
+        assert!(err.to_string().starts_with("TIMEOUT after"));
+        assert_eq!(util::with_timeout(limit, || Ok(7)).unwrap(), 7);
+        let failed = util::with_timeout(None, || Err::<(), _>("bad input".into()));
+        assert_eq!(failed.unwrap_err().to_string(), "bad input");
+        for limit in [None, limit] {
+            let panicked = util::with_timeout(limit, || -> Result<(), String> { panic!("boom") });
+            assert_eq!(panicked.unwrap_err().to_string(), "solver panicked");
+        }
     }
 }
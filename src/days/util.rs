@@ -1,4 +1,11 @@
+use crate::ParseError;
+use std::fmt::Display;
 use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Normalize input like "1", "01", "day1" to canonical "day01".
 pub fn normalize_day(id: &str) -> String {
@@ -10,9 +17,128 @@ pub fn normalize_day(id: &str) -> String {
     }
 }
 
+/// Path of a day's input file: `inputs/dayXX{suffix}.txt`, where `suffix`
+/// is `""` for the real input or e.g. `"_example"`.
+pub fn input_path(day: &str, suffix: &str) -> String {
+    format!("inputs/{}{}.txt", normalize_day(day), suffix)
+}
+
 /// Read input file from `inputs/dayXX.txt`.
 pub fn read_input(day: &str) -> std::io::Result<String> {
-    let id = normalize_day(day);
-    let path = format!("inputs/{}.txt", id);
-    fs::read_to_string(path)
+    fs::read_to_string(input_path(day, ""))
+}
+
+/// Read the published example from `inputs/dayXX_example.txt`.
+pub fn read_example(day: &str) -> std::io::Result<String> {
+    fs::read_to_string(input_path(day, "_example"))
+}
+
+/// Read the part-2-specific example `inputs/dayXX_part2_example.txt`, if any.
+pub fn read_example_part2(day: &str) -> Option<String> {
+    fs::read_to_string(input_path(day, "_part2_example")).ok()
+}
+
+/// Answer for one part, or the input error that prevented computing it.
+pub type PartResult = Result<String, ParseError>;
+
+/// Parse `token`, a slice of `line` (input line `line_no`, 1-based), as `T`;
+/// the error names `what` was expected and the token's column.
+pub fn parse_field<T: FromStr>(
+    line: &str,
+    line_no: usize,
+    token: &str,
+    what: &str,
+) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    let token = token.trim();
+    token
+        .parse()
+        .map_err(|e: T::Err| field_error(line, line_no, token, format!("invalid {}: {}", what, e)))
+}
+
+/// Error about `token` on input line `line_no`. When `token` is a slice of
+/// `line` its column is reported, otherwise column 1.
+pub fn field_error(
+    line: &str,
+    line_no: usize,
+    token: &str,
+    message: impl Into<String>,
+) -> ParseError {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|&o| o <= line.len() && line.is_char_boundary(o))
+        .unwrap_or(0);
+    ParseError {
+        line: line_no,
+        column: line[..offset].chars().count() + 1,
+        token: token.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Options controlling how a day is run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunOptions {
+    /// Print wall-clock time next to each part's answer.
+    pub time: bool,
+    /// Only compute this part (1 or 2); `None` runs both.
+    pub part: Option<u8>,
+    /// Read `inputs/dayXX_example.txt` instead of the real input.
+    pub example: bool,
+    /// Give up on a day that has not finished after this long.
+    pub timeout: Option<Duration>,
+}
+
+impl RunOptions {
+    /// Whether `part` was selected to run.
+    pub fn wants(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Run `f`, returning its result together with the elapsed wall-clock time.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// A day that produced no answer within `RunOptions::timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut(pub Duration);
+
+impl Display for TimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TIMEOUT after {:.2?}", self.0)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// Run `f` on its own thread and wait at most `limit` for it (inline, with no
+/// limit, when `None`). A thread that overruns cannot be stopped; it is left
+/// detached and its result dropped, and [`TimedOut`] is returned instead.
+/// Either way a panic in `f` comes back as a "solver panicked" error.
+pub fn with_timeout<T: Send + 'static>(
+    limit: Option<Duration>,
+    f: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let Some(limit) = limit else {
+        return match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => Ok(result?),
+            Err(_) => Err("solver panicked".into()),
+        };
+    };
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if we already timed out.
+        let _ = tx.send(f());
+    });
+    match rx.recv_timeout(limit) {
+        Ok(result) => Ok(result?),
+        Err(RecvTimeoutError::Timeout) => Err(TimedOut(limit).into()),
+        Err(RecvTimeoutError::Disconnected) => Err("solver panicked".into()),
+    }
 }
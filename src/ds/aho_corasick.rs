@@ -0,0 +1,183 @@
+//! Aho-Corasick automaton for finding many patterns in one pass.
+//!
+//! The patterns are stored in a `Trie`; on top of it we compute, per node:
+//! - `fail`: the longest proper suffix of the node's string that is also a
+//!   trie path (where to continue after a mismatch)
+//! - `dict`: the nearest node along the failure chain that ends a pattern,
+//!   so reporting all matches at a position only visits real outputs
+//! - `hits`: how many patterns end at this node or any of its suffixes,
+//!   which powers the count-only mode without walking the chains
+//!
+//! Scanning a text is O(n + matches); counting is O(n). Positions are
+//! character offsets (identical to byte offsets for ASCII input). Empty
+//! patterns are accepted but never reported.
+
+use crate::ds::trie::Trie;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    trie: Trie,
+    fail: Vec<usize>,
+    dict: Vec<Option<usize>>,
+    out: Vec<Vec<usize>>, // pattern ids ending exactly at each node
+    hits: Vec<usize>,
+    pat_len: Vec<usize>, // length in chars of each pattern id
+}
+
+impl AhoCorasick {
+    /// Build the automaton; pattern ids are their positions in `patterns`.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        let mut trie = Trie::new();
+        let mut ends = Vec::with_capacity(patterns.len());
+        for p in patterns {
+            let p = p.as_ref();
+            ends.push(if p.is_empty() {
+                None
+            } else {
+                Some(trie.insert_node(p))
+            });
+        }
+        let n = trie.nodes.len();
+        let mut out = vec![Vec::new(); n];
+        for (id, end) in ends.iter().enumerate() {
+            if let Some(node) = end {
+                out[*node].push(id);
+            }
+        }
+        let pat_len = patterns
+            .iter()
+            .map(|p| p.as_ref().chars().count())
+            .collect();
+
+        let mut fail = vec![0; n];
+        let mut dict = vec![None; n];
+        let mut hits = vec![0; n];
+        let mut q = VecDeque::new();
+        q.push_back(0);
+        while let Some(u) = q.pop_front() {
+            for (&c, &v) in &trie.nodes[u].children {
+                if u != 0 {
+                    let mut f = fail[u];
+                    loop {
+                        if let Some(&w) = trie.nodes[f].children.get(&c) {
+                            fail[v] = w;
+                            break;
+                        }
+                        if f == 0 {
+                            break;
+                        }
+                        f = fail[f];
+                    }
+                }
+                let fv = fail[v];
+                dict[v] = if out[fv].is_empty() {
+                    dict[fv]
+                } else {
+                    Some(fv)
+                };
+                hits[v] = out[v].len() + hits[fv];
+                q.push_back(v);
+            }
+        }
+        Self {
+            trie,
+            fail,
+            dict,
+            out,
+            hits,
+            pat_len,
+        }
+    }
+    /// Follow goto/failure transitions from `state` on character `c`.
+    fn step(&self, mut state: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.trie.nodes[state].children.get(&c) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+    /// All matches as `(start, pattern_id)`, ordered by end position.
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        let mut state = 0;
+        for (i, c) in text.chars().enumerate() {
+            state = self.step(state, c);
+            let mut node = Some(state);
+            while let Some(u) = node {
+                for &id in &self.out[u] {
+                    res.push((i + 1 - self.pat_len[id], id));
+                }
+                node = self.dict[u];
+            }
+        }
+        res
+    }
+    /// Total number of matches (all patterns, overlapping) without listing them.
+    pub fn count_matches(&self, text: &str) -> usize {
+        let mut state = 0;
+        let mut total = 0;
+        for c in text.chars() {
+            state = self.step(state, c);
+            total += self.hits[state];
+        }
+        total
+    }
+    /// True if any pattern occurs in `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        let mut state = 0;
+        text.chars().any(|c| {
+            state = self.step(state, c);
+            self.hits[state] > 0
+        })
+    }
+    /// Number of patterns the automaton was built from.
+    pub fn pattern_count(&self) -> usize {
+        self.pat_len.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AhoCorasick;
+    use crate::ds::string_alg::kmp_search;
+
+    #[test]
+    fn classic_he_she_his_hers() {
+        let ac = AhoCorasick::new(&["he", "she", "his", "hers"]);
+        let mut m = ac.find_all("ushers");
+        m.sort_unstable();
+        assert_eq!(m, vec![(1, 1), (2, 0), (2, 3)]);
+        assert_eq!(ac.count_matches("ushers"), 3);
+        assert!(ac.is_match("this") && !ac.is_match("xyz"));
+        assert_eq!(ac.pattern_count(), 4);
+    }
+
+    #[test]
+    fn agrees_with_kmp_on_overlaps() {
+        let pats = ["aba", "ba", "a", "abab"];
+        let text = "abababa";
+        let ac = AhoCorasick::new(&pats);
+        let mut got = ac.find_all(text);
+        got.sort_unstable();
+        let mut want: Vec<(usize, usize)> = pats
+            .iter()
+            .enumerate()
+            .flat_map(|(id, p)| kmp_search(text, p).into_iter().map(move |s| (s, id)))
+            .collect();
+        want.sort_unstable();
+        assert_eq!(got, want);
+        assert_eq!(ac.count_matches(text), want.len());
+    }
+
+    #[test]
+    fn duplicates_and_empty_patterns() {
+        let ac = AhoCorasick::new(&["ab", "", "ab"]);
+        assert_eq!(ac.find_all("xab"), vec![(1, 0), (1, 2)]);
+        assert_eq!(ac.count_matches(""), 0);
+    }
+}
@@ -49,6 +49,31 @@ impl<T> ArrayList<T> {
         self.inner.pop()
     }
 
+    /// Insert `v` at position `idx`, shifting later elements right (panics if `idx > len`).
+    pub fn insert(&mut self, idx: usize, v: T) {
+        self.inner.insert(idx, v)
+    }
+
+    /// Remove and return the element at `idx`, shifting later elements left (panics if out of bounds).
+    pub fn remove(&mut self, idx: usize) -> T {
+        self.inner.remove(idx)
+    }
+
+    /// Remove the element at `idx` in O(1) by moving the last element into its place.
+    pub fn swap_remove(&mut self, idx: usize) -> T {
+        self.inner.swap_remove(idx)
+    }
+
+    /// Keep only the elements for which `f` returns true (order preserved).
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.inner.retain(f)
+    }
+
+    /// Sort by a key extraction function (stable).
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+        self.inner.sort_by_key(f)
+    }
+
     /// Get immutable reference by index (returns None if out of bounds).
     pub fn get(&self, idx: usize) -> Option<&T> {
         self.inner.get(idx)
@@ -79,18 +104,90 @@ impl<T> ArrayList<T> {
         self.inner.iter_mut()
     }
 
+    /// Non-overlapping chunks of `n` elements; the last chunk may be shorter (panics if `n == 0`).
+    pub fn chunks(&self, n: usize) -> std::slice::Chunks<'_, T> {
+        self.inner.chunks(n)
+    }
+
+    /// Overlapping windows of `n` consecutive elements (panics if `n == 0`).
+    pub fn windows(&self, n: usize) -> std::slice::Windows<'_, T> {
+        self.inner.windows(n)
+    }
+
+    /// View the flat list as a row-major grid with `width` columns.
+    /// Panics if `width == 0` or the length is not a multiple of `width`.
+    pub fn as_grid(&self, width: usize) -> GridView<'_, T> {
+        assert!(width > 0, "grid width must be positive");
+        assert!(
+            self.inner.len().is_multiple_of(width),
+            "list length {} is not a multiple of width {}",
+            self.inner.len(),
+            width
+        );
+        GridView {
+            data: &self.inner,
+            w: width,
+        }
+    }
+
     /// Consume and return the inner `Vec<T>`.
     pub fn into_vec(self) -> Vec<T> {
         self.inner
     }
 }
 
+impl<T: Ord> ArrayList<T> {
+    /// Sort ascending (stable).
+    pub fn sort(&mut self) {
+        self.inner.sort()
+    }
+
+    /// Binary search a sorted list; `Ok(idx)` if found, otherwise `Err(insert_pos)`.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize> {
+        self.inner.binary_search(x)
+    }
+}
+
 impl<T> From<Vec<T>> for ArrayList<T> {
     fn from(v: Vec<T>) -> Self {
         Self { inner: v }
     }
 }
 
+/// Borrowed row-major 2D view over an `ArrayList` (see `ArrayList::as_grid`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GridView<'a, T> {
+    data: &'a [T],
+    w: usize,
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.w
+    }
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.data.len() / self.w
+    }
+    /// Cell at column `x`, row `y` (None if out of bounds).
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x < self.w {
+            self.data.get(y * self.w + x)
+        } else {
+            None
+        }
+    }
+    /// Row `y` as a slice (panics if out of bounds).
+    pub fn row(&self, y: usize) -> &'a [T] {
+        &self.data[y * self.w..(y + 1) * self.w]
+    }
+    /// Iterate rows top to bottom.
+    pub fn rows(&self) -> std::slice::ChunksExact<'a, T> {
+        self.data.chunks_exact(self.w)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ArrayList;
@@ -139,4 +236,54 @@ mod tests {
         }
         assert_eq!(a.into_vec(), vec![2, 4, 6]);
     }
+
+    #[test]
+    fn positional_insert_remove() {
+        let mut a = ArrayList::from_iter([1, 3, 4]);
+        a.insert(1, 2);
+        assert_eq!(a.slice(0..4), &[1, 2, 3, 4]);
+        assert_eq!(a.remove(0), 1);
+        assert_eq!(a.swap_remove(0), 2);
+        assert_eq!(a.into_vec(), vec![4, 3]);
+    }
+
+    #[test]
+    fn retain_sort_and_search() {
+        let mut a = ArrayList::from_iter([5, 1, 4, 2, 3, 6]);
+        a.retain(|&x| x != 6);
+        a.sort();
+        assert_eq!(a.binary_search(&4), Ok(3));
+        assert_eq!(a.binary_search(&0), Err(0));
+        a.sort_by_key(|&x| std::cmp::Reverse(x));
+        assert_eq!(a.into_vec(), vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        let a = ArrayList::from_iter(1..=5);
+        let c: Vec<&[i32]> = a.chunks(2).collect();
+        assert_eq!(c, vec![&[1, 2][..], &[3, 4], &[5]]);
+        let sums: Vec<i32> = a.windows(3).map(|w| w.iter().sum()).collect();
+        assert_eq!(sums, vec![6, 9, 12]);
+    }
+
+    #[test]
+    fn grid_view() {
+        let a = ArrayList::from_iter("abcdef".chars());
+        let g = a.as_grid(3);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g.get(2, 1), Some(&'f'));
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.get(0, 2), None);
+        assert_eq!(g.row(1), &['d', 'e', 'f']);
+        let rows: Vec<String> = g.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn grid_view_rejects_ragged_width() {
+        let a = ArrayList::from_iter(0..5);
+        let _ = a.as_grid(2);
+    }
 }
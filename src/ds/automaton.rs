@@ -0,0 +1,181 @@
+//! Cellular automata on a bounded 2D grid.
+//!
+//! `GridAutomaton` holds one state per cell and a predicate saying which
+//! states count as "live". Each step updates every cell at once from
+//! `rule(state, live_neighbors)`, where neighbors are the 8 surrounding
+//! cells by default (`with_neighbors(&DELTAS4)` for the orthogonal ones);
+//! cells outside the grid are never live.
+//!
+//! `run_until_stable` repeats steps until nothing changes. After the first
+//! step only cells next to a change can change, so later steps only revisit
+//! those; rules that oscillate never settle, so use `step` with
+//! `cycle::find_cycle` for those.
+
+use crate::ds::coords::Point;
+use crate::ds::neighbors::DELTAS8;
+
+#[derive(Clone, Debug)]
+pub struct GridAutomaton<S> {
+    w: usize,
+    h: usize,
+    cells: Vec<S>,
+    is_live: fn(&S) -> bool,
+    deltas: &'static [Point],
+}
+
+impl<S: Copy + PartialEq> GridAutomaton<S> {
+    /// Automaton over `rows` (all the same width; panics otherwise) with the
+    /// 8-neighborhood.
+    pub fn new(rows: Vec<Vec<S>>, is_live: fn(&S) -> bool) -> Self {
+        let h = rows.len();
+        let w = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|r| r.len() == w), "ragged automaton grid");
+        GridAutomaton {
+            w,
+            h,
+            cells: rows.into_iter().flatten().collect(),
+            is_live,
+            deltas: &DELTAS8,
+        }
+    }
+
+    /// Use `deltas` (e.g. `DELTAS4`) as the neighborhood instead.
+    pub fn with_neighbors(mut self, deltas: &'static [Point]) -> Self {
+        self.deltas = deltas;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    /// State of cell (x, y); panics if out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> S {
+        assert!(x < self.w && y < self.h, "cell ({x}, {y}) out of bounds");
+        self.cells[y * self.w + x]
+    }
+
+    /// Number of cells whose state satisfies `pred`.
+    pub fn count(&self, pred: impl Fn(&S) -> bool) -> usize {
+        self.cells.iter().filter(|s| pred(s)).count()
+    }
+
+    /// Number of live cells.
+    pub fn live(&self) -> usize {
+        self.count(self.is_live)
+    }
+
+    /// Live neighbors of cell (x, y).
+    pub fn live_neighbors(&self, x: usize, y: usize) -> usize {
+        self.neighbors(y * self.w + x)
+            .filter(|&j| (self.is_live)(&self.cells[j]))
+            .count()
+    }
+
+    /// Advance one generation; returns how many cells changed state.
+    pub fn step(&mut self, rule: impl Fn(S, usize) -> S) -> usize {
+        let all: Vec<usize> = (0..self.cells.len()).collect();
+        self.apply(&all, &rule).len()
+    }
+
+    /// Step until a generation changes nothing. Returns the number of steps
+    /// that changed something and the total number of cell changes.
+    pub fn run_until_stable(&mut self, rule: impl Fn(S, usize) -> S) -> (usize, usize) {
+        let mut candidates: Vec<usize> = (0..self.cells.len()).collect();
+        let mut marked = vec![false; self.cells.len()];
+        let (mut steps, mut changes) = (0, 0);
+        loop {
+            let changed = self.apply(&candidates, &rule);
+            if changed.is_empty() {
+                return (steps, changes);
+            }
+            steps += 1;
+            changes += changed.len();
+            candidates.clear();
+            for &i in &changed {
+                for j in std::iter::once(i).chain(self.neighbors(i)) {
+                    if !std::mem::replace(&mut marked[j], true) {
+                        candidates.push(j);
+                    }
+                }
+            }
+            for &j in &candidates {
+                marked[j] = false;
+            }
+        }
+    }
+
+    /// Update the `candidates` cells simultaneously; returns those that changed.
+    fn apply(&mut self, candidates: &[usize], rule: &impl Fn(S, usize) -> S) -> Vec<usize> {
+        let updates: Vec<(usize, S)> = candidates
+            .iter()
+            .filter_map(|&i| {
+                let live = self
+                    .neighbors(i)
+                    .filter(|&j| (self.is_live)(&self.cells[j]))
+                    .count();
+                let next = rule(self.cells[i], live);
+                (next != self.cells[i]).then_some((i, next))
+            })
+            .collect();
+        updates
+            .into_iter()
+            .map(|(i, next)| {
+                self.cells[i] = next;
+                i
+            })
+            .collect()
+    }
+
+    /// In-bounds neighbor indices of cell index `i`.
+    fn neighbors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        let (x, y) = ((i % self.w) as i64, (i / self.w) as i64);
+        self.deltas.iter().filter_map(move |d| {
+            let (nx, ny) = (x + d.x, y + d.y);
+            (nx >= 0 && ny >= 0 && (nx as usize) < self.w && (ny as usize) < self.h)
+                .then(|| ny as usize * self.w + nx as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GridAutomaton;
+    use crate::ds::neighbors::DELTAS4;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|r| r.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn life_blinker_and_block() {
+        let life = |alive: bool, n: usize| n == 3 || (alive && n == 2);
+        let mut blinker =
+            GridAutomaton::new(grid(&[".....", "..#..", "..#..", "..#..", "....."]), |&s| s);
+        assert_eq!(blinker.live_neighbors(2, 2), 2);
+        assert_eq!(blinker.step(life), 4);
+        assert!(blinker.get(1, 2) && blinker.get(3, 2) && !blinker.get(2, 1));
+        assert_eq!(blinker.live(), 3);
+
+        let mut block = GridAutomaton::new(grid(&["....", ".##.", ".##.", "...."]), |&s| s);
+        assert_eq!(block.run_until_stable(life), (0, 0));
+        assert_eq!(block.count(|&s| s), 4);
+    }
+
+    #[test]
+    fn erosion_runs_to_fixpoint() {
+        // Remove live cells with fewer than 2 orthogonal live neighbors: a
+        // line erodes from both ends, one cell per end per step.
+        let mut line = GridAutomaton::new(grid(&["#####"]), |&s| s).with_neighbors(&DELTAS4);
+        let erode = |alive: bool, n: usize| alive && n >= 2;
+        assert_eq!(line.run_until_stable(erode), (3, 5));
+        assert_eq!(line.live(), 0);
+        assert_eq!((line.width(), line.height()), (5, 1));
+    }
+}
@@ -0,0 +1,112 @@
+//! Downward beams through a grid of splitters, one row at a time.
+//!
+//! A beam enters at a start column and falls straight down. When it meets a
+//! splitter it stops there and continues from the columns directly left and
+//! right of it; a branch that would leave the grid ends immediately. Beams
+//! sharing a column merge, but each keeps its own count of timelines (paths
+//! from the start), so the frontier is just one `u128` per column.
+//!
+//! `BeamFrontier::step` consumes one row at a time and never looks back, so a
+//! whole manifold can be streamed with O(width) memory regardless of height.
+
+/// Per-column timeline counts of the beams falling through the current row.
+#[derive(Clone, Debug)]
+pub struct BeamFrontier {
+    counts: Vec<u128>,
+    /// Scratch buffer for the next row, swapped with `counts` each step.
+    next: Vec<u128>,
+    splits: u64,
+    exited: u128,
+}
+
+impl BeamFrontier {
+    /// A single beam (one timeline) at `start` in a grid `width` columns wide.
+    /// Panics if `start` is out of range.
+    pub fn new(width: usize, start: usize) -> Self {
+        assert!(start < width, "start column {start} out of range");
+        let mut counts = vec![0; width];
+        counts[start] = 1;
+        BeamFrontier {
+            counts,
+            next: vec![0; width],
+            splits: 0,
+            exited: 0,
+        }
+    }
+
+    /// Let the beams fall into `row`, splitting on every cell where
+    /// `is_splitter` holds. Panics if `row` is not `width` cells wide.
+    pub fn step<T>(&mut self, row: &[T], is_splitter: impl Fn(&T) -> bool) {
+        let width = self.counts.len();
+        assert_eq!(row.len(), width, "row width mismatch");
+        self.next.iter_mut().for_each(|c| *c = 0);
+        for (col, cell) in row.iter().enumerate() {
+            let count = self.counts[col];
+            if count == 0 {
+                continue;
+            }
+            if !is_splitter(cell) {
+                self.next[col] += count;
+                continue;
+            }
+            self.splits += 1;
+            match col.checked_sub(1) {
+                Some(left) => self.next[left] += count,
+                None => self.exited += count,
+            }
+            if col + 1 < width {
+                self.next[col + 1] += count;
+            } else {
+                self.exited += count;
+            }
+        }
+        std::mem::swap(&mut self.counts, &mut self.next);
+    }
+
+    /// Timeline counts per column of the beams still falling.
+    pub fn counts(&self) -> &[u128] {
+        &self.counts
+    }
+
+    /// Splitters hit so far (each splitter at most once, however many
+    /// timelines reach it).
+    pub fn splits(&self) -> u64 {
+        self.splits
+    }
+
+    /// Timelines so far: those that left through a side plus those still
+    /// falling. After the last row this is the number of distinct paths.
+    pub fn timelines(&self) -> u128 {
+        self.exited + self.counts.iter().sum::<u128>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BeamFrontier;
+
+    fn run(rows: &[&str], start: usize) -> BeamFrontier {
+        let mut beams = BeamFrontier::new(rows[0].len(), start);
+        for row in rows {
+            beams.step(row.as_bytes(), |&c| c == b'^');
+        }
+        beams
+    }
+
+    #[test]
+    fn splits_merge_and_count_timelines() {
+        // One split, then both halves split again and share the middle column.
+        let beams = run(&["...", ".^.", "...", "^.^"], 1);
+        assert_eq!(beams.splits(), 3);
+        assert_eq!(beams.counts(), &[0, 2, 0]);
+        // Two timelines fall off the sides, two land in the middle.
+        assert_eq!(beams.timelines(), 4);
+    }
+
+    #[test]
+    fn untouched_splitters_do_not_count() {
+        let beams = run(&["^..", "..."], 2);
+        assert_eq!(beams.splits(), 0);
+        assert_eq!(beams.timelines(), 1);
+    }
+}
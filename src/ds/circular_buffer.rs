@@ -0,0 +1,112 @@
+//! Fixed-capacity circular buffer that overwrites its oldest entry when full.
+//!
+//! Handy for "remember the last k states" logic (cycle detection, rolling
+//! history). Index 0 is always the oldest element still retained and
+//! `len() - 1` the newest; `get` is O(1) regardless of how often the buffer
+//! has wrapped around.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircularBuffer<T> {
+    buf: Vec<T>,
+    cap: usize,
+    head: usize, // position of the oldest element once the buffer is full
+}
+
+impl<T> CircularBuffer<T> {
+    /// Create an empty buffer holding at most `cap` elements (panics if `cap == 0`).
+    pub fn new(cap: usize) -> Self {
+        assert!(cap > 0, "capacity must be positive");
+        Self {
+            buf: Vec::with_capacity(cap),
+            cap,
+            head: 0,
+        }
+    }
+    /// Append `x`; when full, the oldest element is overwritten and returned.
+    pub fn push(&mut self, x: T) -> Option<T> {
+        if self.buf.len() < self.cap {
+            self.buf.push(x);
+            None
+        } else {
+            let old = std::mem::replace(&mut self.buf[self.head], x);
+            self.head = (self.head + 1) % self.cap;
+            Some(old)
+        }
+    }
+    /// Element `i` positions after the oldest (None if `i >= len`).
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i < self.buf.len() {
+            self.buf.get((self.head + i) % self.cap)
+        } else {
+            None
+        }
+    }
+    /// Oldest retained element.
+    pub fn oldest(&self) -> Option<&T> {
+        self.get(0)
+    }
+    /// Most recently pushed element.
+    pub fn newest(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+    /// Maximum number of retained elements.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+    /// True once `len() == capacity()` (further pushes evict).
+    pub fn is_full(&self) -> bool {
+        self.buf.len() == self.cap
+    }
+    /// Remove all elements, keeping the capacity.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.head = 0;
+    }
+    /// Iterate from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (newer, older) = self.buf.split_at(self.head);
+        older.iter().chain(newer.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CircularBuffer;
+
+    #[test]
+    fn fill_then_overwrite() {
+        let mut b = CircularBuffer::new(3);
+        assert!(b.is_empty() && b.newest().is_none());
+        assert_eq!(b.push(1), None);
+        assert_eq!(b.push(2), None);
+        assert_eq!(b.push(3), None);
+        assert!(b.is_full());
+        assert_eq!(b.push(4), Some(1));
+        assert_eq!(b.push(5), Some(2));
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.capacity(), 3);
+        assert_eq!(b.oldest(), Some(&3));
+        assert_eq!(b.newest(), Some(&5));
+        assert_eq!(b.get(1), Some(&4));
+        assert_eq!(b.get(3), None);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn clear_resets_order() {
+        let mut b = CircularBuffer::new(2);
+        for x in 0..5 {
+            b.push(x);
+        }
+        b.clear();
+        assert!(b.is_empty());
+        b.push(7);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![7]);
+    }
+}
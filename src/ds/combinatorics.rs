@@ -0,0 +1,213 @@
+//! Counting helpers: factorial tables, binomials, permutation/combination iterators.
+//!
+//! - `Factorials<M>`: precomputed `n!` and `1/n!` modulo a prime `M`, giving
+//!   O(1) `binom`/`perm` after an O(n) build.
+//! - `binom(n, k)`: exact value as `u128` (None on overflow), for answers that
+//!   are asked for in full rather than modulo something.
+//! - `permutations(items)` / `combinations(items, k)`: iterators over cloned
+//!   `Vec<T>`s, in lexicographic order of positions, for brute-force orderings.
+
+use crate::ds::modint::ModInt;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Factorials<const M: u64> {
+    fact: Vec<ModInt<M>>,
+    inv_fact: Vec<ModInt<M>>,
+}
+
+impl<const M: u64> Factorials<M> {
+    /// Tables for `0..=n`; `M` must be a prime larger than `n`.
+    pub fn new(n: usize) -> Self {
+        let mut fact = vec![ModInt::from(1u64); n + 1];
+        for i in 1..=n {
+            fact[i] = fact[i - 1] * ModInt::from(i as u64);
+        }
+        let mut inv_fact = vec![ModInt::from(1u64); n + 1];
+        inv_fact[n] = fact[n].inv().expect("M must be a prime > n");
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = inv_fact[i] * ModInt::from(i as u64);
+        }
+        Self { fact, inv_fact }
+    }
+    /// `n!` mod M.
+    pub fn fact(&self, n: usize) -> ModInt<M> {
+        self.fact[n]
+    }
+    /// `(n!)^-1` mod M.
+    pub fn inv_fact(&self, n: usize) -> ModInt<M> {
+        self.inv_fact[n]
+    }
+    /// `C(n, k)` mod M (zero when `k > n`).
+    pub fn binom(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+    /// Ordered selections `n! / (n - k)!` mod M (zero when `k > n`).
+    pub fn perm(&self, n: usize, k: usize) -> ModInt<M> {
+        if k > n {
+            return ModInt::default();
+        }
+        self.fact[n] * self.inv_fact[n - k]
+    }
+}
+
+/// Exact `C(n, k)`, or None if it does not fit in `u128`.
+pub fn binom(n: u64, k: u64) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k) as u128;
+    let mut r: u128 = 1;
+    for i in 1..=k {
+        // r * (n - k + i) is divisible by i; split by gcd to delay overflow.
+        let num = n as u128 - k + i;
+        let g = gcd_u128(r, i);
+        r = (r / g).checked_mul(num / (i / g))?;
+    }
+    Some(r)
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Rearrange `a` into the next lexicographic permutation; false (and sorted
+/// ascending) once `a` was the last one.
+pub fn next_permutation<T: Ord>(a: &mut [T]) -> bool {
+    let Some(i) = (1..a.len()).rev().find(|&i| a[i - 1] < a[i]) else {
+        a.reverse();
+        return false;
+    };
+    let j = (i..a.len()).rev().find(|&j| a[i - 1] < a[j]).unwrap();
+    a.swap(i - 1, j);
+    a[i..].reverse();
+    true
+}
+
+/// Iterator over all orderings of a slice; see `permutations`.
+#[derive(Clone, Debug)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let out = self.idx.iter().map(|&i| self.items[i].clone()).collect();
+        self.done = !next_permutation(&mut self.idx);
+        Some(out)
+    }
+}
+
+/// All `n!` orderings of `items` (by position, so duplicates repeat).
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        idx: (0..items.len()).collect(),
+        done: false,
+    }
+}
+
+/// Iterator over `k`-subsets of a slice; see `combinations`.
+#[derive(Clone, Debug)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let out = self.idx.iter().map(|&i| self.items[i].clone()).collect();
+        let (n, k) = (self.items.len(), self.idx.len());
+        // Advance the rightmost index that still has room to move.
+        match (0..k).rev().find(|&i| self.idx[i] < n - k + i) {
+            Some(i) => {
+                self.idx[i] += 1;
+                for j in i + 1..k {
+                    self.idx[j] = self.idx[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(out)
+    }
+}
+
+/// All `C(n, k)` subsets of `items` of size `k`, each in slice order.
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        idx: (0..k).collect(),
+        done: k > items.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{binom, combinations, next_permutation, permutations, Factorials};
+    use crate::ds::modint::Mod1e9p7;
+
+    #[test]
+    fn binomials_exact_and_modular() {
+        assert_eq!(binom(5, 2), Some(10));
+        assert_eq!(binom(3, 5), Some(0));
+        assert_eq!(
+            binom(100, 50),
+            Some(100_891_344_545_564_193_334_812_497_256)
+        );
+        assert_eq!(binom(200, 100), None);
+        let f = Factorials::<1_000_000_007>::new(100);
+        assert_eq!(f.fact(10).value(), 3_628_800);
+        assert_eq!(
+            f.binom(100, 50).value(),
+            (100_891_344_545_564_193_334_812_497_256u128 % 1_000_000_007) as u64
+        );
+        assert_eq!(f.perm(5, 2).value(), 20);
+        assert_eq!(f.binom(3, 4), Mod1e9p7::default());
+        assert_eq!(f.fact(7) * f.inv_fact(7), Mod1e9p7::from(1u64));
+    }
+
+    #[test]
+    fn permutation_and_combination_iterators() {
+        let ps: Vec<Vec<char>> = permutations(&['a', 'b', 'c']).collect();
+        assert_eq!(ps.len(), 6);
+        assert_eq!(ps[0], vec!['a', 'b', 'c']);
+        assert_eq!(ps[5], vec!['c', 'b', 'a']);
+        assert_eq!(permutations::<i32>(&[]).count(), 1);
+        let cs: Vec<Vec<i32>> = combinations(&[1, 2, 3, 4], 2).collect();
+        assert_eq!(
+            cs,
+            vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![1, 4],
+                vec![2, 3],
+                vec![2, 4],
+                vec![3, 4]
+            ]
+        );
+        assert_eq!(combinations(&[1, 2], 3).count(), 0);
+        assert_eq!(combinations(&[1, 2], 0).count(), 1);
+        let mut a = [1, 2, 2];
+        let mut n = 1;
+        while next_permutation(&mut a) {
+            n += 1;
+        }
+        assert_eq!((n, a), (3, [1, 2, 2]));
+    }
+}
@@ -0,0 +1,117 @@
+//! Cycle detection for deterministic simulations.
+//!
+//! - `find_cycle(x0, step)`: Brent's algorithm. Returns `(mu, lambda)`: the
+//!   first repeated state is reached after `mu` steps and then recurs every
+//!   `lambda` steps. Needs only `Eq` and O(1) memory, at the price of calling
+//!   `step` a few times per state.
+//! - `state_after(x0, step, n)`: the state after `n` steps, simulating only up
+//!   to the first repeat and jumping the remaining whole cycles. Hash-based,
+//!   so `step` runs once per distinct state.
+//!
+//! For puzzles whose full state is huge (falling rocks), step a compact key
+//! (e.g. piece index, jet index, skyline) and extrapolate any tallies with
+//! the returned `(mu, lambda)`.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// `(prefix length, cycle length)` of the sequence `x0, step(x0), ...`.
+pub fn find_cycle<S, F>(x0: S, mut step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Phase 1: find lambda by teleporting the tortoise at powers of two.
+    let (mut power, mut lam) = (1usize, 1usize);
+    let mut tortoise = x0.clone();
+    let mut hare = step(&x0);
+    while tortoise != hare {
+        if power == lam {
+            tortoise = hare.clone();
+            power *= 2;
+            lam = 0;
+        }
+        hare = step(&hare);
+        lam += 1;
+    }
+    // Phase 2: hare starts lambda ahead; advance both until they meet at mu.
+    let (mut tortoise, mut hare) = (x0.clone(), x0);
+    for _ in 0..lam {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+    (mu, lam)
+}
+
+/// State after applying `step` `n` times to `x0`, skipping whole cycles.
+pub fn state_after<S, F>(x0: S, mut step: F, n: u64) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    let mut history = Vec::new();
+    let mut cur = x0;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&cur) {
+            let offset = (n - start) % (i - start);
+            return history.swap_remove((start + offset) as usize);
+        }
+        seen.insert(cur.clone(), i);
+        let next = step(&cur);
+        history.push(cur);
+        cur = next;
+    }
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_cycle, state_after};
+
+    #[test]
+    fn brent_finds_prefix_and_period() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let next = |&x: &u32| if x == 4 { 2 } else { x + 1 };
+        assert_eq!(find_cycle(0, next), (2, 3));
+        assert_eq!(find_cycle(3, next), (0, 3));
+        assert_eq!(find_cycle(7u64, |&x| (x * x + 1) % 255), {
+            // brute force the first repeat
+            let mut seen = vec![7u64];
+            loop {
+                let x = (seen.last().unwrap() * seen.last().unwrap() + 1) % 255;
+                if let Some(p) = seen.iter().position(|&y| y == x) {
+                    break (p, seen.len() - p);
+                }
+                seen.push(x);
+            }
+        });
+    }
+
+    #[test]
+    fn state_after_jumps_cycles() {
+        let next = |&x: &u32| if x == 4 { 2 } else { x + 1 };
+        assert_eq!(state_after(0, next, 0), 0);
+        assert_eq!(state_after(0, next, 3), 3);
+        assert_eq!(state_after(0, next, 5), 2);
+        assert_eq!(state_after(0, next, 1_000_000_000_000), 4); // 2 + (10^12 - 2) % 3
+        let mut calls = 0;
+        let rot = state_after(
+            vec!['a', 'b', 'c', 'd'],
+            |v| {
+                calls += 1;
+                let mut w = v.clone();
+                w.rotate_left(1);
+                w
+            },
+            1_000_000_001,
+        );
+        assert_eq!(rot, vec!['b', 'c', 'd', 'a']);
+        assert_eq!(calls, 4);
+    }
+}
@@ -5,6 +5,7 @@
 //! coordinate space is large and mostly empty.
 
 use crate::ds::coords::Point;
+use crate::ds::string_alg::grid_hash;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DenseGrid2D<T> {
@@ -83,6 +84,15 @@ impl<T> DenseGrid2D<T> {
     }
 }
 
+impl DenseGrid2D<char> {
+    /// Canonical hash of the whole grid (see `string_alg::grid_hash`), handy as a
+    /// cycle-detection key in simulations.
+    pub fn state_hash(&self) -> u128 {
+        let rows: Vec<&[char]> = self.data.chunks(self.w.max(1)).collect();
+        grid_hash(&rows)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +114,15 @@ mod tests {
         let n8: Vec<_> = g.neighbors8(0, 0).into_iter().collect();
         assert!(n8.contains(&Point::new(1, 1)));
     }
+
+    #[test]
+    fn state_hash_tracks_contents() {
+        let mut g = DenseGrid2D::new(3, 2, '.');
+        let h0 = g.state_hash();
+        *g.get_mut(2, 1) = 'O';
+        assert_ne!(g.state_hash(), h0);
+        *g.get_mut(2, 1) = '.';
+        assert_eq!(g.state_hash(), h0);
+        assert_ne!(DenseGrid2D::new(2, 3, '.').state_hash(), h0);
+    }
 }
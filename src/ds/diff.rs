@@ -0,0 +1,136 @@
+//! Difference arrays: many range updates, then one pass to read values.
+//!
+//! `Diff1D` records "add v to every index in `lo..=hi`" in O(1) by touching
+//! only the two ends; `materialize()` takes a prefix sum to recover the
+//! values. `Diff2D` does the same for rectangles with four corner updates and
+//! a 2D prefix sum. Use them when all updates come before all reads (painting
+//! covered cells, counting overlaps); for interleaved updates and queries use
+//! a Fenwick tree instead. `T` should be signed: the end markers are negative.
+
+use std::ops::{Add, Sub};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff1D<T> {
+    d: Vec<T>, // one extra slot so `hi + 1` never needs a bounds check
+}
+
+impl<T: Copy + Default + Add<Output = T> + Sub<Output = T>> Diff1D<T> {
+    /// `len` zeros.
+    pub fn new(len: usize) -> Self {
+        Self {
+            d: vec![T::default(); len + 1],
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.d.len() - 1
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Add `v` to every index in `lo..=hi` (panics if `hi >= len`).
+    pub fn add_range(&mut self, lo: usize, hi: usize, v: T) {
+        assert!(hi < self.len(), "range out of bounds");
+        if lo > hi {
+            return;
+        }
+        self.d[lo] = self.d[lo] + v;
+        self.d[hi + 1] = self.d[hi + 1] - v;
+    }
+    /// The values after all updates.
+    pub fn materialize(&self) -> Vec<T> {
+        let mut acc = T::default();
+        self.d[..self.len()]
+            .iter()
+            .map(|&x| {
+                acc = acc + x;
+                acc
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff2D<T> {
+    width: usize,
+    height: usize,
+    d: Vec<Vec<T>>, // (height + 1) x (width + 1)
+}
+
+impl<T: Copy + Default + Add<Output = T> + Sub<Output = T>> Diff2D<T> {
+    /// `width x height` zeros.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            d: vec![vec![T::default(); width + 1]; height + 1],
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Add `v` to every cell with `x0 <= x <= x1` and `y0 <= y <= y1`.
+    pub fn add_rect(&mut self, x0: usize, y0: usize, x1: usize, y1: usize, v: T) {
+        assert!(
+            x1 < self.width && y1 < self.height,
+            "rectangle out of bounds"
+        );
+        if x0 > x1 || y0 > y1 {
+            return;
+        }
+        let d = &mut self.d;
+        d[y0][x0] = d[y0][x0] + v;
+        d[y0][x1 + 1] = d[y0][x1 + 1] - v;
+        d[y1 + 1][x0] = d[y1 + 1][x0] - v;
+        d[y1 + 1][x1 + 1] = d[y1 + 1][x1 + 1] + v;
+    }
+    /// The values after all updates, indexed `[y][x]`.
+    pub fn materialize(&self) -> Vec<Vec<T>> {
+        let (w, h) = (self.width, self.height);
+        let mut out = vec![vec![T::default(); w]; h];
+        for y in 0..h {
+            for x in 0..w {
+                let mut v = self.d[y][x];
+                if y > 0 {
+                    v = v + out[y - 1][x];
+                }
+                if x > 0 {
+                    v = v + out[y][x - 1];
+                }
+                if y > 0 && x > 0 {
+                    v = v - out[y - 1][x - 1];
+                }
+                out[y][x] = v;
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diff1D, Diff2D};
+
+    #[test]
+    fn ranges_1d() {
+        let mut d = Diff1D::new(6);
+        d.add_range(1, 3, 2i64);
+        d.add_range(3, 5, -1);
+        d.add_range(0, 0, 7);
+        assert_eq!(d.materialize(), vec![7, 2, 2, 1, -1, -1]);
+        assert_eq!(d.len(), 6);
+    }
+
+    #[test]
+    fn overlapping_rects_2d() {
+        let mut d = Diff2D::new(4, 3);
+        d.add_rect(0, 0, 2, 1, 1i32);
+        d.add_rect(1, 1, 3, 2, 1);
+        assert_eq!(
+            d.materialize(),
+            vec![vec![1, 1, 1, 0], vec![1, 2, 2, 1], vec![0, 1, 1, 1]]
+        );
+    }
+}
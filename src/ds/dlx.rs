@@ -0,0 +1,343 @@
+//! Exact cover with Dancing Links (Knuth's Algorithm X).
+//!
+//! Build a `Dlx` over `primary` columns that every solution must cover
+//! exactly once and `secondary` columns that may be covered at most once,
+//! add candidate rows (the columns each one covers), then ask for one
+//! solution (`solve`) or all of them (`count_solutions`). Packing puzzles map
+//! naturally: one primary column per piece kind, one secondary column per
+//! board cell, one row per placement of a piece.
+//!
+//! A primary column can also demand `k` covers (`set_multiplicity`), which is
+//! how `k` identical pieces are packed without trying them in all `k!` orders.
+//!
+//! The matrix is stored as circular doubly linked lists over flat index
+//! arrays; covering a column unlinks it and every row that clashes with it,
+//! and uncovering relinks them in reverse order, so backtracking needs no
+//! copies. The search branches on the primary column with the fewest
+//! remaining rows: each branch takes one of its rows and rules out the rows
+//! tried before it, so every set of rows is reached exactly once.
+
+/// Exact-cover matrix; columns `0..primary` are primary, the next
+/// `secondary` are secondary. Node 0 is the root, node `c + 1` heads column `c`.
+#[derive(Clone, Debug)]
+pub struct Dlx {
+    primary: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header of each node.
+    col: Vec<usize>,
+    /// Row id of each non-header node.
+    row: Vec<usize>,
+    /// Live rows per column, indexed by header node.
+    size: Vec<usize>,
+    /// Covers still required per column, indexed by header node.
+    need: Vec<usize>,
+    rows: usize,
+}
+
+impl Dlx {
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        let headers = primary + secondary + 1;
+        let mut dlx = Dlx {
+            primary,
+            left: (0..headers).collect(),
+            right: (0..headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            col: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            need: vec![1; headers],
+            rows: 0,
+        };
+        // Only primary headers join the root's list; secondary ones stay
+        // self-linked so the search never has to cover them.
+        for h in 1..=primary {
+            dlx.left[h] = h - 1;
+            dlx.right[h - 1] = h;
+        }
+        dlx.left[0] = primary;
+        dlx.right[primary] = 0;
+        dlx
+    }
+
+    /// Require primary column `column` to be covered exactly `times` times
+    /// (default 1). Panics for secondary or out-of-range columns.
+    pub fn set_multiplicity(&mut self, column: usize, times: usize) {
+        assert!(column < self.primary, "column {column} is not primary");
+        self.need[column + 1] = times;
+    }
+
+    /// Number of rows added so far.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Add a row covering `columns` (duplicates ignored) and return its id.
+    /// Panics if a column is out of range.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        let id = self.rows;
+        self.rows += 1;
+        let mut columns = columns.to_vec();
+        columns.sort_unstable();
+        columns.dedup();
+        let mut first = None;
+        for c in columns {
+            let h = c + 1;
+            assert!(h < self.size.len(), "column {c} out of range");
+            let node = self.col.len();
+            self.col.push(h);
+            self.row.push(id);
+            self.up.push(self.up[h]);
+            self.down.push(h);
+            let above = self.up[h];
+            self.down[above] = node;
+            self.up[h] = node;
+            self.size[h] += 1;
+            match first {
+                None => {
+                    self.left.push(node);
+                    self.right.push(node);
+                    first = Some(node);
+                }
+                Some(f) => {
+                    let last = self.left[f];
+                    self.left.push(last);
+                    self.right.push(f);
+                    self.right[last] = node;
+                    self.left[f] = node;
+                }
+            }
+        }
+        id
+    }
+
+    /// One exact cover, as the row ids it uses (in search order), or None.
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        let mut found = None;
+        self.run(&mut |rows| {
+            found = Some(rows.to_vec());
+            true
+        });
+        found
+    }
+
+    /// Number of distinct exact covers (sets of rows).
+    pub fn count_solutions(&mut self) -> u64 {
+        let mut count = 0u64;
+        self.run(&mut |_| {
+            count += 1;
+            false
+        });
+        count
+    }
+
+    /// Search with columns of multiplicity 0 (and their rows) removed up front.
+    fn run(&mut self, visit: &mut dyn FnMut(&[usize]) -> bool) {
+        let unused: Vec<usize> = (1..=self.primary).filter(|&h| self.need[h] == 0).collect();
+        for &h in &unused {
+            self.cover(h);
+        }
+        self.search(&mut Vec::new(), visit);
+        for &h in unused.iter().rev() {
+            self.uncover(h);
+        }
+    }
+
+    /// Algorithm X; `visit` gets each solution and returns true to stop.
+    /// The links are fully restored on return either way.
+    fn search(&mut self, chosen: &mut Vec<usize>, visit: &mut dyn FnMut(&[usize]) -> bool) -> bool {
+        if self.right[0] == 0 {
+            return visit(chosen);
+        }
+        let mut c = self.right[0];
+        let mut h = c;
+        while h != 0 {
+            if self.size[h] < self.size[c] {
+                c = h;
+            }
+            h = self.right[h];
+        }
+        if self.size[c] < self.need[c] {
+            return false;
+        }
+        // Branch i takes the i-th row of `c` and leaves rows 1..i hidden, so
+        // later picks for `c` only come from rows further down.
+        let mut hidden = Vec::new();
+        let mut stop = false;
+        while !stop && self.size[c] >= self.need[c] {
+            let r = self.down[c];
+            self.hide(r);
+            hidden.push(r);
+            chosen.push(self.row[r]);
+            let mut j = r;
+            loop {
+                self.take(self.col[j]);
+                j = self.right[j];
+                if j == r {
+                    break;
+                }
+            }
+            stop = self.search(chosen, visit);
+            let mut j = self.left[r];
+            loop {
+                self.untake(self.col[j]);
+                if j == r {
+                    break;
+                }
+                j = self.left[j];
+            }
+            chosen.pop();
+        }
+        while let Some(r) = hidden.pop() {
+            self.unhide(r);
+        }
+        stop
+    }
+
+    /// Count one cover of column `c`, covering it once none are left to give.
+    fn take(&mut self, c: usize) {
+        self.need[c] -= 1;
+        if self.need[c] == 0 {
+            self.cover(c);
+        }
+    }
+
+    fn untake(&mut self, c: usize) {
+        if self.need[c] == 0 {
+            self.uncover(c);
+        }
+        self.need[c] += 1;
+    }
+
+    /// Unlink every node of row `r` from its column.
+    fn hide(&mut self, r: usize) {
+        let mut j = r;
+        loop {
+            let (u, d) = (self.up[j], self.down[j]);
+            self.down[u] = d;
+            self.up[d] = u;
+            self.size[self.col[j]] -= 1;
+            j = self.right[j];
+            if j == r {
+                break;
+            }
+        }
+    }
+
+    fn unhide(&mut self, r: usize) {
+        let mut j = self.left[r];
+        loop {
+            self.size[self.col[j]] += 1;
+            let (u, d) = (self.up[j], self.down[j]);
+            self.down[u] = j;
+            self.up[d] = j;
+            if j == r {
+                break;
+            }
+            j = self.left[j];
+        }
+    }
+
+    fn cover(&mut self, c: usize) {
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = r;
+        self.left[r] = l;
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.col[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.col[j]] += 1;
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = c;
+        self.left[r] = c;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dlx;
+
+    #[test]
+    fn knuth_example() {
+        // Columns A..G; the unique cover is rows 0, 3 and 4.
+        let rows: [&[usize]; 6] = [
+            &[2, 4, 5],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ];
+        let mut dlx = Dlx::new(7, 0);
+        for r in rows {
+            dlx.add_row(r);
+        }
+        let mut sol = dlx.solve().unwrap();
+        sol.sort_unstable();
+        assert_eq!(sol, vec![0, 3, 4]);
+        assert_eq!(dlx.count_solutions(), 1);
+    }
+
+    #[test]
+    fn secondary_columns_and_counting() {
+        // Two dominoes on a 1x3 strip (cells are secondary): two packings.
+        let mut dlx = Dlx::new(2, 3);
+        for piece in 0..2 {
+            for x in 0..2 {
+                dlx.add_row(&[piece, 2 + x, 3 + x]);
+            }
+        }
+        assert_eq!(dlx.count_solutions(), 0);
+        // One domino and one monomino: domino left or right, monomino fills a free cell.
+        let mut dlx = Dlx::new(2, 3);
+        for x in 0..2 {
+            dlx.add_row(&[0, 2 + x, 3 + x]);
+        }
+        for x in 0..3 {
+            dlx.add_row(&[1, 2 + x]);
+        }
+        assert_eq!(dlx.count_solutions(), 2);
+        assert!(dlx.solve().is_some());
+        assert_eq!(Dlx::new(0, 4).count_solutions(), 1);
+    }
+
+    #[test]
+    fn multiplicity_counts_sets_not_orders() {
+        // Two identical monominoes on a 1x3 strip: C(3, 2) = 3 packings.
+        let mut dlx = Dlx::new(1, 3);
+        dlx.set_multiplicity(0, 2);
+        for x in 0..3 {
+            dlx.add_row(&[0, 1 + x]);
+        }
+        assert_eq!(dlx.count_solutions(), 3);
+        dlx.set_multiplicity(0, 4);
+        assert_eq!(dlx.solve(), None);
+        dlx.set_multiplicity(0, 0);
+        assert_eq!(dlx.count_solutions(), 1);
+    }
+}
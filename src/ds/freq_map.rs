@@ -38,6 +38,44 @@ impl<T: Eq + Hash> FreqMap<T> {
     pub fn is_empty(&self) -> bool {
         self.m.is_empty()
     }
+    /// Add `n` to the count for `k` (no-op when `n` is 0).
+    pub fn add(&mut self, k: T, n: usize) {
+        if n > 0 {
+            *self.m.entry(k).or_default() += n;
+        }
+    }
+    /// Iterate over `(key, count)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.m.iter().map(|(k, &v)| (k, v))
+    }
+    /// Sum of all counts (number of items in the multiset).
+    pub fn total(&self) -> usize {
+        self.m.values().sum()
+    }
+    /// Add `(key, count)` pairs to this map.
+    pub fn extend_counts<I: IntoIterator<Item = (T, usize)>>(&mut self, it: I) {
+        for (k, n) in it {
+            self.add(k, n);
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> FreqMap<T> {
+    /// Add every count from `other` into this map.
+    pub fn merge(&mut self, other: &FreqMap<T>) {
+        self.extend_counts(other.iter().map(|(k, n)| (k.clone(), n)));
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for FreqMap<T> {
+    /// Count occurrences of each item.
+    fn from_iter<I: IntoIterator<Item = T>>(it: I) -> Self {
+        let mut f = FreqMap::new();
+        for k in it {
+            f.inc(k);
+        }
+        f
+    }
 }
 
 #[cfg(test)]
@@ -74,4 +112,31 @@ mod tests {
         f.dec(&'z');
         assert!(f.is_empty());
     }
+
+    #[test]
+    fn collect_total_and_iter() {
+        let f: FreqMap<char> = "abracadabra".chars().collect();
+        assert_eq!(f.get(&'a'), 5);
+        assert_eq!(f.get(&'r'), 2);
+        assert_eq!(f.total(), 11);
+        let mut pairs: Vec<_> = f.iter().map(|(&k, n)| (k, n)).collect();
+        pairs.sort_unstable();
+        assert_eq!(
+            pairs,
+            vec![('a', 5), ('b', 2), ('c', 1), ('d', 1), ('r', 2)]
+        );
+    }
+
+    #[test]
+    fn merge_and_extend_counts() {
+        let mut a: FreqMap<i32> = [1, 1, 2].into_iter().collect();
+        let b: FreqMap<i32> = [2, 3].into_iter().collect();
+        a.merge(&b);
+        assert_eq!((a.get(&1), a.get(&2), a.get(&3)), (2, 2, 1));
+        a.extend_counts([(3, 4), (7, 0)]);
+        assert_eq!(a.get(&3), 5);
+        assert_eq!(a.get(&7), 0);
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.total(), 9);
+    }
 }
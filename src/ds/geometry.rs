@@ -0,0 +1,582 @@
+//! Polygon helpers for lattice points: shoelace area, perimeter, Pick's theorem.
+//!
+//! Polygons are given as their vertices in order (either orientation), with
+//! the closing edge from the last vertex back to the first implied. Areas
+//! come back doubled (`area2`) where exactness matters: a lattice polygon's
+//! area is always a multiple of 1/2.
+//!
+//! Pick's theorem ties them together for simple lattice polygons:
+//! `A = I + B/2 - 1`, where `I` counts lattice points strictly inside and `B`
+//! the lattice points on the boundary. "How many cells does the dug trench
+//! enclose, trench included" is `I + B`.
+//!
+//! `locate_point` / `point_in_polygon` answer containment for any simple
+//! polygon (not just convex) by exact integer ray casting, with points on an
+//! edge or vertex reported as `OnBoundary`.
+//!
+//! `RectilinearRegion` handles the tile view of an axis-aligned loop: the
+//! vertices are grid tiles joined by straight runs of tiles, and the region is
+//! that loop plus every tile it encloses. It coordinate-compresses the loop,
+//! flood-fills the outside once, and keeps prefix sums, so "is this whole
+//! rectangle of tiles inside?" costs two binary searches and O(1) lookups no
+//! matter how large the coordinates are. `largest_rect` finds the biggest
+//! such rectangle spanned by two given tiles, with each corner's compressed
+//! cells found once so every pair costs O(1).
+//!
+//! `segments_intersect` / `segment_intersection` test closed integer segments
+//! exactly (orientation signs in `i128`, so coordinates below 2^62 in
+//! magnitude); a crossing point is returned as `Ratio` coordinates since two
+//! lattice segments can meet off the lattice.
+
+use crate::ds::coords::Point;
+use crate::ds::diff::Diff2D;
+use crate::ds::math::gcd;
+use crate::ds::ratio::Ratio;
+use std::collections::VecDeque;
+
+/// Edges `(a, b)` of the closed polygon.
+fn edges(poly: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    poly.iter()
+        .zip(poly.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Twice the signed area: positive for counter-clockwise vertices (y up).
+pub fn signed_area2(poly: &[Point]) -> i64 {
+    edges(poly).map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+}
+
+/// Twice the (unsigned) area, exact.
+pub fn shoelace_area2(poly: &[Point]) -> i64 {
+    signed_area2(poly).abs()
+}
+
+/// Area as a float (`shoelace_area2 / 2`).
+pub fn shoelace_area(poly: &[Point]) -> f64 {
+    shoelace_area2(poly) as f64 / 2.0
+}
+
+/// Euclidean length of the closed boundary.
+pub fn perimeter(poly: &[Point]) -> f64 {
+    edges(poly)
+        .map(|(a, b)| ((b.x - a.x) as f64).hypot((b.y - a.y) as f64))
+        .sum()
+}
+
+/// Lattice points on the boundary (equals the perimeter for axis-aligned polygons).
+pub fn boundary_points(poly: &[Point]) -> i64 {
+    if poly.len() < 2 {
+        return poly.len() as i64;
+    }
+    edges(poly).map(|(a, b)| gcd(b.x - a.x, b.y - a.y)).sum()
+}
+
+/// Lattice points strictly inside a simple polygon (Pick's theorem).
+pub fn interior_points(poly: &[Point]) -> i64 {
+    if poly.len() < 3 {
+        return 0;
+    }
+    (shoelace_area2(poly) - boundary_points(poly) + 2) / 2
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+fn on_segment(p: Point, a: Point, b: Point) -> bool {
+    cross(a, b, p) == 0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+/// Classify `p` against a simple polygon by casting a ray towards +x.
+/// Exact for coordinates up to 2^62 in magnitude.
+pub fn locate_point(p: Point, poly: &[Point]) -> Location {
+    let mut inside = false;
+    for (a, b) in edges(poly) {
+        if on_segment(p, a, b) {
+            return Location::OnBoundary;
+        }
+        // Half-open rule on y so a ray through a vertex counts it once.
+        if (a.y > p.y) != (b.y > p.y) {
+            // Does the edge cross the ray strictly right of p? Compare
+            // (p.x - a.x) * dy with (p.y - a.y) * dx, flipping for dy < 0.
+            // Widen before subtracting so far-apart points cannot overflow.
+            let d = |q: Point| {
+                (
+                    i128::from(q.x) - i128::from(a.x),
+                    i128::from(q.y) - i128::from(a.y),
+                )
+            };
+            let ((px, py), (dx, dy)) = (d(p), d(b));
+            let (lhs, rhs) = (px * dy, py * dx);
+            if (dy > 0 && lhs < rhs) || (dy < 0 && lhs > rhs) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// True if `p` is inside `poly` or on its boundary.
+pub fn point_in_polygon(p: Point, poly: &[Point]) -> bool {
+    locate_point(p, poly) != Location::Outside
+}
+
+/// How two closed segments meet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SegmentIntersection {
+    /// A single point (possibly off the lattice).
+    Point(Ratio, Ratio),
+    /// Collinear segments sharing the sub-segment between these endpoints.
+    Overlap(Point, Point),
+}
+
+impl SegmentIntersection {
+    /// The meeting point if it is a single lattice point.
+    pub fn lattice_point(&self) -> Option<Point> {
+        match self {
+            Self::Point(x, y) => Some(Point::new(x.to_integer()? as i64, y.to_integer()? as i64)),
+            Self::Overlap(..) => None,
+        }
+    }
+}
+
+/// Twice the signed area of triangle `o a b` (positive when `b` is left of
+/// `o -> a`). Differences are taken in `i128`, so this is exact for
+/// coordinates below 2^62 in magnitude.
+fn cross(o: Point, a: Point, b: Point) -> i128 {
+    let d = |p: Point| {
+        (
+            i128::from(p.x) - i128::from(o.x),
+            i128::from(p.y) - i128::from(o.y),
+        )
+    };
+    let ((ax, ay), (bx, by)) = (d(a), d(b));
+    ax * by - ay * bx
+}
+
+fn key(p: Point) -> (i64, i64) {
+    (p.x, p.y)
+}
+
+/// Orientations of each segment's endpoints against the other's line.
+fn orientations(a1: Point, a2: Point, b1: Point, b2: Point) -> [i128; 4] {
+    [
+        cross(a1, a2, b1),
+        cross(a1, a2, b2),
+        cross(b1, b2, a1),
+        cross(b1, b2, a2),
+    ]
+}
+
+/// True if closed segments `a1-a2` and `b1-b2` share at least one point.
+/// Exact for coordinates below 2^62 in magnitude.
+pub fn segments_intersect(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
+    let [d1, d2, d3, d4] = orientations(a1, a2, b1, b2);
+    if d1 == 0 && d2 == 0 && d3 == 0 && d4 == 0 {
+        return collinear_overlap(a1, a2, b1, b2).is_some();
+    }
+    d1.signum() * d2.signum() <= 0 && d3.signum() * d4.signum() <= 0
+}
+
+/// Where closed segments `a1-a2` and `b1-b2` meet, if anywhere.
+///
+/// Whether they meet is exact for coordinates below 2^62 in magnitude (as
+/// `segments_intersect`). A single crossing point is built with checked
+/// `Ratio` arithmetic, which always fits for coordinates up to about 2^40;
+/// beyond that a point whose exact fraction outgrows `i128` gives None.
+pub fn segment_intersection(
+    a1: Point,
+    a2: Point,
+    b1: Point,
+    b2: Point,
+) -> Option<SegmentIntersection> {
+    let [d1, d2, d3, d4] = orientations(a1, a2, b1, b2);
+    if d1 == 0 && d2 == 0 && d3 == 0 && d4 == 0 {
+        return collinear_overlap(a1, a2, b1, b2);
+    }
+    if d1.signum() * d2.signum() > 0 || d3.signum() * d4.signum() > 0 {
+        return None;
+    }
+    // a1 + t * (a2 - a1) with t = d3 / (d3 - d4).
+    let t = Ratio::new(d3, d3.checked_sub(d4)?)?;
+    let along = |from: i64, to: i64| {
+        let delta = Ratio::from_integer(i128::from(to) - i128::from(from));
+        Ratio::from(from).checked_add(t.checked_mul(delta)?)
+    };
+    Some(SegmentIntersection::Point(
+        along(a1.x, a2.x)?,
+        along(a1.y, a2.y)?,
+    ))
+}
+
+/// Shared part of two collinear (or degenerate) segments, if any.
+fn collinear_overlap(a1: Point, a2: Point, b1: Point, b2: Point) -> Option<SegmentIntersection> {
+    let (alo, ahi) = if key(a1) <= key(a2) {
+        (a1, a2)
+    } else {
+        (a2, a1)
+    };
+    let (blo, bhi) = if key(b1) <= key(b2) {
+        (b1, b2)
+    } else {
+        (b2, b1)
+    };
+    let lo = if key(alo) >= key(blo) { alo } else { blo };
+    let hi = if key(ahi) <= key(bhi) { ahi } else { bhi };
+    if key(lo) > key(hi) {
+        return None;
+    }
+    // A point-segment collinear with the other line must still lie on it.
+    if !on_segment(lo, a1, a2) || !on_segment(lo, b1, b2) {
+        return None;
+    }
+    Some(if lo == hi {
+        SegmentIntersection::Point(Ratio::from(lo.x), Ratio::from(lo.y))
+    } else {
+        SegmentIntersection::Overlap(lo, hi)
+    })
+}
+
+/// Tiles on or enclosed by an axis-aligned loop; see the module docs.
+///
+/// Internally coordinates are doubled so tile `x` spans `[2x - 1, 2x + 1]`;
+/// compressed cell `i` spans `[xs[i], xs[i + 1])` and is uniformly in or out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RectilinearRegion {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    outside_prefix: Vec<Vec<i64>>,
+    area: i64,
+}
+
+/// Sorted, deduplicated tile edges (doubled) plus a padding ring.
+fn compress_axis(values: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut cs: Vec<i64> = values.flat_map(|v| [2 * v - 1, 2 * v + 1]).collect();
+    let (lo, hi) = (cs.iter().min().copied(), cs.iter().max().copied());
+    cs.extend(lo.map(|v| v - 2));
+    cs.extend(hi.map(|v| v + 2));
+    cs.sort_unstable();
+    cs.dedup();
+    cs
+}
+
+fn axis_index(cs: &[i64], v: i64) -> usize {
+    cs.binary_search(&v)
+        .expect("coordinate is on the compressed axis")
+}
+
+impl RectilinearRegion {
+    /// Build from the loop's corner tiles in order (panics on a diagonal edge
+    /// or fewer than two vertices).
+    pub fn new(poly: &[Point]) -> Self {
+        assert!(poly.len() >= 2, "need at least two vertices");
+        let xs = compress_axis(poly.iter().map(|p| p.x));
+        let ys = compress_axis(poly.iter().map(|p| p.y));
+        let (w, h) = (xs.len() - 1, ys.len() - 1);
+        // Mark the loop's tiles with a 2D difference array over compressed cells.
+        let mut diff = Diff2D::new(w, h);
+        for (a, b) in edges(poly) {
+            assert!(a.x == b.x || a.y == b.y, "edges must be axis-aligned");
+            let x0 = axis_index(&xs, 2 * a.x.min(b.x) - 1);
+            let x1 = axis_index(&xs, 2 * a.x.max(b.x) + 1);
+            let y0 = axis_index(&ys, 2 * a.y.min(b.y) - 1);
+            let y1 = axis_index(&ys, 2 * a.y.max(b.y) + 1);
+            diff.add_rect(x0, y0, x1 - 1, y1 - 1, 1i32);
+        }
+        let blocked = diff.materialize();
+        // The padding ring guarantees cell (0, 0) is outside.
+        let mut outside = vec![vec![false; w]; h];
+        let mut queue = VecDeque::from([(0usize, 0usize)]);
+        outside[0][0] = true;
+        while let Some((y, x)) = queue.pop_front() {
+            let nbrs = [
+                (y.wrapping_sub(1), x),
+                (y + 1, x),
+                (y, x.wrapping_sub(1)),
+                (y, x + 1),
+            ];
+            for (ny, nx) in nbrs {
+                if ny < h && nx < w && blocked[ny][nx] == 0 && !outside[ny][nx] {
+                    outside[ny][nx] = true;
+                    queue.push_back((ny, nx));
+                }
+            }
+        }
+        let mut outside_prefix = vec![vec![0i64; w + 1]; h + 1];
+        let mut area = 0;
+        for y in 0..h {
+            let mut row_sum = 0i64;
+            for x in 0..w {
+                if outside[y][x] {
+                    row_sum += 1;
+                } else {
+                    area += (xs[x + 1] - xs[x]) / 2 * ((ys[y + 1] - ys[y]) / 2);
+                }
+                outside_prefix[y + 1][x + 1] = outside_prefix[y][x + 1] + row_sum;
+            }
+        }
+        Self {
+            xs,
+            ys,
+            outside_prefix,
+            area,
+        }
+    }
+
+    /// Compressed cells `[lo, hi)` covering the doubled span `[a, b]`, or
+    /// None if it reaches past the padding (and so is certainly outside).
+    fn cell_span(cs: &[i64], a: i64, b: i64) -> Option<(usize, usize)> {
+        if a < cs[0] || b > cs[cs.len() - 1] {
+            return None;
+        }
+        let lo = cs.partition_point(|&v| v <= a) - 1;
+        let hi = cs.partition_point(|&v| v < b);
+        Some((lo, hi))
+    }
+
+    /// True if every tile in the rectangle with corners `a` and `b` is in the region.
+    pub fn contains_rect(&self, a: Point, b: Point) -> bool {
+        let xspan = Self::cell_span(&self.xs, 2 * a.x.min(b.x) - 1, 2 * a.x.max(b.x) + 1);
+        let yspan = Self::cell_span(&self.ys, 2 * a.y.min(b.y) - 1, 2 * a.y.max(b.y) + 1);
+        let (Some((x0, x1)), Some((y0, y1))) = (xspan, yspan) else {
+            return false;
+        };
+        let p = &self.outside_prefix;
+        p[y1][x1] - p[y0][x1] - p[y1][x0] + p[y0][x0] == 0
+    }
+
+    /// True if tile `p` is on the loop or enclosed by it.
+    pub fn contains_point(&self, p: Point) -> bool {
+        self.contains_rect(p, p)
+    }
+
+    /// Number of tiles in the region (loop included).
+    pub fn area(&self) -> i64 {
+        self.area
+    }
+
+    /// Largest rectangle wholly in the region whose opposite corners are two
+    /// of `corners`, as `(a, b, tiles)`; None if no pair fits. The tile count
+    /// is an `i128` so spans near the `i64` limits can't overflow it.
+    ///
+    /// Each corner's compressed cells are looked up once, so testing a pair is
+    /// four prefix-sum reads instead of `contains_rect`'s binary searches, and
+    /// pairs no larger than the best so far are skipped without a test.
+    pub fn largest_rect(&self, corners: &[Point]) -> Option<(Point, Point, i128)> {
+        // Corners outside the padding can't span an inside rectangle.
+        let cells: Vec<CornerCells> = corners
+            .iter()
+            .filter_map(|&p| {
+                Some(CornerCells {
+                    corner: p,
+                    x: Self::cell_span(&self.xs, 2 * p.x - 1, 2 * p.x + 1)?,
+                    y: Self::cell_span(&self.ys, 2 * p.y - 1, 2 * p.y + 1)?,
+                })
+            })
+            .collect();
+        let p = &self.outside_prefix;
+        let mut best: Option<(Point, Point, i128)> = None;
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                let (ca, cb) = (a.corner, b.corner);
+                let width = (i128::from(ca.x) - i128::from(cb.x)).abs() + 1;
+                let height = (i128::from(ca.y) - i128::from(cb.y)).abs() + 1;
+                let area = width * height;
+                if best.is_some_and(|(_, _, top)| area <= top) {
+                    continue;
+                }
+                let (x0, x1) = (a.x.0.min(b.x.0), a.x.1.max(b.x.1));
+                let (y0, y1) = (a.y.0.min(b.y.0), a.y.1.max(b.y.1));
+                if p[y1][x1] - p[y0][x1] - p[y1][x0] + p[y0][x0] == 0 {
+                    best = Some((ca, cb, area));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// A corner tile with the compressed cell ranges its tile spans in x and y.
+struct CornerCells {
+    corner: Point,
+    x: (usize, usize),
+    y: (usize, usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(v: &[(i64, i64)]) -> Vec<Point> {
+        v.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn square_and_triangle() {
+        let sq = poly(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(signed_area2(&sq), 24);
+        let mut cw = sq.clone();
+        cw.reverse();
+        assert_eq!(signed_area2(&cw), -24);
+        assert_eq!(shoelace_area(&cw), 12.0);
+        assert_eq!(perimeter(&sq), 14.0);
+        assert_eq!(boundary_points(&sq), 14);
+        assert_eq!(interior_points(&sq), 3 * 2);
+        let tri = poly(&[(0, 0), (3, 0), (0, 3)]);
+        assert_eq!(shoelace_area2(&tri), 9);
+        assert_eq!(boundary_points(&tri), 9);
+        assert_eq!(interior_points(&tri), 1);
+    }
+
+    #[test]
+    fn trench_cells_enclosed() {
+        // A dug loop of unit cells whose centers trace this rectilinear path.
+        let p = poly(&[
+            (0, 0),
+            (6, 0),
+            (6, 5),
+            (4, 5),
+            (4, 7),
+            (6, 7),
+            (6, 9),
+            (0, 9),
+        ]);
+        let cells = interior_points(&p) + boundary_points(&p);
+        // 7x10 bounding box minus the 2x1 notch cut at x 5..=6, y 6.
+        assert_eq!(cells, 70 - 2);
+    }
+
+    #[test]
+    fn containment_matches_pick_count() {
+        // Concave "U" shape.
+        let u = poly(&[
+            (0, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 2),
+            (2, 2),
+            (2, 6),
+            (0, 6),
+        ]);
+        assert_eq!(locate_point(Point::new(1, 3), &u), Location::Inside);
+        assert_eq!(locate_point(Point::new(3, 3), &u), Location::Outside);
+        assert_eq!(locate_point(Point::new(3, 2), &u), Location::OnBoundary);
+        assert_eq!(locate_point(Point::new(4, 6), &u), Location::OnBoundary);
+        assert_eq!(locate_point(Point::new(-1, 0), &u), Location::Outside);
+        assert!(point_in_polygon(Point::new(5, 1), &u));
+        // Edges spanning 2^63, so the i64 differences would overflow.
+        let (lo, hi) = (-(1 << 62), 1 << 62);
+        let square = [(lo, lo), (hi, lo), (hi, hi), (lo, hi)].map(|(x, y)| Point::new(x, y));
+        assert_eq!(locate_point(Point::new(0, 0), &square), Location::Inside);
+        assert_eq!(
+            locate_point(Point::new(hi, 0), &square),
+            Location::OnBoundary
+        );
+        assert_eq!(
+            locate_point(Point::new(-hi - 1, 0), &square),
+            Location::Outside
+        );
+        let mut inside = 0;
+        let mut boundary = 0;
+        for x in -1..=7 {
+            for y in -1..=7 {
+                match locate_point(Point::new(x, y), &u) {
+                    Location::Inside => inside += 1,
+                    Location::OnBoundary => boundary += 1,
+                    Location::Outside => {}
+                }
+            }
+        }
+        assert_eq!(
+            (inside, boundary),
+            (interior_points(&u), boundary_points(&u))
+        );
+    }
+
+    #[test]
+    fn rectilinear_region_queries() {
+        // Same "U" as above, read as a loop of tiles.
+        let u = poly(&[
+            (0, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 2),
+            (2, 2),
+            (2, 6),
+            (0, 6),
+        ]);
+        let r = RectilinearRegion::new(&u);
+        assert_eq!(r.area(), interior_points(&u) + boundary_points(&u));
+        assert!(r.contains_point(Point::new(1, 5)));
+        assert!(r.contains_point(Point::new(2, 6)));
+        assert!(!r.contains_point(Point::new(3, 3)));
+        assert!(!r.contains_point(Point::new(-5, 100)));
+        assert!(r.contains_rect(Point::new(0, 0), Point::new(6, 2)));
+        assert!(r.contains_rect(Point::new(5, 6), Point::new(4, 1)));
+        assert!(!r.contains_rect(Point::new(0, 0), Point::new(6, 3)));
+        assert!(!r.contains_rect(Point::new(0, 0), Point::new(7, 0)));
+        // Either 3x7 arm or the 7x3 base; never across the notch.
+        assert_eq!(r.largest_rect(&u).map(|(_, _, area)| area), Some(21));
+        assert_eq!(r.largest_rect(&u[..1]), None);
+        for x in -1..=7 {
+            for y in -1..=7 {
+                let p = Point::new(x, y);
+                assert_eq!(r.contains_point(p), point_in_polygon(p, &u));
+            }
+        }
+    }
+
+    #[test]
+    fn segment_intersections() {
+        let p = Point::new;
+        // Axis-aligned wires crossing at (3, 2).
+        let hit = segment_intersection(p(0, 2), p(8, 2), p(3, -1), p(3, 5)).unwrap();
+        assert_eq!(hit.lattice_point(), Some(p(3, 2)));
+        // Diagonals meeting off the lattice at (1/2, 1/2).
+        let hit = segment_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 0)).unwrap();
+        let half = Ratio::new(1, 2).unwrap();
+        assert_eq!(hit, SegmentIntersection::Point(half, half));
+        assert_eq!(hit.lattice_point(), None);
+        // Touching at an endpoint, parallel, and collinear overlap/disjoint.
+        assert!(segments_intersect(p(0, 0), p(2, 0), p(2, 0), p(2, 5)));
+        assert!(!segments_intersect(p(0, 0), p(2, 0), p(0, 1), p(2, 1)));
+        assert_eq!(
+            segment_intersection(p(0, 0), p(4, 4), p(6, 6), p(2, 2)),
+            Some(SegmentIntersection::Overlap(p(2, 2), p(4, 4)))
+        );
+        assert!(!segments_intersect(p(0, 0), p(1, 1), p(2, 2), p(3, 3)));
+        assert!(!segments_intersect(p(0, 0), p(2, 0), p(3, 0), p(3, 0)));
+        assert!(!segments_intersect(p(0, 0), p(4, 0), p(1, 1), p(1, 1)));
+        assert!(!segments_intersect(p(0, 0), p(1, 0), p(2, -1), p(2, 1)));
+        // Near the i64 limits: differences would overflow before widening.
+        let (lo, hi) = (-(1 << 61), 1 << 61);
+        assert!(segments_intersect(
+            p(lo, lo),
+            p(hi, hi),
+            p(lo, hi),
+            p(hi, lo)
+        ));
+        let hit = segment_intersection(p(lo, 0), p(hi, 0), p(0, lo), p(0, hi)).unwrap();
+        assert_eq!(hit.lattice_point(), Some(p(0, 0)));
+        assert!(!segments_intersect(
+            p(lo, lo),
+            p(hi, lo),
+            p(lo, hi),
+            p(hi, hi)
+        ));
+    }
+}
@@ -4,8 +4,13 @@
 //! nodes are hashable (numbers, strings, etc.). It stores a directed adjacency
 //! list and provides helpers for adding edges, iterating neighbors, and
 //! computing indegrees (useful for topological sorting).
+//!
+//! `WeightedAdjacency` is the same idea with `(neighbor, weight)` pairs, and
+//! can hand its edges straight to the indexed or closure-based searches.
 
-use std::collections::HashMap;
+use crate::ds::search::dijkstra_indexed;
+use crate::ds::topo::{dag_dp, topo_order};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -32,6 +37,32 @@ impl<N: Eq + Hash + Clone> Adjacency<N> {
         self.add_edge(a.clone(), b.clone());
         self.add_edge(b, a);
     }
+    /// True if there is at least one edge `from -> to`.
+    pub fn contains_edge(&self, from: &N, to: &N) -> bool {
+        self.adj.get(from).is_some_and(|v| v.contains(to))
+    }
+    /// Remove every `from -> to` edge; returns true if any was present.
+    pub fn remove_edge(&mut self, from: &N, to: &N) -> bool {
+        match self.adj.get_mut(from) {
+            Some(v) => {
+                let before = v.len();
+                v.retain(|x| x != to);
+                v.len() != before
+            }
+            None => false,
+        }
+    }
+    /// Remove node `n`, its outgoing edges, and every edge pointing at it.
+    /// Returns true if `n` was present as a key or a neighbor.
+    pub fn remove_node(&mut self, n: &N) -> bool {
+        let mut found = self.adj.remove(n).is_some();
+        for v in self.adj.values_mut() {
+            let before = v.len();
+            v.retain(|x| x != n);
+            found |= v.len() != before;
+        }
+        found
+    }
     /// Borrowing iterator over neighbors of `n` (empty if `n` not present).
     pub fn neighbors<'a>(&'a self, n: &N) -> impl Iterator<Item = &'a N> + 'a {
         self.adj.get(n).into_iter().flatten()
@@ -40,6 +71,65 @@ impl<N: Eq + Hash + Clone> Adjacency<N> {
     pub fn nodes(&self) -> impl Iterator<Item = &N> {
         self.adj.keys()
     }
+    /// Index every node so the graph can be passed to `search`, `scc` and
+    /// `topo` helpers: returns (`adj`, index -> node, node -> index). Nodes that
+    /// only appear as neighbors get indices too; index order is unspecified.
+    pub fn to_indexed(&self) -> IndexedGraph<usize, N> {
+        index_map(&self.adj, |v| v, |iv, _| iv)
+    }
+    /// The same graph with every edge flipped (`a -> b` becomes `b -> a`).
+    /// Every node of `self` is present in the result, even without edges.
+    pub fn reversed(&self) -> Self {
+        let mut r = Self::new();
+        for (u, vs) in &self.adj {
+            r.add_node(u.clone());
+            for v in vs {
+                r.add_edge(v.clone(), u.clone());
+            }
+        }
+        r
+    }
+    /// Every node reachable from `start` along directed edges (including `start`).
+    pub fn reachable_from(&self, start: &N) -> HashSet<N> {
+        let mut seen = HashSet::from([start.clone()]);
+        let mut q = VecDeque::from([start.clone()]);
+        while let Some(u) = q.pop_front() {
+            for v in self.neighbors(&u) {
+                if seen.insert(v.clone()) {
+                    q.push_back(v.clone());
+                }
+            }
+        }
+        seen
+    }
+    /// Connected components, ignoring edge direction. Each node (key or
+    /// neighbor) appears in exactly one component; order is unspecified.
+    pub fn connected_components(&self) -> Vec<Vec<N>> {
+        let (adj, names, _) = self.to_indexed();
+        let rev = reverse_graph(&adj);
+        let mut comp_of = vec![usize::MAX; names.len()];
+        let mut comps: Vec<Vec<N>> = Vec::new();
+        for s in 0..names.len() {
+            if comp_of[s] != usize::MAX {
+                continue;
+            }
+            let c = comps.len();
+            comp_of[s] = c;
+            let mut members = Vec::new();
+            let mut st = vec![s];
+            while let Some(u) = st.pop() {
+                members.push(names[u].clone());
+                for &v in adj[u].iter().chain(&rev[u]) {
+                    if comp_of[v] == usize::MAX {
+                        comp_of[v] = c;
+                        st.push(v);
+                    }
+                }
+            }
+            comps.push(members);
+        }
+        comps
+    }
     /// Compute indegree counts for every node mentioned either as a key or as a neighbor.
     pub fn indegrees(&self) -> HashMap<N, usize> {
         let mut indeg: HashMap<N, usize> = self.adj.keys().cloned().map(|n| (n, 0)).collect();
@@ -52,9 +142,181 @@ impl<N: Eq + Hash + Clone> Adjacency<N> {
     }
 }
 
+/// An adjacency map converted to dense indices: (`adj[u]` edge lists,
+/// index -> node, node -> index). Edges are `usize` or `(usize, i64)`.
+pub type IndexedGraph<E, N> = (Vec<Vec<E>>, Vec<N>, HashMap<N, usize>);
+
+/// Indexed graph with every edge flipped, for "who can reach me" queries.
+pub fn reverse_graph(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut r = vec![Vec::new(); adj.len()];
+    for (u, vs) in adj.iter().enumerate() {
+        for &v in vs {
+            r[v].push(u);
+        }
+    }
+    r
+}
+
+/// Count `src` -> `dst` paths in an indexed graph, split by which labels
+/// they pass through. `node_label_mask[u]` is a bitmask of labels carried by
+/// node `u` (0 for none); entry `m` of the result is the number of paths
+/// whose nodes' labels OR together to exactly `m`, so the paths visiting
+/// every required node are at `result[required]`. Paths end at `dst`.
+///
+/// A `dag_dp` fold over the part of the graph reachable from `src` (not
+/// continuing past `dst`): O((V + E) · M) for M = (OR of all labels) + 1.
+/// Only that part has to be acyclic; `Err(u)` names a node on a cycle found
+/// there.
+pub fn count_paths_dag(
+    adj: &[Vec<usize>],
+    src: usize,
+    dst: usize,
+    node_label_mask: &[u32],
+) -> Result<Vec<u128>, usize> {
+    let width = node_label_mask.iter().fold(0, |acc, &m| acc | m) as usize + 1;
+    // Reachable nodes, renumbered in BFS order so `src` is 0.
+    let mut id = vec![usize::MAX; adj.len()];
+    let mut nodes = vec![src];
+    id[src] = 0;
+    let mut next = 0;
+    while let Some(&u) = nodes.get(next) {
+        next += 1;
+        if u == dst {
+            continue;
+        }
+        for &v in &adj[u] {
+            if id[v] == usize::MAX {
+                id[v] = nodes.len();
+                nodes.push(v);
+            }
+        }
+    }
+    let sub: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|&u| {
+            if u == dst {
+                Vec::new()
+            } else {
+                adj[u].iter().map(|&v| id[v]).collect()
+            }
+        })
+        .collect();
+    let order = topo_order(&sub).map_err(|cycle| nodes[cycle[0]])?;
+    let mut counts = dag_dp(&sub, &order, |i, preds: &[(usize, &Vec<u128>)]| {
+        let own = node_label_mask[nodes[i]] as usize;
+        let mut totals = vec![0u128; width];
+        if i == 0 {
+            totals[own] = 1;
+        }
+        for (_, below) in preds {
+            for (mask, &count) in below.iter().enumerate() {
+                totals[mask | own] += count;
+            }
+        }
+        totals
+    });
+    Ok(match id[dst] {
+        usize::MAX => vec![0; width],
+        d => counts.swap_remove(d),
+    })
+}
+
+/// Shared by both adjacency types: assign indices to every node (keys and
+/// neighbors alike) and rewrite each edge list with `edge(target_index, e)`.
+fn index_map<N: Eq + Hash + Clone, T, E>(
+    adj: &HashMap<N, Vec<T>>,
+    target: impl Fn(&T) -> &N,
+    edge: impl Fn(usize, &T) -> E,
+) -> IndexedGraph<E, N> {
+    let mut names: Vec<N> = Vec::new();
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut id_of = |n: &N| {
+        *ids.entry(n.clone()).or_insert_with(|| {
+            names.push(n.clone());
+            names.len() - 1
+        })
+    };
+    let mut edges = Vec::new();
+    for (u, ts) in adj {
+        let iu = id_of(u);
+        for t in ts {
+            edges.push((iu, edge(id_of(target(t)), t)));
+        }
+    }
+    let mut out: Vec<Vec<E>> = (0..names.len()).map(|_| Vec::new()).collect();
+    for (u, e) in edges {
+        out[u].push(e);
+    }
+    (out, names, ids)
+}
+
+/// Directed adjacency map with weighted edges.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WeightedAdjacency<N: Eq + Hash + Clone, W> {
+    adj: HashMap<N, Vec<(N, W)>>, // directed
+}
+
+impl<N: Eq + Hash + Clone, W: Copy> WeightedAdjacency<N, W> {
+    pub fn new() -> Self {
+        Self {
+            adj: HashMap::new(),
+        }
+    }
+    /// Ensure a node exists even if it has no outgoing edges.
+    pub fn add_node(&mut self, n: N) {
+        self.adj.entry(n).or_default();
+    }
+    /// Add a directed edge `from -> to` with weight `w`.
+    pub fn add_edge(&mut self, from: N, to: N, w: W) {
+        self.adj.entry(from).or_default().push((to, w));
+    }
+    /// Add an undirected edge by inserting both directions with the same weight.
+    pub fn add_undirected(&mut self, a: N, b: N, w: W) {
+        self.add_edge(a.clone(), b.clone(), w);
+        self.add_edge(b, a, w);
+    }
+    /// Borrowing iterator over `(neighbor, weight)` pairs of `n`.
+    pub fn neighbors<'a>(&'a self, n: &N) -> impl Iterator<Item = &'a (N, W)> + 'a {
+        self.adj.get(n).into_iter().flatten()
+    }
+    /// Iterator over all nodes that have been mentioned on the left-hand side.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.adj.keys()
+    }
+}
+
+impl<N: Eq + Hash + Clone, W: Copy + Into<i64>> WeightedAdjacency<N, W> {
+    /// Owned `(neighbor, cost)` pairs of `n`, ready to return from the
+    /// `successors` closure of `dijkstra_hash` or `astar`.
+    pub fn successors(&self, n: &N) -> Vec<(N, i64)> {
+        self.neighbors(n)
+            .map(|(v, w)| (v.clone(), (*w).into()))
+            .collect()
+    }
+    /// Index every node: returns (`adj_w` for `dijkstra_indexed`/`astar_indexed`,
+    /// index -> node, node -> index). Index order is unspecified.
+    pub fn to_indexed(&self) -> IndexedGraph<(usize, i64), N> {
+        index_map(&self.adj, |(v, _)| v, |iv, (_, w)| (iv, (*w).into()))
+    }
+    /// Shortest distances from `start` to every reachable node (Dijkstra).
+    pub fn shortest_paths(&self, start: &N) -> HashMap<N, i64> {
+        let (adj_w, names, ids) = self.to_indexed();
+        let Some(&s) = ids.get(start) else {
+            return HashMap::new();
+        };
+        let (dist, _) = dijkstra_indexed(names.len(), &adj_w, s);
+        names
+            .into_iter()
+            .zip(dist)
+            .filter(|&(_, d)| d != i64::MAX)
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Adjacency;
+    use super::{count_paths_dag, reverse_graph, Adjacency, WeightedAdjacency};
+    use crate::ds::search::bfs_distances;
 
     #[test]
     fn basic() {
@@ -81,4 +343,116 @@ mod tests {
         let neighbors2: Vec<_> = g.neighbors(&2).cloned().collect();
         assert!(neighbors2.contains(&1));
     }
+
+    #[test]
+    fn remove_edges_and_nodes() {
+        let mut g = Adjacency::new();
+        g.add_edge("a", "b");
+        g.add_edge("a", "b");
+        g.add_edge("a", "c");
+        g.add_edge("c", "b");
+        assert!(g.contains_edge(&"a", &"b") && !g.contains_edge(&"b", &"a"));
+        assert!(g.remove_edge(&"a", &"b"));
+        assert!(!g.contains_edge(&"a", &"b"));
+        assert!(!g.remove_edge(&"a", &"b"));
+        g.add_edge("a", "b");
+        assert!(g.remove_node(&"b"));
+        assert_eq!(g.neighbors(&"a").collect::<Vec<_>>(), vec![&"c"]);
+        assert_eq!(g.neighbors(&"c").count(), 0);
+        assert!(!g.indegrees().contains_key(&"b"));
+        assert!(!g.remove_node(&"z"));
+    }
+
+    #[test]
+    fn weighted_feeds_searches() {
+        let mut g: WeightedAdjacency<&str, u32> = WeightedAdjacency::new();
+        g.add_edge("a", "b", 4);
+        g.add_edge("a", "c", 1);
+        g.add_undirected("c", "b", 2);
+        g.add_node("z");
+        let d = g.shortest_paths(&"a");
+        assert_eq!((d[&"a"], d[&"b"], d[&"c"]), (0, 3, 1));
+        assert!(!d.contains_key(&"z"));
+        let (adj_w, names, ids) = g.to_indexed();
+        assert_eq!(names.len(), 4);
+        assert!(adj_w[ids["a"]].contains(&(ids["c"], 1)));
+        let (cost, path) =
+            crate::ds::search::dijkstra_hash("a", |n| g.successors(n), |n| *n == "b").unwrap();
+        assert_eq!((cost, path), (3, vec!["a", "c", "b"]));
+    }
+
+    #[test]
+    fn to_indexed_composes_with_search() {
+        let mut g = Adjacency::new();
+        g.add_edge("you", "a");
+        g.add_edge("a", "out");
+        g.add_node("lonely");
+        let (adj, names, ids) = g.to_indexed();
+        assert_eq!(adj.len(), 4);
+        assert_eq!(names[ids["out"]], "out");
+        let dist = bfs_distances(adj.len(), &adj, ids["you"]);
+        assert_eq!(dist[ids["out"]], 2);
+        assert_eq!(dist[ids["lonely"]], -1);
+    }
+
+    #[test]
+    fn reversed_graphs() {
+        let mut g = Adjacency::new();
+        g.add_edge(1, 2);
+        g.add_edge(1, 3);
+        g.add_edge(3, 2);
+        let r = g.reversed();
+        let mut into2: Vec<_> = r.neighbors(&2).cloned().collect();
+        into2.sort();
+        assert_eq!(into2, vec![1, 3]);
+        assert_eq!(r.neighbors(&1).count(), 0);
+        assert!(r.nodes().any(|&n| n == 1));
+        assert_eq!(
+            reverse_graph(&[vec![1, 2], vec![2], vec![]]),
+            vec![vec![], vec![0], vec![0, 1]]
+        );
+    }
+
+    #[test]
+    fn components_and_reachability() {
+        let mut g = Adjacency::new();
+        g.add_edge('a', 'b');
+        g.add_edge('c', 'b');
+        g.add_edge('d', 'e');
+        g.add_node('f');
+        let mut comps: Vec<Vec<char>> = g
+            .connected_components()
+            .into_iter()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+        comps.sort();
+        assert_eq!(comps, vec![vec!['a', 'b', 'c'], vec!['d', 'e'], vec!['f']]);
+        let r = g.reachable_from(&'a');
+        assert!(r.contains(&'a') && r.contains(&'b') && !r.contains(&'c'));
+        assert_eq!(g.reachable_from(&'z').len(), 1);
+    }
+
+    #[test]
+    fn dag_paths_split_by_label_mask() {
+        // 0 -> {1, 2} -> 3 -> 4, plus a shortcut 0 -> 4; node 1 has label
+        // bit 0 and node 3 has label bit 1.
+        let adj = vec![vec![1, 2, 4], vec![3], vec![3], vec![4], vec![]];
+        let labels = [0, 1, 0, 2, 0];
+        let counts = count_paths_dag(&adj, 0, 4, &labels).unwrap();
+        assert_eq!(counts, vec![1, 0, 1, 1]);
+        assert_eq!(count_paths_dag(&adj, 4, 0, &[0; 5]).unwrap(), vec![0]);
+        let cyclic = vec![vec![1], vec![0, 2], vec![]];
+        assert!(count_paths_dag(&cyclic, 0, 2, &[0; 3]).is_err());
+        assert_eq!(count_paths_dag(&cyclic, 2, 2, &[0; 3]).unwrap(), vec![1]);
+        // A long chain must not recurse once per node.
+        let n = 200_000;
+        let chain: Vec<Vec<usize>> = (0..n).map(|u| (u + 1..n).take(1).collect()).collect();
+        assert_eq!(
+            count_paths_dag(&chain, 0, n - 1, &vec![0; n]).unwrap(),
+            vec![1]
+        );
+    }
 }
@@ -11,6 +11,8 @@
 //! - `prio`: stores the current priority for each item (or None if absent)
 //! - `set(idx, p)`: inserts or updates priority; it fixes heap order by
 //!   bubbling up or down depending on whether the priority improved or worsened.
+//! - Indices past the initial size are accepted by `set` (the index tables
+//!   grow), so searches that discover states on the fly can use it too.
 
 #[derive(Clone, Debug)]
 pub struct IndexedMinHeap<P: Ord + Copy> {
@@ -77,6 +79,10 @@ impl<P: Ord + Copy> IndexedMinHeap<P> {
     /// Insert or update priority of item `idx`.
     /// Insert `idx` with priority `p`, or update its priority if present.
     pub fn set(&mut self, idx: usize, p: P) {
+        if idx >= self.pos.len() {
+            self.pos.resize(idx + 1, None);
+            self.prio.resize(idx + 1, None);
+        }
         match (self.pos[idx], self.prio[idx]) {
             (Some(i), Some(old)) => {
                 self.prio[idx] = Some(p);
@@ -97,7 +103,7 @@ impl<P: Ord + Copy> IndexedMinHeap<P> {
     }
     /// Check whether `idx` is currently in the heap.
     pub fn contains(&self, idx: usize) -> bool {
-        self.pos[idx].is_some()
+        self.pos.get(idx).is_some_and(|p| p.is_some())
     }
     /// Pop the minimum (idx, priority) if present.
     pub fn pop_min(&mut self) -> Option<(usize, P)> {
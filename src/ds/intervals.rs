@@ -38,9 +38,10 @@ impl IntervalSet {
         let mut res: Vec<Interval> = Vec::with_capacity(self.v.len() + 1);
         let mut inserted = false;
         for cur in self.v.drain(..) {
-            if cur.end < iv.start - 1 {
+            // Saturating so intervals starting at i64::MIN don't overflow.
+            if cur.end < iv.start.saturating_sub(1) {
                 res.push(cur);
-            } else if iv.end < cur.start - 1 {
+            } else if iv.end < cur.start.saturating_sub(1) {
                 if !inserted {
                     res.push(iv);
                     inserted = true;
@@ -84,5 +85,10 @@ mod tests {
         assert_eq!(s.intervals(), &[Interval::new(1, 7)]);
         assert!(s.contains(6));
         assert!(!s.contains(0));
+
+        let mut edge = IntervalSet::new();
+        edge.add(Interval::new(i64::MIN + 1, 0));
+        edge.add(Interval::new(i64::MIN, i64::MIN));
+        assert_eq!(edge.intervals(), &[Interval::new(i64::MIN, 0)]);
     }
 }
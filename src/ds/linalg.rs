@@ -0,0 +1,325 @@
+//! Exact linear algebra: reduced row echelon form over big rationals.
+//!
+//! `rref(matrix, rhs)` row-reduces the augmented system `matrix · x = rhs`
+//! with `BigRational` arithmetic, so no precision is lost however large the
+//! coefficients get. The result lists the pivot columns (variables fixed by
+//! the others) and the free columns (variables you may choose); each pivot
+//! variable equals `rhs[i] - Σ matrix[i][f] · x[f]` over the free columns `f`.
+//! Inconsistent systems (a `0 = c` row with `c != 0`) yield None.
+//!
+//! `solve_gf2(a, b)` does the same over GF(2), where addition is XOR (light
+//! toggling, parity puzzles). Rows are packed into `u64` bitsets, so each
+//! elimination step is a handful of word XORs. The answer is one particular
+//! solution plus a basis of the null space: every solution is the particular
+//! one XOR any subset of the basis.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+
+/// Row-reduced system; row `i` solves for variable `pivots[i]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rref {
+    /// The `rank` non-zero rows, each with a 1 in its pivot column.
+    pub matrix: Vec<Vec<BigRational>>,
+    /// Right-hand side of each kept row.
+    pub rhs: Vec<BigRational>,
+    pub pivots: Vec<usize>,
+    pub free: Vec<usize>,
+}
+
+impl Rref {
+    /// Number of pivot (independent) rows.
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+    /// True if the system has exactly one solution.
+    pub fn is_unique(&self) -> bool {
+        self.free.is_empty()
+    }
+    /// Full solution for the given free-variable values (same order as `free`).
+    pub fn solve_with(&self, free_values: &[BigRational]) -> Vec<BigRational> {
+        let cols = self.pivots.len() + self.free.len();
+        let mut x = vec![BigRational::zero(); cols];
+        for (&c, v) in self.free.iter().zip(free_values) {
+            x[c] = v.clone();
+        }
+        for (i, &p) in self.pivots.iter().enumerate() {
+            let mut v = self.rhs[i].clone();
+            for &f in &self.free {
+                v -= &self.matrix[i][f] * &x[f];
+            }
+            x[p] = v;
+        }
+        x
+    }
+}
+
+/// Integer as a `BigRational`.
+pub fn rational(v: impl Into<BigInt>) -> BigRational {
+    BigRational::from_integer(v.into())
+}
+
+/// Gauss-Jordan elimination of `matrix · x = rhs`; None if inconsistent.
+/// Panics if `rhs` does not have one entry per row or the rows are ragged.
+pub fn rref<A, B>(matrix: &[Vec<A>], rhs: &[B]) -> Option<Rref>
+where
+    A: Copy + Into<BigInt>,
+    B: Copy + Into<BigInt>,
+{
+    assert_eq!(matrix.len(), rhs.len(), "one right-hand side entry per row");
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, Vec::len);
+    assert!(matrix.iter().all(|row| row.len() == cols), "ragged matrix");
+    let mut vec_rhs: Vec<BigRational> = rhs.iter().map(|&v| rational(v)).collect();
+    if rows == 0 {
+        return Some(Rref::default());
+    }
+    let mut mat: Vec<Vec<BigRational>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|&v| rational(v)).collect())
+        .collect();
+    let mut pivots = Vec::new();
+    let mut current_row = 0usize;
+
+    for col in 0..cols {
+        if current_row == rows {
+            break;
+        }
+        let Some(pivot_idx) = (current_row..rows).find(|&r| !mat[r][col].is_zero()) else {
+            continue;
+        };
+        mat.swap(current_row, pivot_idx);
+        vec_rhs.swap(current_row, pivot_idx);
+        let pivot_val = mat[current_row][col].clone();
+        for v in &mut mat[current_row][col..] {
+            *v /= &pivot_val;
+        }
+        vec_rhs[current_row] /= pivot_val;
+        let pivot_row = mat[current_row][col..].to_vec();
+        let pivot_rhs = vec_rhs[current_row].clone();
+        for (row, (other, other_rhs)) in mat.iter_mut().zip(&mut vec_rhs).enumerate() {
+            if row == current_row || other[col].is_zero() {
+                continue;
+            }
+            let factor = other[col].clone();
+            for (v, p) in other[col..].iter_mut().zip(&pivot_row) {
+                *v -= p * &factor;
+            }
+            *other_rhs -= &pivot_rhs * &factor;
+        }
+        pivots.push(col);
+        current_row += 1;
+    }
+
+    // Remaining rows are all-zero on the left; any non-zero rhs is `0 = c`.
+    if vec_rhs[current_row..].iter().any(|v| !v.is_zero()) {
+        return None;
+    }
+    mat.truncate(current_row);
+    vec_rhs.truncate(current_row);
+    let mut is_pivot = vec![false; cols];
+    for &c in &pivots {
+        is_pivot[c] = true;
+    }
+    let free = (0..cols).filter(|&c| !is_pivot[c]).collect();
+    Some(Rref {
+        matrix: mat,
+        rhs: vec_rhs,
+        pivots,
+        free,
+    })
+}
+
+/// Solution space of a GF(2) system: `particular ^ (any XOR of null_basis)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Gf2Solution {
+    /// The solution with every free variable set to false.
+    pub particular: Vec<bool>,
+    /// One vector per free column; XOR-ing any subset onto a solution gives another.
+    pub null_basis: Vec<Vec<bool>>,
+    pub pivots: Vec<usize>,
+    pub free: Vec<usize>,
+}
+
+fn pack(bits: &[bool]) -> Vec<u64> {
+    let mut words = vec![0u64; bits.len().div_ceil(64)];
+    for (i, _) in bits.iter().enumerate().filter(|(_, &b)| b) {
+        words[i / 64] |= 1 << (i % 64);
+    }
+    words
+}
+
+fn bit(words: &[u64], i: usize) -> bool {
+    words[i / 64] >> (i % 64) & 1 == 1
+}
+
+impl Gf2Solution {
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+    /// Number of solutions `2^free`, or None if that overflows `u128`.
+    pub fn solution_count(&self) -> Option<u128> {
+        1u128.checked_shl(self.free.len() as u32)
+    }
+    /// A solution with the fewest true entries, by Gray-code enumeration of
+    /// the null space: O(2^free · cols / 64), so keep `free` small. None if
+    /// `2^free` overflows `u64` (64 or more free variables).
+    pub fn min_weight_solution(&self) -> Option<Vec<bool>> {
+        let steps = 1u64.checked_shl(u32::try_from(self.free.len()).ok()?)?;
+        let cur0 = pack(&self.particular);
+        let basis: Vec<Vec<u64>> = self.null_basis.iter().map(|v| pack(v)).collect();
+        let weight = |w: &[u64]| w.iter().map(|x| x.count_ones()).sum::<u32>();
+        let (mut cur, mut best_w) = (cur0.clone(), weight(&cur0));
+        let mut best = cur0;
+        for i in 1..steps {
+            // Gray code: step i flips the basis vector at its lowest set bit.
+            let flip = &basis[i.trailing_zeros() as usize];
+            for (c, f) in cur.iter_mut().zip(flip) {
+                *c ^= f;
+            }
+            let w = weight(&cur);
+            if w < best_w {
+                (best_w, best) = (w, cur.clone());
+            }
+        }
+        Some((0..self.particular.len()).map(|i| bit(&best, i)).collect())
+    }
+}
+
+/// Gaussian elimination of `a · x = b` over GF(2); None if inconsistent.
+/// `a` is row-major with one `bool` per column; all rows must share a width.
+/// Panics if `b` does not have one entry per row or the rows are ragged.
+pub fn solve_gf2(a: &[Vec<bool>], b: &[bool]) -> Option<Gf2Solution> {
+    assert_eq!(a.len(), b.len(), "one right-hand side entry per row");
+    let rows = a.len();
+    let cols = a.first().map_or(0, Vec::len);
+    assert!(a.iter().all(|row| row.len() == cols), "ragged GF(2) matrix");
+    // Row bitsets with the right-hand side stored as bit `cols`.
+    let mut m: Vec<Vec<u64>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| {
+            let mut bits = row.clone();
+            bits.push(rhs);
+            pack(&bits)
+        })
+        .collect();
+    let mut pivots = Vec::new();
+    let mut r = 0;
+    for col in 0..cols {
+        if r == rows {
+            break;
+        }
+        let Some(p) = (r..rows).find(|&i| bit(&m[i], col)) else {
+            continue;
+        };
+        m.swap(r, p);
+        let pivot_row = m[r].clone();
+        for (i, row) in m.iter_mut().enumerate() {
+            if i != r && bit(row, col) {
+                for (x, y) in row.iter_mut().zip(&pivot_row) {
+                    *x ^= y;
+                }
+            }
+        }
+        pivots.push(col);
+        r += 1;
+    }
+    if m[r..].iter().any(|row| bit(row, cols)) {
+        return None;
+    }
+    let mut is_pivot = vec![false; cols];
+    let mut particular = vec![false; cols];
+    for (i, &p) in pivots.iter().enumerate() {
+        is_pivot[p] = true;
+        particular[p] = bit(&m[i], cols);
+    }
+    let free: Vec<usize> = (0..cols).filter(|&c| !is_pivot[c]).collect();
+    let null_basis = free
+        .iter()
+        .map(|&f| {
+            let mut v = vec![false; cols];
+            v[f] = true;
+            for (i, &p) in pivots.iter().enumerate() {
+                v[p] = bit(&m[i], f);
+            }
+            v
+        })
+        .collect();
+    Some(Gf2Solution {
+        particular,
+        null_basis,
+        pivots,
+        free,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rational, rref, solve_gf2};
+
+    #[test]
+    fn unique_solution() {
+        // 2x + y = 5, x - y = 1  =>  x = 2, y = 1
+        let r = rref(&[vec![2i64, 1], vec![1, -1]], &[5i64, 1]).unwrap();
+        assert!(r.is_unique());
+        assert_eq!(r.rank(), 2);
+        assert_eq!(r.solve_with(&[]), vec![rational(2), rational(1)]);
+    }
+
+    #[test]
+    fn free_columns_and_inconsistency() {
+        // x + y + z = 6, duplicated; y free, z free
+        let m = vec![vec![1u8, 1, 1], vec![2, 2, 2]];
+        let r = rref(&m, &[6u64, 12]).unwrap();
+        assert_eq!((r.pivots.clone(), r.free.clone()), (vec![0], vec![1, 2]));
+        let x = r.solve_with(&[rational(1), rational(2)]);
+        assert_eq!(x, vec![rational(3), rational(1), rational(2)]);
+        assert!(rref(&m, &[6u64, 13]).is_none());
+        assert!(rref::<u8, u64>(&[], &[]).is_some());
+    }
+
+    #[test]
+    fn gf2_lights_out() {
+        // 4 lights, 6 buttons (columns): (3) (1,3) (2) (2,3) (0,2) (0,1); target .##.
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let a: Vec<Vec<bool>> = (0..4)
+            .map(|l| buttons.iter().map(|b| b.contains(&l)).collect())
+            .collect();
+        let target = [false, true, true, false];
+        let sol = solve_gf2(&a, &target).unwrap();
+        assert_eq!(sol.rank(), 4);
+        assert_eq!(sol.solution_count(), Some(4));
+        let best = sol.min_weight_solution().unwrap();
+        assert_eq!(best.iter().filter(|&&x| x).count(), 2);
+        for (row, &t) in a.iter().zip(&target) {
+            let parity = row.iter().zip(&best).filter(|(&x, &y)| x && y).count() % 2 == 1;
+            assert_eq!(parity, t);
+        }
+        assert!(solve_gf2(&[vec![true, true], vec![true, true]], &[true, false]).is_none());
+
+        // One equation in 65 unknowns: 64 free variables are too many to enumerate.
+        let wide = solve_gf2(&[vec![true; 65]], &[true]).unwrap();
+        assert_eq!(wide.free.len(), 64);
+        assert_eq!(wide.min_weight_solution(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "one right-hand side entry per row")]
+    fn gf2_rejects_missing_equations() {
+        solve_gf2(&[], &[true]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ragged matrix")]
+    fn rref_rejects_ragged_rows() {
+        rref(&[vec![1i64, 2], vec![3]], &[1i64, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "one right-hand side entry per row")]
+    fn rref_rejects_missing_equations() {
+        rref(&[vec![1i64, 2]], &[1i64, 2]);
+    }
+}
@@ -0,0 +1,92 @@
+//! Number theory helpers: gcd, lcm and the extended Euclidean algorithm.
+//!
+//! All functions work on `i64` and normalize signs so results are the usual
+//! mathematical ones (`gcd` is never negative). `lcm` is overflow-checked:
+//! AoC "when do all cycles line up" answers routinely approach `i64::MAX`.
+//!
+//! Digit-structure helpers (repeated blocks, digit counts, arithmetic series
+//! over ranges) live in the `digits` submodule.
+
+pub mod digits;
+
+/// Greatest common divisor (`gcd(0, 0) == 0`).
+///
+/// Panics if the result is 2^63, which only happens when both arguments are
+/// `0` or `i64::MIN` and at least one is `i64::MIN`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    i64::try_from(gcd_u64(a.unsigned_abs(), b.unsigned_abs()))
+        .expect("gcd is 2^63, which does not fit in i64")
+}
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or None if it does not fit in `i64` (including
+/// 2^63, e.g. `lcm(i64::MIN, 1)`). `lcm(0, x) == Some(0)`.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    let l = (a / gcd_u64(a, b)).checked_mul(b)?;
+    i64::try_from(l).ok()
+}
+
+/// Extended Euclid: `(g, x, y)` with `a*x + b*y == g == gcd(a, b)`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1i64, 0i64);
+    let (mut y0, mut y1) = (0i64, 1i64);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ext_gcd, gcd, lcm};
+
+    #[test]
+    fn gcd_lcm_basics() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(-4, 6), Some(12));
+        assert_eq!(lcm(0, 5), Some(0));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        assert_eq!(lcm(i64::MIN, 1), None);
+        assert_eq!(lcm(i64::MIN + 1, -1), Some(i64::MAX));
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        let cycles = [18_023, 19_637, 21_389, 16_697, 14_429, 20_803];
+        let all = cycles.iter().try_fold(1, |acc, &c| lcm(acc, c));
+        assert_eq!(all, Some(6_594_025_047_379_190_081));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in i64")]
+    fn gcd_of_min_and_zero_panics() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn bezout_coefficients() {
+        for (a, b) in [(240, 46), (-240, 46), (17, 0), (0, -5), (7, 13)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+}
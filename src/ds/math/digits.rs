@@ -0,0 +1,108 @@
+//! Digit-structure counting helpers on `u128`.
+//!
+//! Puzzles like "sum every ID in a range whose digits are a block repeated
+//! twice" are solved by algebra instead of iteration: a number made of block
+//! `x` (with `len` digits) repeated `reps` times is `x * repeat_multiplier(len,
+//! reps)`, e.g. `123123 = 123 * 1001`. For a fixed multiplier `m`, the blocks
+//! whose product lands in `[lo, hi]` form one contiguous range
+//! (`scaled_range`), and their total is an arithmetic series (`sum_range`).
+
+/// Number of decimal digits (`num_digits(0) == 1`).
+pub fn num_digits(mut n: u128) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
+
+/// `[10^0, 10^1, ..., 10^max]`.
+pub fn pow10_table(max: usize) -> Vec<u128> {
+    std::iter::successors(Some(1u128), |p| Some(p * 10))
+        .take(max + 1)
+        .collect()
+}
+
+/// Ceiling of `a / b` (`b > 0`).
+pub fn ceil_div(a: u128, b: u128) -> u128 {
+    a.div_ceil(b)
+}
+
+/// Sum of `lo..=hi` (0 when the range is empty).
+pub fn sum_range(lo: u128, hi: u128) -> u128 {
+    if lo > hi {
+        return 0;
+    }
+    let count = hi - lo + 1;
+    // One of count and (lo + hi) is even; halve it first to delay overflow.
+    if count.is_multiple_of(2) {
+        count / 2 * (lo + hi)
+    } else {
+        (lo + hi) / 2 * count
+    }
+}
+
+/// Smallest and largest positive number with exactly `len` digits (`len >= 1`).
+pub fn digit_len_bounds(len: usize) -> (u128, u128) {
+    let hi = 10u128.pow(len as u32) - 1;
+    (hi / 10 + 1, hi)
+}
+
+/// Multiplier that repeats a `len`-digit block `reps` times: `1001` for (3, 2).
+pub fn repeat_multiplier(len: usize, reps: usize) -> u128 {
+    (10u128.pow((len * reps) as u32) - 1) / (10u128.pow(len as u32) - 1)
+}
+
+/// The `x` in `[x_min, x_max]` with `m * x` in `[lo, hi]`, as an inclusive
+/// range, or None if there are none.
+pub fn scaled_range(m: u128, lo: u128, hi: u128, x_min: u128, x_max: u128) -> Option<(u128, u128)> {
+    let x_lo = x_min.max(ceil_div(lo, m));
+    let x_hi = x_max.min(hi / m);
+    (x_lo <= x_hi).then_some((x_lo, x_hi))
+}
+
+/// Decimal digits of `n`, most significant first.
+pub fn to_digits(n: u128) -> Vec<u8> {
+    n.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+/// Inverse of `to_digits`.
+pub fn from_digits(digits: &[u8]) -> u128 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_helpers() {
+        assert_eq!((num_digits(0), num_digits(9), num_digits(10)), (1, 1, 2));
+        assert_eq!(num_digits(u128::MAX), 39);
+        assert_eq!(pow10_table(3), vec![1, 10, 100, 1000]);
+        assert_eq!(ceil_div(0, 5), 0);
+        assert_eq!(ceil_div(1, 5), 1);
+        assert_eq!(ceil_div(9, 5), 2);
+        assert_eq!(ceil_div(10, 5), 2);
+        assert_eq!(sum_range(3, 6), 18);
+        assert_eq!(sum_range(6, 3), 0);
+        assert_eq!(digit_len_bounds(1), (1, 9));
+        assert_eq!(digit_len_bounds(3), (100, 999));
+        assert_eq!(to_digits(4075), vec![4, 0, 7, 5]);
+        assert_eq!(from_digits(&[4, 0, 7, 5]), 4075);
+    }
+
+    #[test]
+    fn doubled_blocks_in_range() {
+        // Numbers in 95..=1_200 that are a 2-digit block written twice: 1010, 1111.
+        let m = repeat_multiplier(2, 2);
+        assert_eq!(m, 101);
+        let (lo, hi) = digit_len_bounds(2);
+        let (x_lo, x_hi) = scaled_range(m, 95, 1_200, lo, hi).unwrap();
+        assert_eq!((x_lo, x_hi), (10, 11));
+        assert_eq!(sum_range(x_lo, x_hi) * m, 1010 + 1111);
+        assert_eq!(repeat_multiplier(1, 3), 111);
+        assert!(scaled_range(m, 1, 100, lo, hi).is_none());
+    }
+}
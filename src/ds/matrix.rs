@@ -0,0 +1,145 @@
+//! Small dense `Matrix<T>` with multiplication and fast exponentiation.
+//!
+//! Aimed at linear recurrences: write one step as `state' = A · state`, then
+//! `A.pow(k).mul_vec(&state)` jumps `k` steps in O(n^3 log k). `T` can be any
+//! ring-like `Copy` type: `i64`/`u128` for exact small answers, or `ModInt<M>`
+//! when the answer is asked modulo a prime. Storage is row-major.
+
+use std::ops::{Add, Index, IndexMut, Mul};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Matrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Copy + Default> Matrix<T> {
+    /// `rows x cols` matrix of zeros (`T::default()`).
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![T::default(); rows * cols],
+        }
+    }
+    /// Build from row vectors (panics if they are ragged).
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|r| r.len() == cols), "ragged rows");
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    /// Row `r` as a slice.
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+}
+
+impl<T: Copy + Default + From<u8>> Matrix<T> {
+    /// `n x n` identity.
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::new(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::from(1);
+        }
+        m
+    }
+}
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> Matrix<T> {
+    /// Matrix product (panics on a dimension mismatch).
+    pub fn mul(&self, rhs: &Self) -> Self {
+        assert_eq!(self.cols, rhs.rows, "dimension mismatch");
+        let mut out = Self::new(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    out[(i, j)] = out[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        out
+    }
+    /// Matrix-vector product `self · v`.
+    pub fn mul_vec(&self, v: &[T]) -> Vec<T> {
+        assert_eq!(self.cols, v.len(), "dimension mismatch");
+        (0..self.rows)
+            .map(|r| {
+                self.row(r)
+                    .iter()
+                    .zip(v)
+                    .fold(T::default(), |acc, (&a, &b)| acc + a * b)
+            })
+            .collect()
+    }
+}
+
+impl<T: Copy + Default + From<u8> + Add<Output = T> + Mul<Output = T>> Matrix<T> {
+    /// `self^k` by binary exponentiation (square matrices only).
+    pub fn pow(&self, mut k: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "pow needs a square matrix");
+        let mut base = self.clone();
+        let mut acc = Self::identity(self.rows);
+        while k > 0 {
+            if k & 1 == 1 {
+                acc = acc.mul(&base);
+            }
+            base = base.mul(&base);
+            k >>= 1;
+        }
+        acc
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use crate::ds::modint::Mod1e9p7;
+
+    #[test]
+    fn fibonacci_by_matrix_power() {
+        let m = Matrix::from_rows(vec![vec![1u128, 1], vec![1, 0]]);
+        assert_eq!(m.pow(0), Matrix::identity(2));
+        assert_eq!(m.pow(90)[(0, 1)], 2_880_067_194_370_816_120);
+        assert_eq!(m.mul_vec(&[1, 0]), vec![1, 1]);
+        let one = Mod1e9p7::from(1u64);
+        let zero = Mod1e9p7::default();
+        let mm = Matrix::from_rows(vec![vec![one, one], vec![one, zero]]);
+        // F(10^12) mod 1e9+7
+        assert_eq!(mm.pow(1_000_000_000_000)[(0, 1)].value(), 730_695_249);
+    }
+
+    #[test]
+    fn rectangular_product() {
+        let a = Matrix::from_rows(vec![vec![1i64, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_rows(vec![vec![7i64, 8], vec![9, 10], vec![11, 12]]);
+        let c = a.mul(&b);
+        assert_eq!((c.rows(), c.cols()), (2, 2));
+        assert_eq!(c.row(0), &[58, 64]);
+        assert_eq!(c.row(1), &[139, 154]);
+    }
+}
@@ -1,27 +1,49 @@
+pub mod aho_corasick;
 pub mod array_list;
+pub mod automaton;
+pub mod beams;
 pub mod bitmask;
+pub mod circular_buffer;
+pub mod combinatorics;
 pub mod coords;
+pub mod cycle;
 pub mod dense_grid;
+pub mod diff;
+pub mod dlx;
 pub mod doubly_linked_list;
 pub mod dsu;
 pub mod fcov;
 pub mod fenwick;
 pub mod freq_map;
+pub mod geometry;
 pub mod graph;
 pub mod hash_map;
 pub mod hash_set_ext;
 pub mod heap;
 pub mod indexed_heap;
 pub mod intervals;
+pub mod linalg;
 pub mod linked_list;
+pub mod math;
+pub mod matrix;
+pub mod modint;
 pub mod monotonic_queue;
+pub mod mst;
 pub mod neighbors;
 pub mod parsing;
+pub mod primes;
 pub mod queue;
+pub mod ratio;
+pub mod ring;
 pub mod scc;
 pub mod search;
+pub mod sliding_window;
 pub mod sparse_grid;
+pub mod spatial_hash;
 pub mod stack;
 pub mod string_alg;
 pub mod topo;
+pub mod treap;
+pub mod tree;
 pub mod tree_map;
+pub mod trie;
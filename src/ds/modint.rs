@@ -0,0 +1,177 @@
+//! `ModInt<M>`: integers modulo a compile-time modulus with operator support.
+//!
+//! Keeps counting answers ("print the result mod 1e9+7") free of scattered
+//! `% M`: values are always stored reduced to `0..M`, and `+ - * /` work on
+//! `ModInt` directly. Multiplication goes through `u128`, so any modulus up to
+//! `u64::MAX` is safe. Division and `inv` need the divisor to be coprime to
+//! `M` (always true for a prime `M` and a non-zero divisor).
+
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64>(u64);
+
+/// The usual contest modulus 1_000_000_007.
+pub type Mod1e9p7 = ModInt<1_000_000_007>;
+/// The NTT-friendly modulus 998_244_353.
+pub type Mod998 = ModInt<998_244_353>;
+
+impl<const M: u64> ModInt<M> {
+    /// Reduce any signed value into `0..M`.
+    pub fn new(x: i64) -> Self {
+        Self((x as i128).rem_euclid(M as i128) as u64)
+    }
+    /// The representative in `0..M`.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+    /// `self^e` by square-and-multiply.
+    pub fn pow(self, mut e: u64) -> Self {
+        let (mut b, mut r) = (self, Self(1 % M));
+        while e > 0 {
+            if e & 1 == 1 {
+                r *= b;
+            }
+            b *= b;
+            e >>= 1;
+        }
+        r
+    }
+    /// Multiplicative inverse, or None if `gcd(self, M) != 1`.
+    pub fn inv(self) -> Option<Self> {
+        // Extended Euclid in i128: Bezout coefficients stay below M in
+        // magnitude, so this works for every u64 modulus, prime or not.
+        let (mut r0, mut r1) = (M as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        (r0 == 1).then(|| Self(t0.rem_euclid(M as i128) as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(x: u64) -> Self {
+        Self(x % M)
+    }
+}
+impl<const M: u64> From<u8> for ModInt<M> {
+    fn from(x: u8) -> Self {
+        Self(x as u64 % M)
+    }
+}
+impl<const M: u64> From<i64> for ModInt<M> {
+    fn from(x: i64) -> Self {
+        Self::new(x)
+    }
+}
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + rhs.0 as u128) % M as u128) as u64)
+    }
+}
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(((self.0 as u128 + M as u128 - rhs.0 as u128) % M as u128) as u64)
+    }
+}
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % M as u128) as u64)
+    }
+}
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+    /// Panics if `rhs` has no inverse modulo `M`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv().expect("divisor not invertible modulo M")
+    }
+}
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(0) - self
+    }
+}
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), |a, b| a + b)
+    }
+}
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(1 % M), |a, b| a * b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Mod1e9p7, ModInt};
+
+    #[test]
+    fn arithmetic_wraps() {
+        let a = Mod1e9p7::new(-1);
+        assert_eq!(a.value(), 1_000_000_006);
+        assert_eq!((a + Mod1e9p7::from(2u64)).value(), 1);
+        assert_eq!(
+            (Mod1e9p7::from(3u64) - Mod1e9p7::from(5u64)).value(),
+            1_000_000_005
+        );
+        assert_eq!(Mod1e9p7::from(2u64).pow(1_000_000_006).value(), 1);
+        let x = Mod1e9p7::from(123_456_789u64);
+        assert_eq!((x / x).value(), 1);
+        assert_eq!(x * x.inv().unwrap(), Mod1e9p7::from(1u64));
+        let fact: Mod1e9p7 = (1..=20u64).map(Mod1e9p7::from).product();
+        assert_eq!(fact.value(), (2_432_902_008_176_640_000u64 % 1_000_000_007));
+        assert_eq!(format!("{}", -Mod1e9p7::from(1u64)), "1000000006");
+    }
+
+    #[test]
+    fn composite_modulus_inverse() {
+        type M12 = ModInt<12>;
+        assert_eq!(M12::from(5u64).inv(), Some(M12::from(5u64)));
+        assert_eq!(M12::from(4u64).inv(), None);
+        let big = ModInt::<{ u64::MAX - 58 }>::from(u64::MAX - 60); // largest u64 prime
+        assert_eq!((big * big.inv().unwrap()).value(), 1);
+        // u64::MAX = 3 · 5 · 17 · 257 · 641 · 65537 · 6700417.
+        type Wide = ModInt<{ u64::MAX }>;
+        assert_eq!(Wide::from(3u64).inv(), None);
+        assert_eq!(Wide::from(641u64 * 7).inv(), None);
+        let two = Wide::from(2u64);
+        assert_eq!((two * two.inv().unwrap()).value(), 1);
+    }
+}
@@ -1,5 +1,13 @@
 //! Monotonic queues for sliding window min/max in O(N).
+//!
+//! `MonotonicQueueMin/Max` store bare values and expect the caller to name
+//! the value leaving the window. `IndexedMonotonicMin/Max` store
+//! `(index, value)` pairs instead, so the window is expressed in positions
+//! and repeated values cannot be evicted by mistake.
+//! `MonotonicQueueMinBy/MaxBy` order arbitrary items with a comparator or a
+//! key function (e.g. search states by cost) instead of requiring `Ord + Copy`.
 
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 
 #[derive(Clone, Debug, Default)]
@@ -70,6 +78,213 @@ impl<T: Ord + Copy> MonotonicQueueMax<T> {
     }
 }
 
+/// Monotonic min-queue ordered by a custom comparator.
+///
+/// Build with `new_by(cmp)` or `by_key(key)`; `pop_if` uses `==` on the item
+/// itself, so equal keys with different payloads are told apart.
+#[derive(Clone, Debug)]
+pub struct MonotonicQueueMinBy<T, C: Fn(&T, &T) -> Ordering> {
+    dq: VecDeque<T>,
+    cmp: C,
+}
+impl<T, C: Fn(&T, &T) -> Ordering> MonotonicQueueMinBy<T, C> {
+    /// Create an empty queue ordered by `cmp`.
+    pub fn new_by(cmp: C) -> Self {
+        Self {
+            dq: VecDeque::new(),
+            cmp,
+        }
+    }
+    /// Push item; removes items ordered after it from the back.
+    pub fn push(&mut self, x: T) {
+        while let Some(back) = self.dq.back() {
+            if (self.cmp)(back, &x) == Ordering::Greater {
+                self.dq.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.dq.push_back(x);
+    }
+    /// Smallest item under the comparator.
+    pub fn min(&self) -> Option<&T> {
+        self.dq.front()
+    }
+    pub fn len(&self) -> usize {
+        self.dq.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.dq.is_empty()
+    }
+}
+impl<T: PartialEq, C: Fn(&T, &T) -> Ordering> MonotonicQueueMinBy<T, C> {
+    /// Pop from front if it equals `x` (use when the sliding window removes `x`).
+    pub fn pop_if(&mut self, x: &T) {
+        if self.dq.front() == Some(x) {
+            self.dq.pop_front();
+        }
+    }
+}
+impl<T> MonotonicQueueMinBy<T, fn(&T, &T) -> Ordering> {
+    /// Create an empty queue ordered by `key(item)`.
+    pub fn by_key<K: Ord>(
+        key: impl Fn(&T) -> K,
+    ) -> MonotonicQueueMinBy<T, impl Fn(&T, &T) -> Ordering> {
+        MonotonicQueueMinBy::new_by(move |a: &T, b: &T| key(a).cmp(&key(b)))
+    }
+}
+
+/// Monotonic max-queue ordered by a custom comparator (see `MonotonicQueueMinBy`).
+#[derive(Clone, Debug)]
+pub struct MonotonicQueueMaxBy<T, C: Fn(&T, &T) -> Ordering> {
+    dq: VecDeque<T>,
+    cmp: C,
+}
+impl<T, C: Fn(&T, &T) -> Ordering> MonotonicQueueMaxBy<T, C> {
+    /// Create an empty queue ordered by `cmp`.
+    pub fn new_by(cmp: C) -> Self {
+        Self {
+            dq: VecDeque::new(),
+            cmp,
+        }
+    }
+    /// Push item; removes items ordered before it from the back.
+    pub fn push(&mut self, x: T) {
+        while let Some(back) = self.dq.back() {
+            if (self.cmp)(back, &x) == Ordering::Less {
+                self.dq.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.dq.push_back(x);
+    }
+    /// Largest item under the comparator.
+    pub fn max(&self) -> Option<&T> {
+        self.dq.front()
+    }
+    pub fn len(&self) -> usize {
+        self.dq.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.dq.is_empty()
+    }
+}
+impl<T: PartialEq, C: Fn(&T, &T) -> Ordering> MonotonicQueueMaxBy<T, C> {
+    /// Pop from front if it equals `x` (use when the sliding window removes `x`).
+    pub fn pop_if(&mut self, x: &T) {
+        if self.dq.front() == Some(x) {
+            self.dq.pop_front();
+        }
+    }
+}
+impl<T> MonotonicQueueMaxBy<T, fn(&T, &T) -> Ordering> {
+    /// Create an empty queue ordered by `key(item)`.
+    pub fn by_key<K: Ord>(
+        key: impl Fn(&T) -> K,
+    ) -> MonotonicQueueMaxBy<T, impl Fn(&T, &T) -> Ordering> {
+        MonotonicQueueMaxBy::new_by(move |a: &T, b: &T| key(a).cmp(&key(b)))
+    }
+}
+
+/// Sliding-window minimum keyed by position: `push(i, v)` then `evict_older_than(lo)`.
+/// Indices must be pushed in non-decreasing order.
+#[derive(Clone, Debug, Default)]
+pub struct IndexedMonotonicMin<T: Ord + Copy> {
+    dq: VecDeque<(usize, T)>,
+}
+impl<T: Ord + Copy> IndexedMonotonicMin<T> {
+    pub fn new() -> Self {
+        Self {
+            dq: VecDeque::new(),
+        }
+    }
+    /// Push value `v` at index `i`; drops entries that can never be the minimum again.
+    pub fn push(&mut self, i: usize, v: T) {
+        while let Some(&(_, back)) = self.dq.back() {
+            if back >= v {
+                self.dq.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.dq.push_back((i, v));
+    }
+    /// Remove every entry whose index is `< i` (the window now starts at `i`).
+    pub fn evict_older_than(&mut self, i: usize) {
+        while let Some(&(j, _)) = self.dq.front() {
+            if j < i {
+                self.dq.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+    /// Minimum value in the window.
+    pub fn min(&self) -> Option<T> {
+        self.dq.front().map(|&(_, v)| v)
+    }
+    /// Index of the window minimum (the most recent one on ties).
+    pub fn argmin(&self) -> Option<usize> {
+        self.dq.front().map(|&(i, _)| i)
+    }
+    pub fn len(&self) -> usize {
+        self.dq.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.dq.is_empty()
+    }
+}
+
+/// Sliding-window maximum keyed by position: `push(i, v)` then `evict_older_than(lo)`.
+/// Indices must be pushed in non-decreasing order.
+#[derive(Clone, Debug, Default)]
+pub struct IndexedMonotonicMax<T: Ord + Copy> {
+    dq: VecDeque<(usize, T)>,
+}
+impl<T: Ord + Copy> IndexedMonotonicMax<T> {
+    pub fn new() -> Self {
+        Self {
+            dq: VecDeque::new(),
+        }
+    }
+    /// Push value `v` at index `i`; drops entries that can never be the maximum again.
+    pub fn push(&mut self, i: usize, v: T) {
+        while let Some(&(_, back)) = self.dq.back() {
+            if back <= v {
+                self.dq.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.dq.push_back((i, v));
+    }
+    /// Remove every entry whose index is `< i` (the window now starts at `i`).
+    pub fn evict_older_than(&mut self, i: usize) {
+        while let Some(&(j, _)) = self.dq.front() {
+            if j < i {
+                self.dq.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+    /// Maximum value in the window.
+    pub fn max(&self) -> Option<T> {
+        self.dq.front().map(|&(_, v)| v)
+    }
+    /// Index of the window maximum (the most recent one on ties).
+    pub fn argmax(&self) -> Option<usize> {
+        self.dq.front().map(|&(i, _)| i)
+    }
+    pub fn len(&self) -> usize {
+        self.dq.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.dq.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +340,95 @@ mod tests {
         mx.push(2);
         assert_eq!(mx.max(), Some(2));
     }
+
+    #[test]
+    fn indexed_min_with_repeats() {
+        let a = [3, 1, 1, 4, 1, 5];
+        let k = 2;
+        let mut mq = IndexedMonotonicMin::new();
+        let mut out = Vec::new();
+        for (i, &v) in a.iter().enumerate() {
+            mq.push(i, v);
+            if i + 1 >= k {
+                mq.evict_older_than(i + 1 - k);
+                out.push((mq.min().unwrap(), mq.argmin().unwrap()));
+            }
+        }
+        assert_eq!(out, vec![(1, 1), (1, 2), (1, 2), (1, 4), (1, 4)]);
+    }
+
+    #[test]
+    fn indexed_max_window() {
+        let a = [1, 3, 2, 5, 4, 1];
+        let k = 3;
+        let mut mq = IndexedMonotonicMax::new();
+        let mut out = Vec::new();
+        for (i, &v) in a.iter().enumerate() {
+            mq.push(i, v);
+            if i + 1 >= k {
+                mq.evict_older_than(i + 1 - k);
+                out.push(mq.max().unwrap());
+            }
+        }
+        assert_eq!(out, vec![3, 5, 5, 5]);
+        assert_eq!(mq.argmax(), Some(3));
+        mq.evict_older_than(10);
+        assert!(mq.is_empty() && mq.max().is_none());
+        assert_eq!(mq.len(), 0);
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct State {
+        cost: u32,
+        name: &'static str,
+    }
+
+    #[test]
+    fn min_by_key_over_structs() {
+        let a = [
+            State { cost: 5, name: "a" },
+            State { cost: 2, name: "b" },
+            State { cost: 2, name: "c" },
+            State { cost: 7, name: "d" },
+        ];
+        let mut mq = MonotonicQueueMinBy::by_key(|s: &State| s.cost);
+        let k = 2;
+        let mut out = Vec::new();
+        for i in 0..a.len() {
+            mq.push(a[i].clone());
+            if i >= k - 1 {
+                out.push(mq.min().unwrap().name);
+                mq.pop_if(&a[i + 1 - k]);
+            }
+        }
+        // "b" leaves the window by identity even though "c" has the same cost.
+        assert_eq!(out, vec!["b", "b", "c"]);
+        assert_eq!(mq.len(), 1);
+        assert!(!mq.is_empty());
+    }
+
+    #[test]
+    fn max_by_comparator() {
+        // Reverse comparator turns the max-queue into a min-queue.
+        let mut mq = MonotonicQueueMaxBy::new_by(|a: &i32, b: &i32| b.cmp(a));
+        for x in [4, 6, 1, 3] {
+            mq.push(x);
+        }
+        assert_eq!(mq.max(), Some(&1));
+        mq.pop_if(&1);
+        assert_eq!(mq.max(), Some(&3));
+
+        let mut by_len = MonotonicQueueMaxBy::by_key(|s: &&str| s.len());
+        by_len.push("ab");
+        by_len.push("abcd");
+        by_len.push("x");
+        assert_eq!(by_len.max(), Some(&"abcd"));
+        assert_eq!(by_len.len(), 2);
+        assert!(!by_len.is_empty());
+
+        let mut mn = MonotonicQueueMinBy::new_by(|a: &i32, b: &i32| a.cmp(b));
+        assert!(mn.is_empty() && mn.min().is_none());
+        mn.push(2);
+        assert_eq!(mn.min(), Some(&2));
+    }
 }
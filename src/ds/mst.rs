@@ -0,0 +1,109 @@
+//! Minimum spanning trees (forests) over indexed, undirected weighted graphs.
+//!
+//! - `mst_kruskal(n, edges)`: sort the edge list, keep every edge that joins
+//!   two different `DisjointSet` components. O(m log m).
+//! - `mst_prim(adj)`: grow a tree from each unvisited node with a binary heap
+//!   of crossing edges. O(m log m). `adj` must list both directions.
+//!
+//! Both return `(total_weight, chosen_edges)`; on a disconnected graph the
+//! result is a minimum spanning forest (n - components edges).
+
+use crate::ds::dsu::DisjointSet;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Kruskal's MST over `(u, v, w)` edges on nodes 0..n.
+/// Ties keep the input order, so equal-weight edges listed first win.
+pub fn mst_kruskal(n: usize, edges: &[(usize, usize, i64)]) -> (i64, Vec<(usize, usize, i64)>) {
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by_key(|&i| edges[i].2);
+    let mut dsu = DisjointSet::new();
+    let mut total = 0;
+    let mut chosen = Vec::new();
+    for i in order {
+        let (u, v, w) = edges[i];
+        if !dsu.connected(u, v) {
+            dsu.union(u, v);
+            total += w;
+            chosen.push((u, v, w));
+            if chosen.len() + 1 == n {
+                break;
+            }
+        }
+    }
+    (total, chosen)
+}
+
+/// Prim's MST over an undirected adjacency list (`adj[u]` holds `(v, w)`).
+/// Chosen edges are reported as `(tree_node, new_node, w)`.
+pub fn mst_prim(adj: &[Vec<(usize, i64)>]) -> (i64, Vec<(usize, usize, i64)>) {
+    let n = adj.len();
+    let mut in_tree = vec![false; n];
+    let mut total = 0;
+    let mut chosen = Vec::new();
+    let mut pq = BinaryHeap::new();
+    for root in 0..n {
+        if in_tree[root] {
+            continue;
+        }
+        in_tree[root] = true;
+        pq.extend(adj[root].iter().map(|&(v, w)| Reverse((w, root, v))));
+        while let Some(Reverse((w, u, v))) = pq.pop() {
+            if in_tree[v] {
+                continue;
+            }
+            in_tree[v] = true;
+            total += w;
+            chosen.push((u, v, w));
+            pq.extend(
+                adj[v]
+                    .iter()
+                    .filter(|&&(x, _)| !in_tree[x])
+                    .map(|&(x, wx)| Reverse((wx, v, x))),
+            );
+        }
+    }
+    (total, chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mst_kruskal, mst_prim};
+
+    #[test]
+    fn kruskal_and_prim_agree() {
+        let edges = vec![
+            (0, 1, 4),
+            (0, 2, 3),
+            (1, 2, 1),
+            (1, 3, 2),
+            (2, 3, 4),
+            (3, 4, 2),
+            (4, 5, 6),
+        ];
+        let n = 6;
+        let (wk, ek) = mst_kruskal(n, &edges);
+        assert_eq!(wk, 14);
+        assert_eq!(ek.len(), n - 1);
+        let mut adj = vec![vec![]; n];
+        for &(u, v, w) in &edges {
+            adj[u].push((v, w));
+            adj[v].push((u, w));
+        }
+        let (wp, ep) = mst_prim(&adj);
+        assert_eq!((wp, ep.len()), (wk, n - 1));
+    }
+
+    #[test]
+    fn disconnected_gives_forest() {
+        let edges = vec![(0, 1, 5), (2, 3, 1), (3, 4, 1), (2, 4, 9)];
+        let (w, e) = mst_kruskal(6, &edges);
+        assert_eq!((w, e.len()), (7, 3));
+        let mut adj = vec![vec![]; 6];
+        for &(u, v, w) in &edges {
+            adj[u].push((v, w));
+            adj[v].push((u, w));
+        }
+        assert_eq!(mst_prim(&adj).0, 7);
+    }
+}
@@ -1,4 +1,68 @@
 //! Parsing helpers commonly used in AoC: grids, ints, and tokenization.
+//!
+//! The plain helpers are lenient (skip what they cannot read) for quick puzzle
+//! code. The `try_*`, `parse_split`/`parse_lines` and point parsers instead
+//! return a `ParseError` naming the 1-based line and column of the first bad
+//! token, for callers that need to report or recover from malformed input.
+
+use crate::ds::coords::{Point, Point3};
+use crate::ds::dense_grid::DenseGrid2D;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// A token that failed to parse, with its 1-based line and column (in chars).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: cannot parse {:?}: {}",
+            self.line, self.column, self.token, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parse one token, reporting failures at `line`/`column`.
+fn parse_token<T: FromStr>(tok: &str, line: usize, column: usize) -> Result<T, ParseError>
+where
+    T::Err: fmt::Display,
+{
+    tok.parse().map_err(|e: T::Err| ParseError {
+        line,
+        column,
+        token: tok.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Trimmed, non-empty pieces of `s` split on `sep`, with their 0-based char columns.
+fn tokens(s: &str, sep: char) -> impl Iterator<Item = (usize, &str)> {
+    split_fields(s, sep).filter(|(_, t)| !t.is_empty())
+}
+
+/// Every trimmed piece of `s` split on `sep` (empty ones included), with its
+/// 0-based char column.
+fn split_fields(s: &str, sep: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut col = 0;
+    s.split(sep).map(move |piece| {
+        let lead = piece.len() - piece.trim_start().len();
+        let at = col + piece[..lead].chars().count();
+        col += piece.chars().count() + 1;
+        (at, piece.trim())
+    })
+}
 
 /// Parse a grid of characters; each line becomes a Vec<char>.
 pub fn parse_grid_chars(input: &str) -> Vec<Vec<char>> {
@@ -41,6 +105,428 @@ pub fn parse_lines_i64(input: &str) -> Vec<i64> {
         .collect()
 }
 
+/// All `k`-character windows of `s`, left to right (empty if `k == 0` or `k > len`).
+pub fn windows_str(s: &str, k: usize) -> Vec<&str> {
+    let idx: Vec<usize> = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .collect();
+    if k == 0 || k >= idx.len() {
+        return Vec::new();
+    }
+    (0..idx.len() - k).map(|i| &s[idx[i]..idx[i + k]]).collect()
+}
+
+/// Split `s` into consecutive `k`-character chunks; the last may be shorter (panics if `k == 0`).
+///
+/// Useful for fixed-width fields, e.g. `chunks_str("[A] [B]    ", 4)`.
+pub fn chunks_str(s: &str, k: usize) -> Vec<&str> {
+    assert!(k > 0, "chunk size must be positive");
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (n, (i, _)) in s.char_indices().enumerate() {
+        if n > 0 && n % k == 0 {
+            out.push(&s[start..i]);
+            start = i;
+        }
+    }
+    if start < s.len() {
+        out.push(&s[start..]);
+    }
+    out
+}
+
+/// Split on any of the given delimiter chars, dropping empty pieces.
+pub fn split_on_multiple<'a>(s: &'a str, delims: &[char]) -> Vec<&'a str> {
+    s.split(|c: char| delims.contains(&c))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Split into blank-line-separated blocks (whitespace-only lines count as
+/// blank). Runs of blank lines and leading/trailing ones produce no empty
+/// blocks; each block keeps its inner newlines but not the trailing one.
+pub fn split_blocks(input: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = None; // byte offset of the current block's first line
+    let mut end = 0; // byte offset just past its last non-blank line
+    let mut pos = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() {
+                out.push(&input[s..end]);
+            }
+        } else {
+            start.get_or_insert(pos);
+            end = pos + line.trim_end_matches(['\n', '\r']).len();
+        }
+        pos += line.len();
+    }
+    if let Some(s) = start {
+        out.push(&input[s..end]);
+    }
+    out
+}
+
+/// Replace each tab with spaces up to the next multiple of `tab_width`
+/// columns (counted in chars), as a terminal would display it.
+pub fn expand_tabs(line: &str, tab_width: usize) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut col = 0;
+    for c in line.chars() {
+        if c == '\t' && tab_width > 0 {
+            let pad = tab_width - col % tab_width;
+            out.extend(std::iter::repeat_n(' ', pad));
+            col += pad;
+        } else {
+            out.push(c);
+            col += 1;
+        }
+    }
+    out
+}
+
+/// Lines as a rectangular char grid for column-aligned input: tabs expanded
+/// (see `expand_tabs`), short rows right-padded with spaces, and trailing
+/// blank lines dropped. Empty when the input has no non-blank line.
+pub fn normalize_grid(input: &str, tab_width: usize) -> Vec<Vec<char>> {
+    let mut rows: Vec<Vec<char>> = input
+        .lines()
+        .map(|line| expand_tabs(line, tab_width).chars().collect())
+        .collect();
+    while rows
+        .last()
+        .is_some_and(|row| row.iter().all(|c| c.is_whitespace()))
+    {
+        rows.pop();
+    }
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, ' ');
+    }
+    rows
+}
+
+/// Column ranges of the side-by-side blocks in a grid: maximal runs of
+/// columns where some row has a non-whitespace char. Rows may be ragged;
+/// missing cells count as blank.
+pub fn column_blocks(grid: &[Vec<char>]) -> Vec<Range<usize>> {
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let blank = |col: usize| {
+        grid.iter()
+            .all(|row| row.get(col).is_none_or(|c| c.is_whitespace()))
+    };
+    let mut out = Vec::new();
+    let mut start = None;
+    for col in 0..width {
+        match (blank(col), start) {
+            (false, None) => start = Some(col),
+            (true, Some(s)) => {
+                out.push(s..col);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        out.push(s..width);
+    }
+    out
+}
+
+/// Parse each blank-line-separated block with `f` (see `split_blocks`).
+pub fn parse_blocks_with<T>(input: &str, f: impl FnMut(&str) -> T) -> Vec<T> {
+    split_blocks(input).into_iter().map(f).collect()
+}
+
+/// Parse `"key<sep> v1 v2 ..."` (e.g. `"aaa: bbb ccc"`) into the key and its
+/// whitespace-separated values; None if `sep` is missing or any part fails to parse.
+pub fn parse_key_list<K: FromStr, V: FromStr>(line: &str, sep: char) -> Option<(K, Vec<V>)> {
+    let (k, rest) = line.split_once(sep)?;
+    let key = k.trim().parse().ok()?;
+    let vals = rest
+        .split_whitespace()
+        .map(|t| t.parse().ok())
+        .collect::<Option<Vec<V>>>()?;
+    Some((key, vals))
+}
+
+/// `parse_key_list` over every non-blank line, collected into a map.
+pub fn parse_key_lists<K, V>(input: &str, sep: char) -> Option<HashMap<K, Vec<V>>>
+where
+    K: FromStr + Eq + Hash,
+    V: FromStr,
+{
+    input
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| parse_key_list(l, sep))
+        .collect()
+}
+
+/// Parse `"k1=v1,k2=v2"` style pairs in order (`pair_sep = ','`, `kv_sep = '='`);
+/// pieces are trimmed and empty pieces skipped. None on a malformed pair.
+pub fn parse_kv_pairs<K: FromStr, V: FromStr>(
+    s: &str,
+    pair_sep: char,
+    kv_sep: char,
+) -> Option<Vec<(K, V)>> {
+    s.split(pair_sep)
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| {
+            let (k, v) = p.split_once(kv_sep)?;
+            Some((k.trim().parse().ok()?, v.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// `parse_kv_pairs` collected into a map (later keys overwrite earlier ones).
+pub fn parse_kv_map<K, V>(s: &str, pair_sep: char, kv_sep: char) -> Option<HashMap<K, V>>
+where
+    K: FromStr + Eq + Hash,
+    V: FromStr,
+{
+    parse_kv_pairs(s, pair_sep, kv_sep).map(|v| v.into_iter().collect())
+}
+
+/// Parse every `sep`-separated piece of `s` (trimmed; empty pieces skipped),
+/// e.g. `parse_split::<u64>("3,4, 5", ',')`. Errors carry line 1 and the column.
+pub fn parse_split<T: FromStr>(s: &str, sep: char) -> Result<Vec<T>, ParseError>
+where
+    T::Err: fmt::Display,
+{
+    tokens(s, sep)
+        .map(|(col, t)| parse_token(t, 1, col + 1))
+        .collect()
+}
+
+/// Parse each non-blank line (trimmed) as one `T`; errors carry the line number.
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T::Err: fmt::Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            let col = l.chars().take_while(|c| c.is_whitespace()).count();
+            parse_token(l.trim(), i + 1, col + 1)
+        })
+        .collect()
+}
+
+/// Comma-separated `i64` fields of each non-blank line, skipping `#` comments;
+/// every line must have exactly `N` fields, none of them empty.
+fn parse_tuples<const N: usize>(input: &str) -> Result<Vec<[i64; N]>, ParseError> {
+    let mut out = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let t = line.trim();
+        if t.is_empty() || t.starts_with('#') {
+            continue;
+        }
+        let mut fields = [0i64; N];
+        let mut n = 0;
+        for (col, tok) in split_fields(line, ',') {
+            if tok.is_empty() {
+                return Err(ParseError {
+                    line: i + 1,
+                    column: col + 1,
+                    token: t.to_string(),
+                    message: "empty field".into(),
+                });
+            }
+            if n < N {
+                fields[n] = parse_token(tok, i + 1, col + 1)?;
+            }
+            n += 1;
+        }
+        if n != N {
+            return Err(ParseError {
+                line: i + 1,
+                column: 1,
+                token: t.to_string(),
+                message: format!("expected {N} comma-separated numbers, found {n}"),
+            });
+        }
+        out.push(fields);
+    }
+    Ok(out)
+}
+
+/// Parse `x,y` lines into `Point`s (blank lines and `#` comments skipped).
+pub fn parse_points2(input: &str) -> Result<Vec<Point>, ParseError> {
+    Ok(parse_tuples::<2>(input)?
+        .into_iter()
+        .map(|[x, y]| Point::new(x, y))
+        .collect())
+}
+
+/// Parse `x,y,z` lines into `Point3`s (blank lines and `#` comments skipped).
+pub fn parse_points3(input: &str) -> Result<Vec<Point3>, ParseError> {
+    Ok(parse_tuples::<3>(input)?
+        .into_iter()
+        .map(|[x, y, z]| Point3::new(x, y, z))
+        .collect())
+}
+
+/// Every integer in `s` (a `-` directly before digits makes it negative),
+/// ignoring all other text: `"p=3,-4 v=10"` gives `[3, -4, 10]`.
+pub fn extract_ints(s: &str) -> Vec<i64> {
+    let b = s.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < b.len() {
+        if b[i].is_ascii_digit() {
+            // Parse the sign with the digits so i64::MIN fits.
+            let start = if i > 0 && b[i - 1] == b'-' { i - 1 } else { i };
+            while i < b.len() && b[i].is_ascii_digit() {
+                i += 1;
+            }
+            if let Ok(v) = s[start..i].parse::<i64>() {
+                out.push(v);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Raw puzzle input with on-demand views, so a day starts from
+/// `let input = Input::new(raw);` and picks the shape it needs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Input {
+    raw: String,
+}
+
+impl Input {
+    pub fn new(raw: impl Into<String>) -> Self {
+        Self { raw: raw.into() }
+    }
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+    /// Non-blank lines with trailing whitespace (incl. `\r`) removed.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.raw
+            .lines()
+            .map(str::trim_end)
+            .filter(|l| !l.is_empty())
+    }
+    /// Blank-line-separated sections (see `split_blocks`).
+    pub fn blocks(&self) -> Vec<&str> {
+        split_blocks(&self.raw)
+    }
+    /// Character grid of the non-blank lines (rows may be ragged).
+    pub fn grid(&self) -> Vec<Vec<char>> {
+        self.lines().map(|l| l.chars().collect()).collect()
+    }
+    /// Character grid as a `DenseGrid2D`, short rows padded with `fill`.
+    pub fn dense_grid(&self, fill: char) -> DenseGrid2D<char> {
+        let rows = self.grid();
+        let w = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut g = DenseGrid2D::new(w, rows.len(), fill);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                *g.get_mut(x, y) = c;
+            }
+        }
+        g
+    }
+    /// Every integer anywhere in the input (see `extract_ints`).
+    pub fn ints(&self) -> Vec<i64> {
+        extract_ints(&self.raw)
+    }
+    /// Each non-blank line parsed as `T`.
+    pub fn lines_as<T: FromStr>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: fmt::Display,
+    {
+        parse_lines(&self.raw)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for Input {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+/// Like `parse_grid_chars`, but every line must have the same width. A stray
+/// trailing `\r` on a line is dropped rather than counted as a cell.
+pub fn try_parse_grid_chars(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid: Vec<Vec<char>> = input
+        .lines()
+        .map(|l| l.strip_suffix('\r').unwrap_or(l).chars().collect())
+        .collect();
+    if let Some(first) = grid.first() {
+        if let Some((i, row)) = grid
+            .iter()
+            .enumerate()
+            .find(|(_, r)| r.len() != first.len())
+        {
+            return Err(ParseError {
+                line: i + 1,
+                column: row.len().min(first.len()) + 1,
+                token: row.iter().collect(),
+                message: format!("expected {} columns, found {}", first.len(), row.len()),
+            });
+        }
+    }
+    Ok(grid)
+}
+
+/// Like `parse_grid_digits`, but any non-digit (other than trailing
+/// whitespace) is an error instead of being skipped.
+pub fn try_parse_grid_digits(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| {
+            l.trim_end()
+                .chars()
+                .enumerate()
+                .map(|(j, c)| {
+                    c.to_digit(10).map(i64::from).ok_or_else(|| ParseError {
+                        line: i + 1,
+                        column: j + 1,
+                        token: c.to_string(),
+                        message: "expected a digit".to_string(),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Like `parse_ints_whitespace`, but a non-integer token is an error.
+pub fn try_parse_ints_whitespace(input: &str) -> Result<Vec<i64>, ParseError> {
+    let mut out = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut col = 0;
+        for piece in line.split(char::is_whitespace) {
+            if !piece.is_empty() {
+                out.push(parse_token(piece, i + 1, col + 1)?);
+            }
+            col += piece.chars().count() + 1;
+        }
+    }
+    Ok(out)
+}
+
+/// Like `parse_lines_i64`, but a line that is not an integer is an error.
+pub fn try_parse_lines_i64(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_lines(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +547,133 @@ mod tests {
         let v2 = parse_lines_i64("\n10\n 20 \n\n-5\n");
         assert_eq!(v2, vec![10, 20, -5]);
     }
+
+    #[test]
+    fn string_windows_chunks_and_splits() {
+        assert_eq!(windows_str("abcd", 2), vec!["ab", "bc", "cd"]);
+        assert!(windows_str("ab", 3).is_empty());
+        assert!(windows_str("ab", 0).is_empty());
+        assert_eq!(windows_str("héé", 2), vec!["hé", "éé"]);
+        assert_eq!(chunks_str("[A]     [C]", 4), vec!["[A] ", "    ", "[C]"]);
+        assert_eq!(chunks_str("", 3), Vec::<&str>::new());
+        assert_eq!(
+            split_on_multiple("1-3,5-7;9", &['-', ',', ';']),
+            vec!["1", "3", "5", "7", "9"]
+        );
+        assert_eq!(split_on_multiple(",,a,,", &[',']), vec!["a"]);
+    }
+
+    #[test]
+    fn blank_line_blocks() {
+        let input = "\n1-3\n5-7\n\n  \n4\r\n9\r\n\r\nlast";
+        assert_eq!(split_blocks(input), vec!["1-3\n5-7", "4\r\n9", "last"]);
+        assert!(split_blocks("\n \n").is_empty());
+        let sums = parse_blocks_with("1\n2\n\n3\n", |b| parse_lines_i64(b).iter().sum::<i64>());
+        assert_eq!(sums, vec![3, 3]);
+    }
+
+    #[test]
+    fn tabs_and_column_blocks() {
+        assert_eq!(expand_tabs("a\tb\t\tc", 4), "a   b       c");
+        let grid = normalize_grid("12\t3\n4  56\n+\n \n", 4);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 5));
+        assert_eq!(column_blocks(&grid), vec![0..2, 3..5]);
+        let ragged = vec![vec!['1', ' ', '2'], vec!['3']];
+        assert_eq!(column_blocks(&ragged), vec![0..1, 2..3]);
+        assert!(normalize_grid(" \n\n", 8).is_empty());
+    }
+
+    #[test]
+    fn key_value_lines() {
+        let (k, v): (String, Vec<String>) = parse_key_list("aaa: you hhh", ':').unwrap();
+        assert_eq!(
+            (k.as_str(), v),
+            ("aaa", vec!["you".to_string(), "hhh".to_string()])
+        );
+        let (k, v): (u64, Vec<i64>) = parse_key_list("190: 10 -19", ':').unwrap();
+        assert_eq!((k, v), (190, vec![10, -19]));
+        assert!(parse_key_list::<u64, i64>("190 10 19", ':').is_none());
+        let g: HashMap<String, Vec<String>> = parse_key_lists("a: b c\n\nb: c\n", ':').unwrap();
+        assert_eq!(g["a"].len(), 2);
+        assert_eq!(g["b"], vec!["c"]);
+        let pairs: Vec<(char, u32)> = parse_kv_pairs("x=787, m=2655,a=1222", ',', '=').unwrap();
+        assert_eq!(pairs, vec![('x', 787), ('m', 2655), ('a', 1222)]);
+        let m: HashMap<String, i32> = parse_kv_map("p=1;v=-2", ';', '=').unwrap();
+        assert_eq!(m["v"], -2);
+        assert!(parse_kv_map::<String, i32>("p=1;v", ';', '=').is_none());
+    }
+
+    #[test]
+    fn fallible_split_and_lines() {
+        assert_eq!(parse_split::<u64>("3,4, 5\n", ','), Ok(vec![3, 4, 5]));
+        assert_eq!(parse_split::<i32>("1 -2  3", ' '), Ok(vec![1, -2, 3]));
+        let e = parse_split::<u8>("1, x2,3", ',').unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (1, 4, "x2"));
+        assert_eq!(parse_lines::<i64>("10\n\n -5\n"), Ok(vec![10, -5]));
+        let e = parse_lines::<i64>("1\n2\n  x\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+        assert!(e.to_string().starts_with("3:3: cannot parse \"x\""));
+    }
+
+    #[test]
+    fn point_lines() {
+        let pts = parse_points2("# corners\n7,1\n\n 11, -7 \n").unwrap();
+        assert_eq!(pts, vec![Point::new(7, 1), Point::new(11, -7)]);
+        let p3 = parse_points3("162,817,812\n57,618,57\n").unwrap();
+        assert_eq!(p3[1], Point3::new(57, 618, 57));
+        let e = parse_points3("1,2,3\n4,5\n").unwrap_err();
+        assert_eq!(e.line, 2);
+        let e = parse_points2("1,y\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (1, 3, "y"));
+        let e = parse_points2("1,2\n1,,2\n").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.message.as_str()),
+            (2, 3, "empty field")
+        );
+    }
+
+    #[test]
+    fn input_views() {
+        let input = Input::from("#.\r\n.#\n\n3\n-4\n");
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            vec!["#.", ".#", "3", "-4"]
+        );
+        assert_eq!(input.blocks().len(), 2);
+        assert_eq!(
+            Input::new(input.blocks()[0]).grid(),
+            vec![vec!['#', '.'], vec!['.', '#']]
+        );
+        assert_eq!(input.ints(), vec![3, -4]);
+        assert_eq!(
+            Input::new(input.blocks()[1]).lines_as::<i32>(),
+            Ok(vec![3, -4])
+        );
+        let g = Input::new("ab\nc").dense_grid(' ');
+        assert_eq!((g.width(), g.height(), *g.get(1, 1)), (2, 2, ' '));
+        assert_eq!(extract_ints("p=3,-4 v=10 x-y 7"), vec![3, -4, 10, 7]);
+        assert_eq!(extract_ints("lo=-9223372036854775808"), vec![i64::MIN]);
+    }
+
+    #[test]
+    fn try_variants_report_positions() {
+        assert_eq!(
+            try_parse_grid_digits("12\r\n90\n"),
+            Ok(vec![vec![1, 2], vec![9, 0]])
+        );
+        let e = try_parse_grid_digits("12\n9x0\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (2, 2, "x"));
+        assert!(try_parse_grid_chars("ab\ncd").is_ok());
+        assert_eq!(
+            try_parse_grid_chars("ab\r\ncd\r"),
+            Ok(vec![vec!['a', 'b'], vec!['c', 'd']])
+        );
+        let e = try_parse_grid_chars("abc\nab\nabc").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 3));
+        assert_eq!(try_parse_ints_whitespace("1 -2\n 3"), Ok(vec![1, -2, 3]));
+        let e = try_parse_ints_whitespace("1 2\n3  4.5").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (2, 4, "4.5"));
+        assert_eq!(try_parse_lines_i64("7\nz").unwrap_err().line, 2);
+    }
 }
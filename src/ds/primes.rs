@@ -0,0 +1,187 @@
+//! Primes: sieve, deterministic primality, factorization and divisors.
+//!
+//! - `sieve(n)`: linear sieve listing every prime `<= n` in O(n).
+//! - `is_prime(n)`: Miller-Rabin with the 7 bases that are exact for all `u64`.
+//! - `factorize(n)`: Pollard's rho on top of `is_prime`, so even 64-bit
+//!   semiprimes split quickly; trial division handles the small factors.
+//! - `divisors(n)`: every divisor, ascending, built from the factorization.
+
+/// All primes `<= n` in ascending order (linear sieve).
+pub fn sieve(n: usize) -> Vec<usize> {
+    let mut lp = vec![0usize; n + 1]; // least prime factor
+    let mut primes = Vec::new();
+    for i in 2..=n {
+        if lp[i] == 0 {
+            lp[i] = i;
+            primes.push(i);
+        }
+        for &p in &primes {
+            if p > lp[i] || i * p > n {
+                break;
+            }
+            lp[i * p] = p;
+        }
+    }
+    primes
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut b: u64, mut e: u64, m: u64) -> u64 {
+    let mut r = 1 % m;
+    b %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = mul_mod(r, b, m);
+        }
+        b = mul_mod(b, b, m);
+        e >>= 1;
+    }
+    r
+}
+
+/// Deterministic primality test for any `u64`.
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 7] = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
+    if n < 2 {
+        return false;
+    }
+    for p in [2u64, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for &a in &BASES {
+        let a = a % n;
+        if a == 0 {
+            continue;
+        }
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A non-trivial factor of the odd composite `n` (Pollard's rho, Floyd cycle).
+fn rho(n: u64) -> u64 {
+    for c in 1.. {
+        // In u128: x² mod n plus c can pass u64::MAX when n is close to it.
+        let f = |x: u64| ((mul_mod(x, x, n) as u128 + c) % n as u128) as u64;
+        let (mut x, mut y, mut d) = (2u64, 2u64, 1u64);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd_u64(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+fn split(n: u64, out: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        out.push(n);
+        return;
+    }
+    let d = rho(n);
+    split(d, out);
+    split(n / d, out);
+}
+
+/// Prime factorization as `(prime, exponent)` pairs, primes ascending.
+/// `factorize(1)` is empty; `factorize(0)` panics.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "cannot factorize 0");
+    let mut ps = Vec::new();
+    for p in 2..1000u64 {
+        while n.is_multiple_of(p) {
+            ps.push(p);
+            n /= p;
+        }
+    }
+    split(n, &mut ps);
+    ps.sort_unstable();
+    let mut out: Vec<(u64, u32)> = Vec::new();
+    for p in ps {
+        match out.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => out.push((p, 1)),
+        }
+    }
+    out
+}
+
+/// Every positive divisor of `n`, ascending (`n > 0`).
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut ds = vec![1u64];
+    for (p, e) in factorize(n) {
+        let len = ds.len();
+        let mut pk = 1;
+        for _ in 0..e {
+            pk *= p;
+            for i in 0..len {
+                ds.push(ds[i] * pk);
+            }
+        }
+    }
+    ds.sort_unstable();
+    ds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{divisors, factorize, is_prime, sieve};
+
+    #[test]
+    fn sieve_matches_is_prime() {
+        let ps = sieve(1000);
+        assert_eq!(&ps[..6], &[2, 3, 5, 7, 11, 13]);
+        assert_eq!(ps.len(), 168);
+        let brute: Vec<usize> = (0..=1000).filter(|&n| is_prime(n as u64)).collect();
+        assert_eq!(ps, brute);
+        assert!(sieve(1).is_empty());
+    }
+
+    #[test]
+    fn large_primes_and_factorization() {
+        assert!(is_prime(18_446_744_073_709_551_557)); // largest u64 prime
+        assert!(!is_prime(3_215_031_751)); // strong pseudoprime to 2, 3, 5, 7
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1), vec![]);
+        let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+        assert_eq!(factorize(p * q), vec![(q, 1), (p, 1)]);
+    }
+
+    #[test]
+    fn divisors_ascending() {
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(97), vec![1, 97]);
+        assert_eq!(divisors(720).len(), 30);
+    }
+}
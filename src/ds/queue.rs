@@ -1,4 +1,8 @@
 //! Queue/Deque: FIFO and double-ended queues for BFS and sliding windows.
+//!
+//! Both types wrap `std::collections::VecDeque`, a growable ring buffer, so
+//! pushes and pops at either end are amortized O(1) and iteration walks the
+//! elements front-to-back without reallocating.
 
 use std::collections::VecDeque;
 
@@ -11,6 +15,12 @@ impl<T> Queue<T> {
     pub fn new() -> Self {
         Self { q: VecDeque::new() }
     }
+    /// Create with room for `n` elements before reallocating.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            q: VecDeque::with_capacity(n),
+        }
+    }
     pub fn push(&mut self, x: T) {
         self.q.push_back(x)
     }
@@ -20,6 +30,18 @@ impl<T> Queue<T> {
     pub fn peek(&self) -> Option<&T> {
         self.q.front()
     }
+    /// Next element to be popped (same as `peek`).
+    pub fn peek_front(&self) -> Option<&T> {
+        self.q.front()
+    }
+    /// Most recently pushed element.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.q.back()
+    }
+    /// Iterate front (oldest) to back (newest).
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.q.iter()
+    }
     pub fn is_empty(&self) -> bool {
         self.q.is_empty()
     }
@@ -37,6 +59,12 @@ impl<T> Deque<T> {
     pub fn new() -> Self {
         Self { d: VecDeque::new() }
     }
+    /// Create with room for `n` elements before reallocating.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            d: VecDeque::with_capacity(n),
+        }
+    }
     pub fn push_front(&mut self, x: T) {
         self.d.push_front(x)
     }
@@ -55,6 +83,18 @@ impl<T> Deque<T> {
     pub fn back(&self) -> Option<&T> {
         self.d.back()
     }
+    /// Alias for `front`, mirroring `Queue::peek_front`.
+    pub fn peek_front(&self) -> Option<&T> {
+        self.d.front()
+    }
+    /// Alias for `back`, mirroring `Queue::peek_back`.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.d.back()
+    }
+    /// Iterate front to back.
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, T> {
+        self.d.iter()
+    }
     pub fn is_empty(&self) -> bool {
         self.d.is_empty()
     }
@@ -99,4 +139,24 @@ mod tests {
         q.push(1);
         assert_eq!(q.len(), 1);
     }
+
+    #[test]
+    fn peeks_and_iter() {
+        let mut q = Queue::with_capacity(4);
+        assert!(q.peek_front().is_none() && q.peek_back().is_none());
+        for x in 1..=3 {
+            q.push(x);
+        }
+        assert_eq!(q.peek_front(), Some(&1));
+        assert_eq!(q.peek_back(), Some(&3));
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let mut d = Deque::with_capacity(2);
+        d.push_back(2);
+        d.push_front(1);
+        d.push_back(3);
+        assert_eq!(d.peek_front(), Some(&1));
+        assert_eq!(d.peek_back(), Some(&3));
+        assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
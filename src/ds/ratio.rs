@@ -0,0 +1,250 @@
+//! `Ratio`: exact fractions over `i128` without a bignum dependency.
+//!
+//! Values are always normalized: the denominator is positive and
+//! `gcd(num, den) == 1`, so derived equality and hashing are structural.
+//! `checked_*` methods return None on overflow; the operators panic instead
+//! (like integer overflow in debug builds). When intermediate values can
+//! outgrow `i128`, fall back to `num_rational::BigRational` (see `linalg`).
+//! Ordering is exact and never overflows (continued-fraction comparison).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+/// `gcd(|a|, |b|)`, or None when that is 2^127 and so not an `i128` (each
+/// argument 0 or `i128::MIN`, at least one of them `i128::MIN`).
+fn gcd(a: i128, b: i128) -> Option<i128> {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).ok()
+}
+
+impl Default for Ratio {
+    fn default() -> Self {
+        Self::from_integer(0)
+    }
+}
+
+impl Ratio {
+    /// `num / den` in lowest terms; None if `den == 0` or normalizing overflows.
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+        let g = gcd(num, den)?;
+        let (mut num, mut den) = (num / g, den / g);
+        if den < 0 {
+            num = num.checked_neg()?;
+            den = den.checked_neg()?;
+        }
+        Some(Self { num, den })
+    }
+    pub fn from_integer(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+    /// Always positive.
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+    /// The value if it is a whole number.
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+    /// Largest integer `<= self`.
+    pub fn floor(&self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+    /// Smallest integer `>= self`.
+    pub fn ceil(&self) -> i128 {
+        // No negation, so an `i128::MIN` numerator is fine; the `+ 1` only
+        // happens below a non-integer value and cannot overflow.
+        let q = self.floor();
+        if self.is_integer() {
+            q
+        } else {
+            q + 1
+        }
+    }
+    /// `-self`; None only when the numerator is `i128::MIN`.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        // Divide by gcd of denominators first to keep intermediates small.
+        let g = gcd(self.den, rhs.den)?;
+        let num = self
+            .num
+            .checked_mul(rhs.den / g)?
+            .checked_add(rhs.num.checked_mul(self.den / g)?)?;
+        Self::new(num, (self.den / g).checked_mul(rhs.den)?)
+    }
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(rhs.checked_neg()?)
+    }
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let g1 = gcd(self.num, rhs.den)?.max(1);
+        let g2 = gcd(rhs.num, self.den)?.max(1);
+        let num = (self.num / g1).checked_mul(rhs.num / g2)?;
+        let den = (self.den / g2).checked_mul(rhs.den / g1)?;
+        Self::new(num, den)
+    }
+    /// None on overflow or division by zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_mul(Self::new(rhs.den, rhs.num)?)
+    }
+}
+
+impl From<i128> for Ratio {
+    fn from(n: i128) -> Self {
+        Self::from_integer(n)
+    }
+}
+impl From<i64> for Ratio {
+    fn from(n: i64) -> Self {
+        Self::from_integer(n as i128)
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare integer parts; on a tie compare the reciprocals of the
+        // remainders (reversed), which is Euclid's algorithm on both sides.
+        let (mut a, mut b) = ((self.num, self.den), (other.num, other.den));
+        let mut flipped = false;
+        loop {
+            let (qa, ra) = (a.0.div_euclid(a.1), a.0.rem_euclid(a.1));
+            let (qb, rb) = (b.0.div_euclid(b.1), b.0.rem_euclid(b.1));
+            let ord = match (qa.cmp(&qb), ra, rb) {
+                (Ordering::Equal, 0, 0) => Ordering::Equal,
+                (Ordering::Equal, 0, _) => Ordering::Less,
+                (Ordering::Equal, _, 0) => Ordering::Greater,
+                (Ordering::Equal, _, _) => {
+                    (a, b) = ((a.1, ra), (b.1, rb));
+                    flipped = !flipped;
+                    continue;
+                }
+                (o, _, _) => o,
+            };
+            return if flipped { ord.reverse() } else { ord };
+        }
+    }
+}
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.checked_neg().expect("Ratio overflow")
+    }
+}
+impl Add for Ratio {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("Ratio overflow")
+    }
+}
+impl Sub for Ratio {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).expect("Ratio overflow")
+    }
+}
+impl Mul for Ratio {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).expect("Ratio overflow")
+    }
+}
+impl Div for Ratio {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self.checked_div(rhs)
+            .expect("Ratio overflow or division by zero")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ratio;
+
+    fn r(n: i128, d: i128) -> Ratio {
+        Ratio::new(n, d).unwrap()
+    }
+
+    #[test]
+    fn normalizes_and_computes() {
+        assert_eq!(r(6, -8), r(-3, 4));
+        assert_eq!(r(-3, 4).denom(), 4);
+        assert!(Ratio::new(1, 0).is_none());
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(3, 4), r(-1, 4));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert_eq!((r(1, 3) * Ratio::from(3i64)).to_integer(), Some(1));
+        assert_eq!((r(-7, 2).floor(), r(-7, 2).ceil()), (-4, -3));
+        assert_eq!(r(-7, 2).to_string(), "-7/2");
+        assert!(r(1, 1).checked_div(Ratio::default()).is_none());
+        assert!(Ratio::from(i128::MAX).checked_add(r(1, 1)).is_none());
+        // i128::MIN normalizes where it can and is rejected where it can't.
+        assert_eq!(r(i128::MIN, 2).numer(), i128::MIN / 2);
+        assert_eq!(r(i128::MIN, 1).numer(), i128::MIN);
+        assert!(Ratio::new(i128::MIN, -1).is_none());
+        assert!(Ratio::new(i128::MIN, i128::MIN).is_none());
+        // ...and only negating it fails.
+        let min = r(i128::MIN, 1);
+        assert_eq!((min.floor(), min.ceil()), (i128::MIN, i128::MIN));
+        assert_eq!(r(i128::MIN + 1, 2).ceil(), i128::MIN / 2 + 1);
+        assert!(min.checked_neg().is_none());
+        assert_eq!(-r(3, 4), r(-3, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ratio overflow")]
+    fn negating_min_panics() {
+        let _ = -Ratio::from(i128::MIN);
+    }
+
+    #[test]
+    fn exact_ordering_without_overflow() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(2, 4) == r(1, 2));
+        let big = i128::MAX;
+        assert!(r(big - 2, big - 1) < r(big - 1, big));
+        assert!(r(-big, big - 1) > r(-(big - 1), big - 2));
+        let mut v = vec![r(3, 7), r(-2, 5), r(5, 1), r(3, 8)];
+        v.sort();
+        assert_eq!(v, vec![r(-2, 5), r(3, 8), r(3, 7), r(5, 1)]);
+    }
+}
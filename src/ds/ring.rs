@@ -0,0 +1,128 @@
+//! A dial of `size` positions `0..size` that wraps around.
+//!
+//! `ModRing` tracks the current position and answers the usual dial
+//! questions in O(1) however long the rotation: where it ends up
+//! (`rotate` / `position_after`) and how many clicks along the way land on
+//! a given mark (`crossings_of`).
+
+use std::fmt;
+
+/// Direction of a rotation: `Left` counts down, `Right` counts up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    /// `L`/`R` (either case) to a turn.
+    pub fn from_char(c: char) -> Option<Turn> {
+        match c.to_ascii_uppercase() {
+            'L' => Some(Turn::Left),
+            'R' => Some(Turn::Right),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Turn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Turn::Left => "L",
+            Turn::Right => "R",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ModRing {
+    size: u64,
+    pos: u64,
+}
+
+impl ModRing {
+    /// A ring of `size` positions starting at `start` (taken mod `size`).
+    /// Panics if `size` is 0.
+    pub fn new(size: u64, start: u64) -> Self {
+        assert!(size > 0, "ring size must be positive");
+        ModRing {
+            size,
+            pos: start % size,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// True when the ring points at `mark` (taken mod `size`).
+    pub fn is_at(&self, mark: u64) -> bool {
+        self.pos == mark % self.size
+    }
+
+    /// Where `steps` clicks in direction `turn` would leave the ring.
+    pub fn position_after(&self, turn: Turn, steps: u64) -> u64 {
+        let steps = steps % self.size;
+        match turn {
+            Turn::Right => (self.pos + steps) % self.size,
+            Turn::Left => (self.pos + self.size - steps) % self.size,
+        }
+    }
+
+    /// Rotate by `steps` clicks and return the new position.
+    pub fn rotate(&mut self, turn: Turn, steps: u64) -> u64 {
+        self.pos = self.position_after(turn, steps);
+        self.pos
+    }
+
+    /// How many of the next `steps` clicks in direction `turn` would land
+    /// on `mark`, counting the final one but not the starting position.
+    pub fn crossings_of(&self, mark: u64, turn: Turn, steps: u64) -> u64 {
+        let mark = mark % self.size;
+        let distance = match turn {
+            Turn::Right => (mark + self.size - self.pos) % self.size,
+            Turn::Left => (self.pos + self.size - mark) % self.size,
+        };
+        let first = if distance == 0 { self.size } else { distance };
+        if steps < first {
+            0
+        } else {
+            1 + (steps - first) / self.size
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModRing, Turn};
+
+    #[test]
+    fn rotation_wraps_both_ways() {
+        let mut dial = ModRing::new(100, 150);
+        assert_eq!(dial.position(), 50);
+        assert_eq!(dial.rotate(Turn::Left, 68), 82);
+        assert_eq!(dial.rotate(Turn::Right, 318), 0);
+        assert!(dial.is_at(200));
+        assert_eq!(dial.position_after(Turn::Left, 1), 99);
+        assert_eq!(Turn::from_char('r'), Some(Turn::Right));
+        assert_eq!(Turn::Left.to_string(), "L");
+    }
+
+    #[test]
+    fn crossings_match_click_by_click() {
+        for start in 0..7 {
+            let ring = ModRing::new(7, start);
+            for turn in [Turn::Left, Turn::Right] {
+                for steps in 0..30 {
+                    let mut slow = ring;
+                    let hits = (0..steps).filter(|_| slow.rotate(turn, 1) == 3).count() as u64;
+                    assert_eq!(ring.crossings_of(3, turn, steps), hits);
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
-//! Strongly Connected Components via Tarjan's algorithm.
+//! Strongly Connected Components via Tarjan's algorithm, Kosaraju's algorithm
+//! as a cross-check, and the condensation DAG built from them.
+
+use crate::ds::graph::reverse_graph;
+use crate::ds::search::{dfs_visit, DfsVisitor};
 
 /// Returns a vector of components; each component is a vector of node indices.
 pub fn tarjan_scc(adj: &Vec<Vec<usize>>) -> Vec<Vec<usize>> {
@@ -66,9 +70,78 @@ pub fn tarjan_scc(adj: &Vec<Vec<usize>>) -> Vec<Vec<usize>> {
     comps
 }
 
+/// Kosaraju's two-pass SCC algorithm (iterative, no recursion limit).
+/// Components come out in topological order of the condensation: no edge
+/// goes from a later component to an earlier one.
+pub fn kosaraju_scc(adj: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Finish(Vec<usize>);
+    impl DfsVisitor for Finish {
+        fn finish(&mut self, u: usize, _parent: Option<usize>) {
+            self.0.push(u);
+        }
+    }
+    struct Collect(Vec<Vec<usize>>);
+    impl DfsVisitor for Collect {
+        fn discover(&mut self, u: usize, parent: Option<usize>) {
+            if parent.is_none() {
+                self.0.push(Vec::new());
+            }
+            self.0.last_mut().unwrap().push(u);
+        }
+    }
+    let n = adj.len();
+    let mut order = Finish(Vec::with_capacity(n));
+    dfs_visit(n, adj, 0..n, &mut order);
+    let mut comps = Collect(Vec::new());
+    dfs_visit(
+        n,
+        &reverse_graph(adj),
+        order.0.into_iter().rev(),
+        &mut comps,
+    );
+    comps.0
+}
+
+/// The DAG of strongly connected components.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Condensation {
+    /// `comp[u]` is the component index of node `u`.
+    pub comp: Vec<usize>,
+    /// Nodes of each component.
+    pub members: Vec<Vec<usize>>,
+    /// Deduplicated edges between distinct components.
+    pub dag: Vec<Vec<usize>>,
+}
+
+/// Collapse every SCC of `adj` into one node. Component indices follow a
+/// topological order of the resulting DAG, so a simple loop over `0..k`
+/// (or its reverse) is enough for DAG DP.
+pub fn condensation(adj: &[Vec<usize>]) -> Condensation {
+    let members = kosaraju_scc(adj);
+    let mut comp = vec![0; adj.len()];
+    for (c, ms) in members.iter().enumerate() {
+        for &u in ms {
+            comp[u] = c;
+        }
+    }
+    let mut dag = vec![Vec::new(); members.len()];
+    for (u, vs) in adj.iter().enumerate() {
+        for &v in vs {
+            if comp[u] != comp[v] {
+                dag[comp[u]].push(comp[v]);
+            }
+        }
+    }
+    for out in dag.iter_mut() {
+        out.sort_unstable();
+        out.dedup();
+    }
+    Condensation { comp, members, dag }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::tarjan_scc;
+    use super::{condensation, kosaraju_scc, tarjan_scc};
     #[test]
     fn scc_small() {
         // 0->1->2->0 forms one SCC; 3->4; 4 alone (no edge back)
@@ -95,4 +168,24 @@ mod tests {
         let comps = tarjan_scc(&adj);
         assert_eq!(comps.len(), 2);
     }
+
+    #[test]
+    fn kosaraju_matches_tarjan_and_condenses() {
+        // {0,1,2} -> {3,4} -> 5
+        let adj = vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3, 5], vec![]];
+        let norm = |mut cs: Vec<Vec<usize>>| {
+            cs.iter_mut().for_each(|c| c.sort_unstable());
+            cs.sort();
+            cs
+        };
+        assert_eq!(norm(kosaraju_scc(&adj)), norm(tarjan_scc(&adj)));
+        let c = condensation(&adj);
+        assert_eq!(c.members.len(), 3);
+        assert_eq!(c.comp[0], c.comp[2]);
+        let (a, b, z) = (c.comp[0], c.comp[3], c.comp[5]);
+        assert!(a < b && b < z);
+        assert_eq!(c.dag[a], vec![b]);
+        assert_eq!(c.dag[b], vec![z]);
+        assert!(c.dag[z].is_empty());
+    }
 }
@@ -5,15 +5,31 @@
 //! - Weighted edges use i64 costs; weights are assumed non-negative for
 //!   Dijkstra and A* correctness (typical AoC constraints).
 //! - A* requires an admissible (non-overestimating) heuristic `h`.
+//! - For implicit state spaces (position + direction + keys, ...) use
+//!   `bfs_hash`/`dijkstra_hash`, which intern states into indices internally.
+//! - Grid searches (`grid_bfs`, `grid_dijkstra`) work directly on
+//!   `DenseGrid2D` cells and return a grid of distances.
 
+use crate::ds::coords::Point;
+use crate::ds::dense_grid::DenseGrid2D;
 use crate::ds::indexed_heap::IndexedMinHeap;
-use std::collections::VecDeque;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
 
 /// BFS distances from `start` in an unweighted directed graph.
 /// Returns a vector `dist` of length n with -1 for unreachable.
 /// Compute BFS distances (in edge counts) from `start`.
 pub fn bfs_distances(n: usize, adj: &Vec<Vec<usize>>, start: usize) -> Vec<i64> {
+    bfs_with_prev(n, adj, start).0
+}
+
+/// BFS from `start` that also records predecessor links.
+/// Returns (dist, prev) with -1 distances for unreachable; feed `prev` to
+/// `reconstruct_path` to recover a shortest path.
+pub fn bfs_with_prev(n: usize, adj: &[Vec<usize>], start: usize) -> (Vec<i64>, Vec<Option<usize>>) {
     let mut dist = vec![-1; n];
+    let mut prev = vec![None; n];
     let mut q = VecDeque::new();
     dist[start] = 0;
     q.push_back(start);
@@ -22,10 +38,52 @@ pub fn bfs_distances(n: usize, adj: &Vec<Vec<usize>>, start: usize) -> Vec<i64>
         for &v in &adj[u] {
             if dist[v] == -1 {
                 dist[v] = du + 1;
+                prev[v] = Some(u);
                 q.push_back(v);
             }
         }
     }
+    (dist, prev)
+}
+
+/// Walk predecessor links back from `goal`; returns the path start..=goal.
+/// Works with the `prev` vectors of `bfs_with_prev` and `dijkstra_indexed`.
+/// Only meaningful when `goal` was reached (otherwise the path is just `[goal]`).
+pub fn reconstruct_path(prev: &[Option<usize>], goal: usize) -> Vec<usize> {
+    let mut path = vec![goal];
+    let mut cur = goal;
+    while let Some(p) = prev[cur] {
+        path.push(p);
+        cur = p;
+    }
+    path.reverse();
+    path
+}
+
+/// 0-1 BFS: shortest paths when every edge weight is 0 or 1.
+/// Returns i64 distances, i64::MAX meaning unreachable.
+/// Weight-0 edges go to the front of the deque, weight-1 edges to the back: O(n + m).
+/// - `adj_w[u]` contains (v, w) edges with `w` in {0, 1}, so the same graph
+///   can be handed to `dijkstra_indexed` as well.
+pub fn bfs01_indexed(n: usize, adj_w: &[Vec<(usize, i64)>], start: usize) -> Vec<i64> {
+    let mut dist = vec![i64::MAX; n];
+    let mut dq = VecDeque::new();
+    dist[start] = 0;
+    dq.push_back(start);
+    while let Some(u) = dq.pop_front() {
+        let du = dist[u];
+        for &(v, w) in &adj_w[u] {
+            debug_assert!(w == 0 || w == 1, "bfs01 edge weight must be 0 or 1");
+            if du + w < dist[v] {
+                dist[v] = du + w;
+                if w == 0 {
+                    dq.push_front(v);
+                } else {
+                    dq.push_back(v);
+                }
+            }
+        }
+    }
     dist
 }
 
@@ -51,6 +109,59 @@ pub fn dfs_preorder(n: usize, adj: &Vec<Vec<usize>>, start: usize) -> Vec<usize>
     out
 }
 
+/// Callbacks for `dfs_visit`; every method defaults to a no-op so visitors
+/// only implement what they need.
+pub trait DfsVisitor {
+    /// `u` is entered for the first time (`parent` is None for a root).
+    fn discover(&mut self, _u: usize, _parent: Option<usize>) {}
+    /// All descendants of `u` are done (post-order).
+    fn finish(&mut self, _u: usize, _parent: Option<usize>) {}
+    /// Edge `u -> v` points at a node still on the stack: a directed cycle.
+    fn back_edge(&mut self, _u: usize, _v: usize) {}
+}
+
+/// Iterative DFS from every node in `roots` (skipping already visited ones),
+/// reporting events to `vis`. Pass `0..n` to cover the whole graph, e.g. for
+/// finish orders or cycle detection.
+pub fn dfs_visit(
+    n: usize,
+    adj: &[Vec<usize>],
+    roots: impl IntoIterator<Item = usize>,
+    vis: &mut impl DfsVisitor,
+) {
+    // 0 = unvisited, 1 = on stack, 2 = finished
+    let mut color = vec![0u8; n];
+    let mut st: Vec<(usize, usize, Option<usize>)> = Vec::new(); // (node, next edge, parent)
+    for r in roots {
+        if color[r] != 0 {
+            continue;
+        }
+        color[r] = 1;
+        vis.discover(r, None);
+        st.push((r, 0, None));
+        while let Some(top) = st.last_mut() {
+            let (u, i, parent) = *top;
+            if i < adj[u].len() {
+                top.1 += 1;
+                let v = adj[u][i];
+                match color[v] {
+                    0 => {
+                        color[v] = 1;
+                        vis.discover(v, Some(u));
+                        st.push((v, 0, Some(u)));
+                    }
+                    1 => vis.back_edge(u, v),
+                    _ => {}
+                }
+            } else {
+                color[u] = 2;
+                vis.finish(u, parent);
+                st.pop();
+            }
+        }
+    }
+}
+
 /// Dijkstra on an indexed weighted graph.
 /// - `adj_w[u]` contains (v, w) edges.
 /// Returns (dist, prev) with i64 distances, i64::MAX meaning unreachable.
@@ -78,6 +189,156 @@ pub fn dijkstra_indexed(
     (dist, prev)
 }
 
+/// Bellman-Ford shortest paths from `start`; negative weights are allowed.
+/// Returns `Ok(dist)` (i64::MAX meaning unreachable), or `Err(cycle)` with the
+/// nodes of a negative cycle reachable from `start`, in edge order.
+/// O(n * m); use it when Dijkstra's non-negative assumption does not hold.
+/// - `edges` are directed (u, v, w) triples.
+pub fn bellman_ford(
+    n: usize,
+    edges: &[(usize, usize, i64)],
+    start: usize,
+) -> Result<Vec<i64>, Vec<usize>> {
+    let mut dist = vec![i64::MAX; n];
+    let mut prev = vec![None; n];
+    dist[start] = 0;
+    let mut last = None;
+    // Pass n only relaxes something if a negative cycle is reachable.
+    for _ in 0..n {
+        last = None;
+        for &(u, v, w) in edges {
+            if dist[u] != i64::MAX && dist[u] + w < dist[v] {
+                dist[v] = dist[u] + w;
+                prev[v] = Some(u);
+                last = Some(v);
+            }
+        }
+        if last.is_none() {
+            return Ok(dist);
+        }
+    }
+    let Some(mut x) = last else {
+        return Ok(dist);
+    };
+    // Walking back n steps is guaranteed to land inside the cycle.
+    for _ in 0..n {
+        x = prev[x].expect("relaxed node has a predecessor");
+    }
+    let mut cycle = vec![x];
+    let mut cur = prev[x].expect("cycle node has a predecessor");
+    while cur != x {
+        cycle.push(cur);
+        cur = prev[cur].expect("cycle node has a predecessor");
+    }
+    cycle.reverse();
+    Err(cycle)
+}
+
+/// Dijkstra with a bucket queue (Dial's algorithm) for small integer weights.
+/// Every weight must lie in 0..=max_weight. Returns (dist, prev) like
+/// `dijkstra_indexed`, i64::MAX meaning unreachable.
+/// Runs in O(n + m + D) where D is the largest finite distance, with
+/// `max_weight + 1` circular buckets instead of a heap: prefer it when weights
+/// are bounded by a small constant (single digits on grids, 1/1000 turn costs);
+/// stick with `dijkstra_indexed` when weights are large or unbounded.
+pub fn dijkstra_bucket(
+    n: usize,
+    adj_w: &[Vec<(usize, i64)>],
+    start: usize,
+    max_weight: i64,
+) -> (Vec<i64>, Vec<Option<usize>>) {
+    assert!(max_weight >= 0, "max_weight must be non-negative");
+    let nb = max_weight as usize + 1;
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); nb];
+    let mut dist = vec![i64::MAX; n];
+    let mut prev = vec![None; n];
+    dist[start] = 0;
+    buckets[0].push(start);
+    let mut pending = 1usize;
+    let mut d = 0i64;
+    while pending > 0 {
+        let b = d as usize % nb;
+        while let Some(u) = buckets[b].pop() {
+            pending -= 1;
+            if dist[u] != d {
+                continue; // stale entry
+            }
+            for &(v, w) in &adj_w[u] {
+                debug_assert!((0..=max_weight).contains(&w), "edge weight out of range");
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    prev[v] = Some(u);
+                    buckets[(d + w) as usize % nb].push(v);
+                    pending += 1;
+                }
+            }
+        }
+        d += 1;
+    }
+    (dist, prev)
+}
+
+/// Parallel delta-stepping shortest paths (requires the `rayon` feature).
+/// Same input and result as `dijkstra_indexed`'s distances: non-negative
+/// weights, i64::MAX meaning unreachable.
+///
+/// Nodes are grouped into buckets of width `delta`; each bucket is settled by
+/// relaxing its light edges (w <= delta) in parallel rounds, then its heavy
+/// edges once. Worth it on very large graphs (millions of nodes); on typical
+/// inputs `dijkstra_indexed` is faster. A `delta` around the average edge
+/// weight is a good start.
+#[cfg(feature = "rayon")]
+pub fn delta_stepping(n: usize, adj_w: &[Vec<(usize, i64)>], start: usize, delta: i64) -> Vec<i64> {
+    use rayon::prelude::*;
+    assert!(delta > 0, "delta must be positive");
+    let mut dist = vec![i64::MAX; n];
+    let mut buckets: Vec<Vec<usize>> = Vec::new();
+    let relax = |dist: &mut Vec<i64>, buckets: &mut Vec<Vec<usize>>, v: usize, d: i64| {
+        if d < dist[v] {
+            dist[v] = d;
+            let b = (d / delta) as usize;
+            if buckets.len() <= b {
+                buckets.resize_with(b + 1, Vec::new);
+            }
+            buckets[b].push(v); // stale copies elsewhere are skipped on pop
+        }
+    };
+    let requests = |dist: &[i64], nodes: &[usize], light: bool| -> Vec<(usize, i64)> {
+        nodes
+            .par_iter()
+            .flat_map_iter(|&u| {
+                let du = dist[u];
+                adj_w[u]
+                    .iter()
+                    .filter(move |&&(_, w)| (w <= delta) == light)
+                    .map(move |&(v, w)| (v, du + w))
+            })
+            .collect()
+    };
+    relax(&mut dist, &mut buckets, start, 0);
+    let mut i = 0;
+    while i < buckets.len() {
+        let mut settled = Vec::new();
+        while !buckets[i].is_empty() {
+            let mut frontier = std::mem::take(&mut buckets[i]);
+            frontier.retain(|&u| (dist[u] / delta) as usize == i);
+            frontier.sort_unstable();
+            frontier.dedup();
+            for (v, d) in requests(&dist, &frontier, true) {
+                relax(&mut dist, &mut buckets, v, d);
+            }
+            settled.extend(frontier);
+        }
+        settled.sort_unstable();
+        settled.dedup();
+        for (v, d) in requests(&dist, &settled, false) {
+            relax(&mut dist, &mut buckets, v, d);
+        }
+        i += 1;
+    }
+    dist
+}
+
 /// A* on an indexed weighted graph using a non-negative heuristic `h`.
 /// `h` maps node index -> estimated remaining cost.
 /// A* search with heuristic `h`; returns total cost and the path on success.
@@ -109,15 +370,277 @@ pub fn astar_indexed(
     if g[goal] == i64::MAX {
         return None;
     }
-    // reconstruct path
-    let mut path = Vec::new();
-    let mut cur = Some(goal);
-    while let Some(u) = cur {
-        path.push(u);
-        cur = prev[u];
+    Some((g[goal], reconstruct_path(&prev, goal)))
+}
+
+/// Hash-map interner shared by the implicit-state searches: states get dense
+/// indices in discovery order, with a predecessor link per index.
+struct Interner<S> {
+    ids: HashMap<S, usize>,
+    states: Vec<S>,
+    prev: Vec<Option<usize>>,
+}
+
+impl<S: Clone + Eq + Hash> Interner<S> {
+    fn new(start: S) -> Self {
+        let mut ids = HashMap::new();
+        ids.insert(start.clone(), 0);
+        Self {
+            ids,
+            states: vec![start],
+            prev: vec![None],
+        }
     }
-    path.reverse();
-    Some((g[goal], path))
+    /// Index of `s` and whether it was newly added.
+    fn intern(&mut self, s: S) -> (usize, bool) {
+        if let Some(&id) = self.ids.get(&s) {
+            return (id, false);
+        }
+        let id = self.states.len();
+        self.ids.insert(s.clone(), id);
+        self.states.push(s);
+        self.prev.push(None);
+        (id, true)
+    }
+    fn path_to(&self, id: usize) -> Vec<S> {
+        reconstruct_path(&self.prev, id)
+            .into_iter()
+            .map(|i| self.states[i].clone())
+            .collect()
+    }
+}
+
+/// BFS over an implicit state space.
+/// `neighbors(&s)` yields successor states; the first state satisfying
+/// `is_goal` is returned with its step count and the path from `start`.
+pub fn bfs_hash<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = Interner::new(start);
+    let mut dist = vec![0usize];
+    let mut q = VecDeque::from([0]);
+    while let Some(u) = q.pop_front() {
+        if is_goal(&seen.states[u]) {
+            return Some((dist[u], seen.path_to(u)));
+        }
+        let next = neighbors(&seen.states[u]);
+        for v in next {
+            let (v, new) = seen.intern(v);
+            if new {
+                seen.prev[v] = Some(u);
+                dist.push(dist[u] + 1);
+                q.push_back(v);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra over an implicit state space.
+/// `neighbors(&s)` yields `(next_state, cost)` pairs with non-negative costs;
+/// the cheapest state satisfying `is_goal` is returned with its cost and path.
+pub fn dijkstra_hash<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(i64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i64)>,
+{
+    let mut seen = Interner::new(start);
+    let mut dist = vec![0i64];
+    let mut pq = BinaryHeap::from([Reverse((0i64, 0usize))]);
+    while let Some(Reverse((d, u))) = pq.pop() {
+        if d > dist[u] {
+            continue; // stale entry
+        }
+        if is_goal(&seen.states[u]) {
+            return Some((d, seen.path_to(u)));
+        }
+        let next = neighbors(&seen.states[u]);
+        for (v, w) in next {
+            let (v, new) = seen.intern(v);
+            if new {
+                dist.push(i64::MAX);
+            }
+            if d + w < dist[v] {
+                dist[v] = d + w;
+                seen.prev[v] = Some(u);
+                pq.push(Reverse((d + w, v)));
+            }
+        }
+    }
+    None
+}
+
+/// Beam search for optimization puzzles where exact search blows up.
+/// Starting from `start`, expands the frontier `steps` times, keeping only the
+/// `width` highest-scoring children per level. Children for which
+/// `prune(&state, best_score_so_far)` returns true are dropped before ranking
+/// (use it for upper-bound cuts). Returns the best-scoring state seen.
+///
+/// Ties are broken by generation order (a stable sort), so results are
+/// deterministic for a deterministic `expand`. Not exact: a too-narrow beam
+/// can miss the optimum. A `width` of 0 keeps no children, so `start` is
+/// returned without calling `expand`.
+pub fn beam_search<S, I>(
+    start: S,
+    mut expand: impl FnMut(&S) -> I,
+    score: impl Fn(&S) -> i64,
+    width: usize,
+    steps: usize,
+    mut prune: impl FnMut(&S, i64) -> bool,
+) -> S
+where
+    S: Clone,
+    I: IntoIterator<Item = S>,
+{
+    if width == 0 {
+        return start;
+    }
+    let mut best_score = score(&start);
+    let mut best = start.clone();
+    let mut beam = vec![start];
+    for _ in 0..steps {
+        let mut next: Vec<(i64, S)> = Vec::new();
+        for s in &beam {
+            for c in expand(s) {
+                if !prune(&c, best_score) {
+                    next.push((score(&c), c));
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        next.sort_by_key(|&(sc, _)| Reverse(sc));
+        next.truncate(width);
+        if next[0].0 > best_score {
+            best_score = next[0].0;
+            best = next[0].1.clone();
+        }
+        beam = next.into_iter().map(|(_, s)| s).collect();
+    }
+    best
+}
+
+/// A* over an implicit state space, built on `IndexedMinHeap`.
+/// `successors(&s)` yields `(next_state, cost)` pairs with non-negative costs
+/// and `heuristic(&s)` must never overestimate the remaining cost. Returns the
+/// cost and path to the first goal state popped, like `dijkstra_hash` but
+/// guided by the heuristic.
+pub fn astar<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> i64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(i64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, i64)>,
+{
+    let mut pq = IndexedMinHeap::with_items(0);
+    let h0 = heuristic(&start);
+    let mut seen = Interner::new(start);
+    let mut g = vec![0i64];
+    pq.set(0, (h0, h0)); // (f, h): prefer deeper states on f ties
+    while let Some((u, _)) = pq.pop_min() {
+        if is_goal(&seen.states[u]) {
+            return Some((g[u], seen.path_to(u)));
+        }
+        let next = successors(&seen.states[u]);
+        for (v, w) in next {
+            let (v, new) = seen.intern(v);
+            if new {
+                g.push(i64::MAX);
+            }
+            if g[u] + w < g[v] {
+                g[v] = g[u] + w;
+                seen.prev[v] = Some(u);
+                let h = heuristic(&seen.states[v]);
+                pq.set(v, (g[v] + h, h));
+            }
+        }
+    }
+    None
+}
+
+/// Which cells count as adjacent in the grid searches.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left, right.
+    Four,
+    /// The four orthogonal moves plus diagonals.
+    Eight,
+}
+
+fn grid_neighbors<T>(grid: &DenseGrid2D<T>, x: usize, y: usize, conn: Connectivity) -> Vec<Point> {
+    match conn {
+        Connectivity::Four => grid.neighbors4(x, y),
+        Connectivity::Eight => grid.neighbors8(x, y),
+    }
+}
+
+/// BFS over the cells of `grid` from `start = (x, y)`.
+/// Only cells whose value satisfies `passable` are entered (the start cell is
+/// always used). Returns a same-sized grid of step counts, -1 for unreachable.
+pub fn grid_bfs<T>(
+    grid: &DenseGrid2D<T>,
+    start: (usize, usize),
+    conn: Connectivity,
+    passable: impl Fn(&T) -> bool,
+) -> DenseGrid2D<i64> {
+    let mut dist = DenseGrid2D::new(grid.width(), grid.height(), -1);
+    let mut q = VecDeque::new();
+    *dist.get_mut(start.0, start.1) = 0;
+    q.push_back(start);
+    while let Some((x, y)) = q.pop_front() {
+        let d = *dist.get(x, y);
+        for p in grid_neighbors(grid, x, y, conn) {
+            let (nx, ny) = (p.x as usize, p.y as usize);
+            if *dist.get(nx, ny) == -1 && passable(grid.get(nx, ny)) {
+                *dist.get_mut(nx, ny) = d + 1;
+                q.push_back((nx, ny));
+            }
+        }
+    }
+    dist
+}
+
+/// Dijkstra over the cells of `grid` from `start = (x, y)`.
+/// `cost(&cell)` is the price of stepping onto a cell (`None` = wall, costs
+/// must be non-negative). Returns a same-sized grid of distances, i64::MAX
+/// for unreachable.
+pub fn grid_dijkstra<T>(
+    grid: &DenseGrid2D<T>,
+    start: (usize, usize),
+    conn: Connectivity,
+    cost: impl Fn(&T) -> Option<i64>,
+) -> DenseGrid2D<i64> {
+    let mut dist = DenseGrid2D::new(grid.width(), grid.height(), i64::MAX);
+    let mut pq = IndexedMinHeap::with_items(grid.width() * grid.height());
+    *dist.get_mut(start.0, start.1) = 0;
+    pq.set(grid.idx(start.0, start.1), 0);
+    while let Some((i, d)) = pq.pop_min() {
+        let (x, y) = (i % grid.width(), i / grid.width());
+        for p in grid_neighbors(grid, x, y, conn) {
+            let (nx, ny) = (p.x as usize, p.y as usize);
+            if let Some(w) = cost(grid.get(nx, ny)) {
+                if d + w < *dist.get(nx, ny) {
+                    *dist.get_mut(nx, ny) = d + w;
+                    pq.set(grid.idx(nx, ny), d + w);
+                }
+            }
+        }
+    }
+    dist
 }
 
 #[cfg(test)]
@@ -158,4 +681,186 @@ mod tests {
         let adj2 = vec![vec![], vec![], vec![]];
         assert!(astar_indexed(n2, &adj2, 0, 2, &h).is_none());
     }
+    #[test]
+    fn bfs01_matches_dijkstra() {
+        // 0 -0-> 1 -1-> 2, 0 -1-> 2 -0-> 3, 1 -1-> 3; node 4 unreachable
+        let n = 5;
+        let mut adj = vec![vec![]; n];
+        adj[0] = vec![(1, 0), (2, 1)];
+        adj[1] = vec![(2, 1), (3, 1)];
+        adj[2] = vec![(3, 0)];
+        let dist = bfs01_indexed(n, &adj, 0);
+        assert_eq!(dist, vec![0, 0, 1, 1, i64::MAX]);
+        assert_eq!(dist, dijkstra_indexed(n, &adj, 0).0);
+    }
+    #[test]
+    fn bellman_ford_negative_edges_and_cycles() {
+        let edges = vec![(0, 1, 4), (0, 2, 5), (2, 1, -3), (1, 3, 2)];
+        assert_eq!(bellman_ford(5, &edges, 0), Ok(vec![0, 2, 5, 4, i64::MAX]));
+        // 1 -> 2 -> 3 -> 1 sums to -1
+        let edges = vec![(0, 1, 1), (1, 2, 1), (2, 3, -4), (3, 1, 2), (3, 4, 1)];
+        let mut cycle = bellman_ford(5, &edges, 0).unwrap_err();
+        let k = cycle.iter().position(|&u| u == 1).unwrap();
+        cycle.rotate_left(k);
+        assert_eq!(cycle, vec![1, 2, 3]);
+        // An unreachable negative cycle does not matter.
+        assert!(bellman_ford(3, &[(1, 2, -1), (2, 1, -1)], 0).is_ok());
+    }
+    #[test]
+    fn hashed_state_searches() {
+        // Reach 10 from 1 using +1 (cost 1) or *2 (cost 3).
+        let steps = |&x: &u32| {
+            [(x + 1, 1), (x * 2, 3)]
+                .into_iter()
+                .filter(|&(y, _)| y <= 20)
+        };
+        let (cost, path) = dijkstra_hash(1u32, steps, |&x| x == 10).unwrap();
+        assert_eq!(cost, 7); // four +1 steps to 5, then double
+        assert_eq!(path.first(), Some(&1));
+        assert_eq!(path.last(), Some(&10));
+        let (len, path) = bfs_hash(1u32, |&x| steps(&x).map(|(y, _)| y), |&x| x == 10).unwrap();
+        assert_eq!(len, 4); // 1 -> 2 -> 4 -> 5 -> 10
+        assert_eq!(path, vec![1, 2, 4, 5, 10]);
+        assert!(bfs_hash(1u32, |&x| steps(&x).map(|(y, _)| y), |&x| x == 0).is_none());
+    }
+    #[test]
+    fn prev_links_rebuild_paths() {
+        let adj = vec![vec![1, 2], vec![3], vec![3], vec![4], vec![]];
+        let (dist, prev) = bfs_with_prev(5, &adj, 0);
+        assert_eq!(dist, bfs_distances(5, &adj, 0));
+        assert_eq!(reconstruct_path(&prev, 4), vec![0, 1, 3, 4]);
+        assert_eq!(reconstruct_path(&prev, 0), vec![0]);
+        let adj_w = vec![vec![(1, 5), (2, 1)], vec![(3, 1)], vec![(1, 1)], vec![]];
+        let (_, prev) = dijkstra_indexed(4, &adj_w, 0);
+        assert_eq!(reconstruct_path(&prev, 3), vec![0, 2, 1, 3]);
+    }
+    #[test]
+    fn grid_searches() {
+        let rows = ["S.#", ".##", "..."];
+        let mut g = DenseGrid2D::new(3, 3, '.');
+        for (y, r) in rows.iter().enumerate() {
+            for (x, c) in r.chars().enumerate() {
+                *g.get_mut(x, y) = c;
+            }
+        }
+        let d = grid_bfs(&g, (0, 0), Connectivity::Four, |&c| c != '#');
+        assert_eq!(*d.get(2, 2), 4);
+        assert_eq!(*d.get(2, 0), -1);
+        let d8 = grid_bfs(&g, (0, 0), Connectivity::Eight, |&c| c != '#');
+        assert_eq!(*d8.get(2, 2), 3);
+        // Digits as entry costs; walls block.
+        let mut w = DenseGrid2D::new(3, 2, 1i64);
+        *w.get_mut(1, 0) = 9;
+        let dw = grid_dijkstra(&w, (0, 0), Connectivity::Four, |&c| Some(c));
+        assert_eq!(*dw.get(2, 0), 4); // down, right, right, up
+        let blocked = grid_dijkstra(&w, (0, 0), Connectivity::Four, |&c| (c == 1).then_some(c));
+        assert_eq!(*blocked.get(1, 0), i64::MAX);
+    }
+    #[test]
+    fn dfs_visitor_events() {
+        #[derive(Default)]
+        struct Rec {
+            finish: Vec<usize>,
+            back: Vec<(usize, usize)>,
+            size: Vec<usize>,
+        }
+        impl DfsVisitor for Rec {
+            fn discover(&mut self, _u: usize, _p: Option<usize>) {}
+            fn finish(&mut self, u: usize, p: Option<usize>) {
+                self.finish.push(u);
+                self.size[u] += 1;
+                if let Some(p) = p {
+                    self.size[p] += self.size[u];
+                }
+            }
+            fn back_edge(&mut self, u: usize, v: usize) {
+                self.back.push((u, v));
+            }
+        }
+        // Tree 0 -> {1, 2}, 1 -> 3; separate node 4.
+        let adj = vec![vec![1, 2], vec![3], vec![], vec![], vec![]];
+        let mut r = Rec {
+            size: vec![0; 5],
+            ..Default::default()
+        };
+        dfs_visit(5, &adj, 0..5, &mut r);
+        assert_eq!(r.finish, vec![3, 1, 2, 0, 4]);
+        assert_eq!(r.size, vec![4, 2, 1, 1, 1]);
+        assert!(r.back.is_empty());
+        // Adding 3 -> 0 closes a cycle.
+        let mut adj = adj;
+        adj[3].push(0);
+        let mut r = Rec {
+            size: vec![0; 5],
+            ..Default::default()
+        };
+        dfs_visit(5, &adj, [0], &mut r);
+        assert_eq!(r.back, vec![(3, 0)]);
+    }
+    #[test]
+    fn beam_search_keeps_best() {
+        // State (value, rate): each step either cashes in (value += rate) or
+        // invests (rate += 1). Over 4 steps investing first pays off: 6 vs 4.
+        let expand = |&(v, r): &(i64, i64)| [(v + r, r), (v, r + 1)];
+        let score = |&(v, _): &(i64, i64)| v;
+        let wide = beam_search((0, 1), expand, score, 8, 4, |_, _| false);
+        assert_eq!(wide.0, 6);
+        let greedy = beam_search((0, 1), expand, score, 1, 4, |_, _| false);
+        assert_eq!(greedy, (4, 1));
+        // Pruning every investment leaves only the greedy line.
+        let capped = beam_search((0, 1), expand, score, 8, 4, |&(_, r), _| r > 1);
+        assert_eq!(capped, (4, 1));
+        // An empty beam never leaves the start.
+        let empty = beam_search((0, 1), expand, score, 0, 4, |_, _| false);
+        assert_eq!(empty, (0, 1));
+    }
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn delta_stepping_matches_dijkstra() {
+        let n = 200;
+        let mut seed = 0x2545_f491_u64;
+        let mut adj = vec![vec![]; n];
+        for edges in adj.iter_mut() {
+            for _ in 0..4 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                edges.push(((seed % n as u64) as usize, (seed >> 32) as i64 % 20));
+            }
+        }
+        let want = dijkstra_indexed(n, &adj, 0).0;
+        for delta in [1, 5, 50] {
+            assert_eq!(delta_stepping(n, &adj, 0, delta), want);
+        }
+    }
+    #[test]
+    fn bucket_dijkstra_matches_heap() {
+        let mut adj = vec![vec![]; 6];
+        adj[0] = vec![(1, 7), (2, 9), (5, 14)];
+        adj[1] = vec![(2, 0), (3, 15)];
+        adj[2] = vec![(3, 11), (5, 2)];
+        adj[3] = vec![(4, 6)];
+        adj[5] = vec![(4, 9)];
+        let (dist, prev) = dijkstra_bucket(6, &adj, 0, 15);
+        assert_eq!(dist, dijkstra_indexed(6, &adj, 0).0);
+        assert_eq!(dist, vec![0, 7, 7, 18, 18, 9]);
+        assert_eq!(reconstruct_path(&prev, 5), vec![0, 1, 2, 5]);
+    }
+    #[test]
+    fn closure_astar_on_open_plane() {
+        // Walk on an unbounded plane around a wall at x == 2 (|y| <= 3).
+        let succ = |&(x, y): &(i64, i64)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(nx, ny)| !(nx == 2 && ny.abs() <= 3))
+                .map(|p| (p, 1))
+        };
+        let goal = (4, 0);
+        let manhattan = |&(x, y): &(i64, i64)| (x - goal.0).abs() + (y - goal.1).abs();
+        let (cost, path) = astar((0, 0), succ, manhattan, |&p| p == goal).unwrap();
+        assert_eq!(cost, 12); // up 4, across 4, down 4
+        assert_eq!(path.len(), 13);
+        let dij = dijkstra_hash((0, 0), succ, |&p| p == goal).unwrap();
+        assert_eq!(dij.0, cost);
+    }
 }
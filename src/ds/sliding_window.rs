@@ -0,0 +1,114 @@
+//! Sliding-window aggregation over any associative operation (two-stack queue).
+//!
+//! `SlidingWindow` behaves like a FIFO queue whose `query()` returns the
+//! fold of every element currently inside, using a caller-supplied
+//! associative `op` (sum, min, max, gcd, matrix product, ...). The operation
+//! does not need an identity or to be commutative: elements are always
+//! combined oldest-to-newest.
+//!
+//! Design
+//! - `back`: newly pushed elements, each paired with the fold of the back
+//!   stack from its bottom up to itself.
+//! - `front`: older elements (oldest on top), each paired with the fold from
+//!   itself down to the bottom of the front stack.
+//! - When `front` runs dry on `pop`, `back` is flipped onto it, so every
+//!   element moves at most once: push/pop/query are amortized O(1).
+
+#[derive(Clone, Debug)]
+pub struct SlidingWindow<T: Clone, F: Fn(&T, &T) -> T> {
+    op: F,
+    front: Vec<(T, T)>, // (value, fold of value..=bottom), oldest on top
+    back: Vec<(T, T)>,  // (value, fold of bottom..=value), newest on top
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SlidingWindow<T, F> {
+    /// Create an empty window aggregated with the associative `op`.
+    pub fn new(op: F) -> Self {
+        Self {
+            op,
+            front: Vec::new(),
+            back: Vec::new(),
+        }
+    }
+    /// Add `x` as the newest element of the window.
+    pub fn push(&mut self, x: T) {
+        let agg = match self.back.last() {
+            Some((_, a)) => (self.op)(a, &x),
+            None => x.clone(),
+        };
+        self.back.push((x, agg));
+    }
+    /// Remove and return the oldest element of the window.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some((x, _)) = self.back.pop() {
+                let agg = match self.front.last() {
+                    Some((_, a)) => (self.op)(&x, a),
+                    None => x.clone(),
+                };
+                self.front.push((x, agg));
+            }
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+    /// Fold of all elements in the window, oldest to newest (None if empty).
+    pub fn query(&self) -> Option<T> {
+        match (self.front.last(), self.back.last()) {
+            (Some((_, f)), Some((_, b))) => Some((self.op)(f, b)),
+            (Some((_, f)), None) => Some(f.clone()),
+            (None, Some((_, b))) => Some(b.clone()),
+            (None, None) => None,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlidingWindow;
+
+    #[test]
+    fn window_sum_and_min() {
+        let a = [4, 2, 12, 3, 1, 5];
+        let k = 3;
+        let mut sum = SlidingWindow::new(|x: &i64, y: &i64| x + y);
+        let mut min = SlidingWindow::new(|x: &i64, y: &i64| *x.min(y));
+        let (mut sums, mut mins) = (Vec::new(), Vec::new());
+        for &x in &a {
+            sum.push(x);
+            min.push(x);
+            if sum.len() > k {
+                sum.pop();
+                min.pop();
+            }
+            if sum.len() == k {
+                sums.push(sum.query().unwrap());
+                mins.push(min.query().unwrap());
+            }
+        }
+        assert_eq!(sums, vec![18, 17, 16, 9]);
+        assert_eq!(mins, vec![2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn non_commutative_order_preserved() {
+        let mut w = SlidingWindow::new(|a: &String, b: &String| format!("{a}{b}"));
+        assert!(w.query().is_none() && w.is_empty());
+        for s in ["a", "b", "c"] {
+            w.push(s.to_string());
+        }
+        assert_eq!(w.pop().as_deref(), Some("a"));
+        w.push("d".to_string());
+        assert_eq!(w.query().as_deref(), Some("bcd"));
+        assert_eq!(w.pop().as_deref(), Some("b"));
+        assert_eq!(w.pop().as_deref(), Some("c"));
+        assert_eq!(w.query().as_deref(), Some("d"));
+        assert_eq!(w.pop().as_deref(), Some("d"));
+        assert_eq!(w.pop(), None);
+    }
+}
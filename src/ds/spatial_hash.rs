@@ -0,0 +1,199 @@
+//! Uniform-grid spatial hash for radius queries over 2D points, and
+//! `SpatialHash3` for 3D points.
+//!
+//! Points are bucketed by the cell `(x.div_euclid(s), y.div_euclid(s))` for
+//! a fixed cell size `s`. A query "within distance d of p" only scans the
+//! cells overlapping the square of side 2d around `p`, so with a cell size
+//! close to the typical query radius each query touches a handful of buckets.
+//! Simpler than a KD-tree and usually faster on evenly spread AoC inputs.
+
+use crate::ds::coords::{Point, Point3};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug)]
+pub struct SpatialHash<T> {
+    cell: i64,
+    buckets: HashMap<(i64, i64), Vec<(Point, T)>>,
+    len: usize,
+}
+
+impl<T> SpatialHash<T> {
+    /// Create an empty hash with square cells of side `cell` (panics if `cell <= 0`).
+    pub fn new(cell: i64) -> Self {
+        assert!(cell > 0, "cell size must be positive");
+        Self {
+            cell,
+            buckets: HashMap::new(),
+            len: 0,
+        }
+    }
+    fn key(&self, p: Point) -> (i64, i64) {
+        (p.x.div_euclid(self.cell), p.y.div_euclid(self.cell))
+    }
+    /// Store `v` at `p` (several values may share a point).
+    pub fn insert(&mut self, p: Point, v: T) {
+        let k = self.key(p);
+        self.buckets.entry(k).or_default().push((p, v));
+        self.len += 1;
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Every stored `(point, value)` whose Euclidean distance to `p` is at most `d`.
+    pub fn within(&self, p: Point, d: i64) -> Vec<(Point, &T)> {
+        let d2 = d * d;
+        self.scan(p, d, |q| {
+            let (dx, dy) = (q.x - p.x, q.y - p.y);
+            dx * dx + dy * dy <= d2
+        })
+    }
+    /// Every stored `(point, value)` within Manhattan distance `d` of `p`.
+    pub fn within_manhattan(&self, p: Point, d: i64) -> Vec<(Point, &T)> {
+        self.scan(p, d, |q| (q.x - p.x).abs() + (q.y - p.y).abs() <= d)
+    }
+    /// Visit the cells overlapping the square `p ± d` and keep matches.
+    fn scan(&self, p: Point, d: i64, keep: impl Fn(Point) -> bool) -> Vec<(Point, &T)> {
+        let lo = self.key(Point::new(p.x - d, p.y - d));
+        let hi = self.key(Point::new(p.x + d, p.y + d));
+        let mut out = Vec::new();
+        for cx in lo.0..=hi.0 {
+            for cy in lo.1..=hi.1 {
+                if let Some(b) = self.buckets.get(&(cx, cy)) {
+                    out.extend(b.iter().filter(|(q, _)| keep(*q)).map(|(q, v)| (*q, v)));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// [`SpatialHash`] over 3D points: cubic cells, Euclidean radius queries.
+#[derive(Clone, Debug)]
+pub struct SpatialHash3<T> {
+    cell: i64,
+    buckets: HashMap<(i64, i64, i64), Vec<(Point3, T)>>,
+    len: usize,
+}
+
+impl<T> SpatialHash3<T> {
+    /// Create an empty hash with cubic cells of side `cell` (panics if `cell <= 0`).
+    pub fn new(cell: i64) -> Self {
+        assert!(cell > 0, "cell size must be positive");
+        Self {
+            cell,
+            buckets: HashMap::new(),
+            len: 0,
+        }
+    }
+    fn key(&self, p: Point3) -> (i64, i64, i64) {
+        (
+            p.x.div_euclid(self.cell),
+            p.y.div_euclid(self.cell),
+            p.z.div_euclid(self.cell),
+        )
+    }
+    /// Store `v` at `p` (several values may share a point).
+    pub fn insert(&mut self, p: Point3, v: T) {
+        let k = self.key(p);
+        self.buckets.entry(k).or_default().push((p, v));
+        self.len += 1;
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Every stored `(point, value)` whose Euclidean distance to `p` is at most `d`.
+    pub fn within(&self, p: Point3, d: i64) -> Vec<(Point3, &T)> {
+        let d2 = d * d;
+        let lo = self.key(Point3::new(p.x - d, p.y - d, p.z - d));
+        let hi = self.key(Point3::new(p.x + d, p.y + d, p.z + d));
+        let mut out = Vec::new();
+        for cx in lo.0..=hi.0 {
+            for cy in lo.1..=hi.1 {
+                for cz in lo.2..=hi.2 {
+                    let Some(b) = self.buckets.get(&(cx, cy, cz)) else {
+                        continue;
+                    };
+                    out.extend(
+                        b.iter()
+                            .filter(|(q, _)| {
+                                let (dx, dy, dz) = (q.x - p.x, q.y - p.y, q.z - p.z);
+                                dx * dx + dy * dy + dz * dz <= d2
+                            })
+                            .map(|(q, v)| (*q, v)),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SpatialHash, SpatialHash3};
+    use crate::ds::coords::{Point, Point3};
+
+    #[test]
+    fn radius_queries_match_brute_force() {
+        let mut h = SpatialHash::new(4);
+        let mut pts = Vec::new();
+        let mut seed = 12345u64;
+        for i in 0..300 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let p = Point::new((seed >> 33) as i64 % 61 - 30, (seed >> 13) as i64 % 61 - 30);
+            h.insert(p, i);
+            pts.push((p, i));
+        }
+        assert_eq!(h.len(), 300);
+        let q = Point::new(-3, 5);
+        for d in [0, 3, 7, 20] {
+            let mut got: Vec<i32> = h.within(q, d).into_iter().map(|(_, &v)| v).collect();
+            got.sort_unstable();
+            let want: Vec<i32> = pts
+                .iter()
+                .filter(|(p, _)| (p.x - q.x).pow(2) + (p.y - q.y).pow(2) <= d * d)
+                .map(|&(_, v)| v)
+                .collect();
+            assert_eq!(got, want);
+        }
+        let m = h.within_manhattan(q, 5);
+        assert!(m
+            .iter()
+            .all(|(p, _)| (p.x - q.x).abs() + (p.y - q.y).abs() <= 5));
+    }
+
+    #[test]
+    fn radius_queries_3d() {
+        let mut h = SpatialHash3::new(5);
+        let mut pts = Vec::new();
+        for x in -6..=6 {
+            for y in -6..=6 {
+                for z in [-4, 0, 9] {
+                    let p = Point3::new(x, y, z);
+                    h.insert(p, pts.len());
+                    pts.push(p);
+                }
+            }
+        }
+        let q = Point3::new(1, -2, 0);
+        for d in [0, 4, 11] {
+            let mut got: Vec<usize> = h.within(q, d).into_iter().map(|(_, &i)| i).collect();
+            got.sort_unstable();
+            let want: Vec<usize> = (0..pts.len())
+                .filter(|&i| {
+                    let p = pts[i];
+                    (p.x - q.x).pow(2) + (p.y - q.y).pow(2) + (p.z - q.z).pow(2) <= d * d
+                })
+                .collect();
+            assert_eq!(got, want);
+        }
+    }
+}
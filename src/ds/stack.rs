@@ -1,4 +1,7 @@
 //! Stack: LIFO wrapper over Vec<T> for parsing and DFS tasks.
+//!
+//! `MinStack`/`MaxStack` additionally track the running minimum/maximum in
+//! O(1) by storing, next to each value, the extreme of everything beneath it.
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Stack<T> {
@@ -18,6 +21,102 @@ impl<T> Stack<T> {
     pub fn peek(&self) -> Option<&T> {
         self.v.last()
     }
+    /// Mutable reference to the top element.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.v.last_mut()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.v.len()
+    }
+    /// Iterate from the top of the stack to the bottom.
+    pub fn iter(&self) -> std::iter::Rev<std::slice::Iter<'_, T>> {
+        self.v.iter().rev()
+    }
+    /// Consume and return the inner `Vec<T>` (bottom first, top last).
+    pub fn into_vec(self) -> Vec<T> {
+        self.v
+    }
+}
+
+impl<T> From<Vec<T>> for Stack<T> {
+    /// The last element of the vector becomes the top of the stack.
+    fn from(v: Vec<T>) -> Self {
+        Self { v }
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    /// Push each item in order; the last item ends up on top.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, it: I) {
+        self.v.extend(it)
+    }
+}
+
+/// Stack that reports its minimum element in O(1).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MinStack<T: Ord + Clone> {
+    v: Vec<(T, T)>, // (value, min of stack up to and including value)
+}
+
+impl<T: Ord + Clone> MinStack<T> {
+    pub fn new() -> Self {
+        Self { v: Vec::new() }
+    }
+    pub fn push(&mut self, x: T) {
+        let m = match self.v.last() {
+            Some((_, m)) if *m < x => m.clone(),
+            _ => x.clone(),
+        };
+        self.v.push((x, m));
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.v.pop().map(|(x, _)| x)
+    }
+    pub fn peek(&self) -> Option<&T> {
+        self.v.last().map(|(x, _)| x)
+    }
+    /// Smallest element currently on the stack.
+    pub fn min(&self) -> Option<&T> {
+        self.v.last().map(|(_, m)| m)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.v.len()
+    }
+}
+
+/// Stack that reports its maximum element in O(1).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaxStack<T: Ord + Clone> {
+    v: Vec<(T, T)>, // (value, max of stack up to and including value)
+}
+
+impl<T: Ord + Clone> MaxStack<T> {
+    pub fn new() -> Self {
+        Self { v: Vec::new() }
+    }
+    pub fn push(&mut self, x: T) {
+        let m = match self.v.last() {
+            Some((_, m)) if *m > x => m.clone(),
+            _ => x.clone(),
+        };
+        self.v.push((x, m));
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        self.v.pop().map(|(x, _)| x)
+    }
+    pub fn peek(&self) -> Option<&T> {
+        self.v.last().map(|(x, _)| x)
+    }
+    /// Largest element currently on the stack.
+    pub fn max(&self) -> Option<&T> {
+        self.v.last().map(|(_, m)| m)
+    }
     pub fn is_empty(&self) -> bool {
         self.v.is_empty()
     }
@@ -28,7 +127,7 @@ impl<T> Stack<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::Stack;
+    use super::{MaxStack, MinStack, Stack};
     #[test]
     fn basic() {
         let mut s = Stack::new();
@@ -47,4 +146,49 @@ mod tests {
         let s: Stack<i32> = Stack::new();
         assert!(s.peek().is_none());
     }
+
+    #[test]
+    fn peek_mut_iter_and_vec_roundtrip() {
+        let mut s: Stack<i32> = vec![1, 2].into();
+        s.extend([3, 4]);
+        if let Some(top) = s.peek_mut() {
+            *top *= 10;
+        }
+        assert_eq!(s.iter().copied().collect::<Vec<_>>(), vec![40, 3, 2, 1]);
+        assert_eq!(s.pop(), Some(40));
+        assert_eq!(s.into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn min_stack_tracks_minimum() {
+        let mut s = MinStack::new();
+        assert!(s.min().is_none());
+        for x in [5, 3, 7, 3, 1] {
+            s.push(x);
+        }
+        assert_eq!(s.min(), Some(&1));
+        assert_eq!(s.pop(), Some(1));
+        assert_eq!(s.min(), Some(&3));
+        s.pop();
+        assert_eq!(s.min(), Some(&3)); // duplicate 3 still below
+        s.pop();
+        s.pop();
+        assert_eq!(s.peek(), Some(&5));
+        assert_eq!(s.min(), Some(&5));
+        assert_eq!(s.len(), 1);
+    }
+
+    #[test]
+    fn max_stack_tracks_maximum() {
+        let mut s = MaxStack::new();
+        for x in [2, 9, 4] {
+            s.push(x);
+        }
+        assert_eq!(s.max(), Some(&9));
+        s.pop();
+        s.pop();
+        assert_eq!(s.max(), Some(&2));
+        s.pop();
+        assert!(s.is_empty() && s.max().is_none());
+    }
 }
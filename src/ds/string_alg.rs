@@ -1,4 +1,16 @@
-//! String algorithms: KMP, Z-function, and a simple rolling hash.
+//! String algorithms: KMP, Z-function, and rolling hashes.
+//!
+//! `RollingHash` uses a single (base, mod) pair and is fine for short inputs.
+//! `RollingHash2` carries two independent pairs and only reports equality
+//! when both agree, which makes accidental collisions negligible even on
+//! long strings. Its bases are randomized per process by default.
+//!
+//! Like the rest of the module, the search/hash helpers work on bytes and
+//! are intended for ASCII input.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
 
 /// KMP prefix (failure) function.
 pub fn kmp_prefix(s: &[u8]) -> Vec<usize> {
@@ -41,6 +53,55 @@ pub fn kmp_search(t: &str, p: &str) -> Vec<usize> {
     res
 }
 
+/// Boyer–Moore–Horspool search: same contract as `kmp_search` (all, possibly
+/// overlapping, start indices). Skips ahead using the last byte of each
+/// window, so it tends to beat KMP on long texts with large alphabets;
+/// worst case is O(|t|·|p|).
+pub fn fast_search(t: &str, p: &str) -> Vec<usize> {
+    let (t, p) = (t.as_bytes(), p.as_bytes());
+    let m = p.len();
+    if m == 0 {
+        return (0..=t.len()).collect();
+    }
+    let mut shift = [m; 256];
+    for (i, &b) in p[..m - 1].iter().enumerate() {
+        shift[b as usize] = m - 1 - i;
+    }
+    let mut res = Vec::new();
+    let mut i = 0;
+    while i + m <= t.len() {
+        let last = t[i + m - 1];
+        if last == p[m - 1] && t[i..i + m - 1] == p[..m - 1] {
+            res.push(i);
+        }
+        i += shift[last as usize];
+    }
+    res
+}
+
+/// Smallest period `p` such that `s[i] == s[i + p]` for every valid `i`
+/// (computed from the prefix function). `p` need not divide `s.len()`;
+/// returns 0 for the empty string.
+pub fn smallest_period(s: &str) -> usize {
+    let b = s.as_bytes();
+    match kmp_prefix(b).last() {
+        Some(&border) => b.len() - border,
+        None => 0,
+    }
+}
+
+/// If `s` is some block repeated two or more times, return `(block_len, reps)`
+/// for the shortest such block (e.g. "abababab" -> (2, 4)).
+pub fn is_repetition(s: &str) -> Option<(usize, usize)> {
+    let n = s.len();
+    let p = smallest_period(s);
+    if p > 0 && p < n && n.is_multiple_of(p) {
+        Some((p, n / p))
+    } else {
+        None
+    }
+}
+
 /// Z-function: z[i] = length of longest substring starting at i that is also a prefix.
 pub fn z_function(s: &str) -> Vec<usize> {
     let s = s.as_bytes();
@@ -87,6 +148,203 @@ impl RollingHash {
     }
 }
 
+/// Hash of a string segment under both moduli of a `RollingHash2`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DoubleHash {
+    pub h1: u64,
+    pub h2: u64,
+    pub len: usize,
+}
+
+/// Two independent polynomial rolling hashes evaluated side by side.
+#[derive(Clone, Debug)]
+pub struct RollingHash2 {
+    params: [(u64, u64); 2], // (base, mod)
+    pref: [Vec<u64>; 2],
+    pow: [Vec<u64>; 2],
+}
+
+impl RollingHash2 {
+    const MOD1: u64 = 1_000_000_007;
+    const MOD2: u64 = 998_244_353;
+
+    /// Build with random bases (fresh per process) over two fixed primes.
+    pub fn new(s: &str) -> Self {
+        let mut seed = RandomState::new().build_hasher();
+        seed.write_usize(s.len());
+        let r = seed.finish();
+        let b1 = 256 + (r & 0xFFFF_FFFF) % (Self::MOD1 - 512);
+        let b2 = 256 + (r >> 32) % (Self::MOD2 - 512);
+        Self::with_params(s, (b1, Self::MOD1), (b2, Self::MOD2))
+    }
+    /// Build with explicit `(base, mod)` pairs (deterministic). Bases are reduced
+    /// modulo their modulus.
+    ///
+    /// # Panics
+    /// If a modulus is 0 or not below 2^32 (products must fit in u64).
+    pub fn with_params(s: &str, p1: (u64, u64), p2: (u64, u64)) -> Self {
+        let n = s.len();
+        let params = [p1, p2].map(|(base, m)| {
+            assert!(
+                m > 0 && m < 1 << 32,
+                "RollingHash2 modulus {m} must be in 1..2^32"
+            );
+            (base % m, m)
+        });
+        let mut pref = [vec![0; n + 1], vec![0; n + 1]];
+        let mut pow = [vec![1; n + 1], vec![1; n + 1]];
+        for (k, &(base, m)) in params.iter().enumerate() {
+            for (i, &b) in s.as_bytes().iter().enumerate() {
+                pref[k][i + 1] = (pref[k][i] * base + b as u64) % m;
+                pow[k][i + 1] = (pow[k][i] * base) % m;
+            }
+        }
+        Self { params, pref, pow }
+    }
+    /// Hash another string with the same bases, so hashes are comparable.
+    pub fn same_params(&self, s: &str) -> Self {
+        Self::with_params(s, self.params[0], self.params[1])
+    }
+    /// The `(base, mod)` pairs in use.
+    pub fn params(&self) -> [(u64, u64); 2] {
+        self.params
+    }
+    /// Hash of substring s[l..r) (0-based, exclusive r).
+    pub fn hash(&self, l: usize, r: usize) -> DoubleHash {
+        let one = |k: usize| {
+            let m = self.params[k].1;
+            (self.pref[k][r] + m - (self.pref[k][l] * self.pow[k][r - l]) % m) % m
+        };
+        DoubleHash {
+            h1: one(0),
+            h2: one(1),
+            len: r - l,
+        }
+    }
+    /// True if s[a..a+len) == s[b..b+len) (up to a negligible collision chance).
+    pub fn substr_eq(&self, a: usize, b: usize, len: usize) -> bool {
+        self.hash(a, a + len) == self.hash(b, b + len)
+    }
+    /// Hash of the concatenation `x` followed by `y`.
+    pub fn concat(&self, x: DoubleHash, y: DoubleHash) -> DoubleHash {
+        let join = |k: usize, hx: u64, hy: u64| {
+            let (base, m) = self.params[k];
+            let shift = match self.pow[k].get(y.len) {
+                Some(&p) => p,
+                None => mod_pow(base, y.len as u64, m),
+            };
+            (hx * shift + hy) % m
+        };
+        DoubleHash {
+            h1: join(0, x.h1, y.h1),
+            h2: join(1, x.h2, y.h2),
+            len: x.len + y.len,
+        }
+    }
+}
+
+/// Longest common subsequence of `a` and `b` (by chars): (length, one witness).
+/// O(|a|·|b|) time and memory.
+pub fn lcs(a: &str, b: &str) -> (usize, String) {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if a[i] == b[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+    let mut out = String::with_capacity(dp[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if a[i] == b[j] {
+            out.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    (dp[0][0], out)
+}
+
+/// Longest common substring of `a` and `b` (by chars, like [`lcs`]): (length,
+/// one witness taken from `a`). Binary search on the length with
+/// `RollingHash2`; O((|a|+|b|) log min(|a|,|b|)) expected.
+pub fn longest_common_substring(a: &str, b: &str) -> (usize, String) {
+    let ha = RollingHash2::new(a);
+    let hb = ha.same_params(b);
+    // Byte offset of every char boundary, so windows never split a char.
+    let bounds =
+        |s: &str| -> Vec<usize> { s.char_indices().map(|(i, _)| i).chain([s.len()]).collect() };
+    let (ba, bb) = (bounds(a), bounds(b));
+    let (na, nb) = (ba.len() - 1, bb.len() - 1);
+    // Start (in chars) in `a` of some common substring of `len` chars, if any.
+    let common_at = |len: usize| -> Option<usize> {
+        if len == 0 {
+            return Some(0);
+        }
+        let seen: HashMap<DoubleHash, usize> = (0..=na - len)
+            .map(|i| (ha.hash(ba[i], ba[i + len]), i))
+            .collect();
+        (0..=nb - len).find_map(|j| seen.get(&hb.hash(bb[j], bb[j + len])).copied())
+    };
+    let (mut lo, mut hi, mut at) = (0, na.min(nb), 0);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        match common_at(mid) {
+            Some(i) => {
+                lo = mid;
+                at = i;
+            }
+            None => hi = mid - 1,
+        }
+    }
+    (lo, a[ba[at]..ba[at + lo]].to_string())
+}
+
+/// Canonical 128-bit hash of a character grid, for cycle detection without
+/// cloning whole grids into map keys.
+///
+/// Uses two fixed polynomial hashes (same grid, same value, every run) and
+/// feeds a separator after each row so that different shapes do not collide
+/// trivially. Rows may have different lengths.
+pub fn grid_hash<R: AsRef<[char]>>(rows: &[R]) -> u128 {
+    const P: [(u64, u64); 2] = [(1_000_003, 1_000_000_007), (911_382_323, 998_244_353)];
+    let mut h = [0u64; 2];
+    let mut feed = |v: u64| {
+        for (hk, &(base, m)) in h.iter_mut().zip(P.iter()) {
+            *hk = (*hk * base + v) % m;
+        }
+    };
+    for row in rows {
+        for &c in row.as_ref() {
+            feed(c as u64 + 1);
+        }
+        feed(0);
+    }
+    ((h[0] as u128) << 64) | h[1] as u128
+}
+
+fn mod_pow(mut b: u64, mut e: u64, m: u64) -> u64 {
+    let mut r = 1 % m;
+    b %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            r = r * b % m;
+        }
+        b = b * b % m;
+        e >>= 1;
+    }
+    r
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +364,120 @@ mod tests {
         assert_eq!(h.hash(0, 3), h.hash(0, 3));
         assert_ne!(h.hash(0, 3), h.hash(1, 4));
     }
+
+    #[test]
+    fn double_hash_equality_and_concat() {
+        let s = "abcabcxabc";
+        let h = RollingHash2::new(s);
+        assert!(h.substr_eq(0, 3, 3));
+        assert!(h.substr_eq(0, 7, 3));
+        assert!(!h.substr_eq(0, 1, 3));
+        // "ab" + "cx" stitched together equals the hash of "abcx" from another string.
+        let stitched = h.concat(h.hash(3, 5), h.hash(5, 7));
+        let other = h.same_params("zabcx");
+        assert_eq!(stitched, other.hash(1, 5));
+        assert_eq!(stitched.len, 4);
+        // Concatenating beyond the precomputed power table still works.
+        let long = h.concat(h.hash(0, 10), h.hash(0, 10));
+        let doubled = h.same_params("abcabcxabcabcabcxabc");
+        assert_eq!(long, doubled.hash(0, 20));
+        let prefixed = h.concat(h.hash(0, 1), long);
+        assert_eq!(prefixed, h.same_params("aabcabcxabcabcabcxabc").hash(0, 21));
+    }
+
+    #[test]
+    fn double_hash_explicit_params() {
+        let a = RollingHash2::with_params("hello", (131, 1_000_000_007), (137, 998_244_353));
+        let b = RollingHash2::with_params("yellow", (131, 1_000_000_007), (137, 998_244_353));
+        assert_eq!(a.hash(1, 5), b.hash(1, 5));
+        assert_eq!(a.params()[0], (131, 1_000_000_007));
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        // A base at or above the modulus hashes like its residue.
+        let big = RollingHash2::with_params(
+            "hello",
+            (131 + (1 << 20) * 1_000_000_007, 1_000_000_007),
+            (137, 998_244_353),
+        );
+        assert_eq!(big.params()[0], (131, 1_000_000_007));
+        assert_eq!(big.hash(0, 5), a.hash(0, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be in 1..2^32")]
+    fn double_hash_rejects_wide_modulus() {
+        RollingHash2::with_params("ab", (3, 1 << 32), (5, 7));
+    }
+
+    #[test]
+    fn lcs_length_and_witness() {
+        let (n, w) = lcs("AGGTAB", "GXTXAYB");
+        assert_eq!(n, 4);
+        assert_eq!(w, "GTAB");
+        assert_eq!(lcs("", "abc"), (0, String::new()));
+        assert_eq!(lcs("abc", "abc").1, "abc");
+    }
+
+    #[test]
+    fn longest_common_substring_cases() {
+        assert_eq!(
+            longest_common_substring("xabcdey", "zzbcdezz"),
+            (4, "bcde".to_string())
+        );
+        assert_eq!(longest_common_substring("abc", "xyz"), (0, String::new()));
+        assert_eq!(longest_common_substring("", "xyz").0, 0);
+        assert_eq!(
+            longest_common_substring("aaaa", "aa"),
+            (2, "aa".to_string())
+        );
+        // Lengths are in chars, and 'é'/'ê' share only a lead byte.
+        assert_eq!(longest_common_substring("xéy", "zêw"), (0, String::new()));
+        assert_eq!(
+            longest_common_substring("naïve café", "a ïve!"),
+            (3, "ïve".to_string())
+        );
+    }
+
+    #[test]
+    fn periods_and_repetitions() {
+        assert_eq!(smallest_period("abcabcab"), 3);
+        assert_eq!(smallest_period("aaaa"), 1);
+        assert_eq!(smallest_period("abcd"), 4);
+        assert_eq!(smallest_period(""), 0);
+        assert_eq!(is_repetition("abababab"), Some((2, 4)));
+        assert_eq!(is_repetition("121212"), Some((2, 3)));
+        assert_eq!(is_repetition("1111"), Some((1, 4)));
+        assert_eq!(is_repetition("abcabcab"), None); // period 3 does not divide 8
+        assert_eq!(is_repetition("7"), None);
+        assert_eq!(is_repetition(""), None);
+    }
+
+    #[test]
+    fn horspool_matches_kmp() {
+        let cases = [
+            ("ababaabababa", "ababa"),
+            ("aaaaa", "aa"),
+            ("hello world", "world"),
+            ("abc", "abcd"),
+            ("abc", ""),
+            ("xyz", "q"),
+        ];
+        for (t, p) in cases {
+            assert_eq!(fast_search(t, p), kmp_search(t, p), "{t:?} / {p:?}");
+        }
+    }
+
+    #[test]
+    fn grid_hash_detects_repeats_and_shapes() {
+        let a = vec![vec!['#', '.'], vec!['.', 'O']];
+        let b = a.clone();
+        let mut c = a.clone();
+        c[1][1] = '.';
+        assert_eq!(grid_hash(&a), grid_hash(&b));
+        assert_ne!(grid_hash(&a), grid_hash(&c));
+        // Same cells, different shape.
+        let flat = vec![vec!['#', '.', '.', 'O']];
+        assert_ne!(grid_hash(&a), grid_hash(&flat));
+        let rows: Vec<&[char]> = a.iter().map(|r| r.as_slice()).collect();
+        assert_eq!(grid_hash(&rows), grid_hash(&a));
+    }
 }
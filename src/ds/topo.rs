@@ -1,53 +1,319 @@
 //! Topological sorting utilities (Kahn's algorithm) for dependency problems.
+//!
+//! `topo_sort` returns some valid order (unspecified among ties);
+//! `topo_sort_lex` always takes the smallest ready node next, which is what
+//! "do the alphabetically first available step" puzzles ask for.
+//!
+//! On a cyclic input both return `Err(cycle)`: the nodes of one offending
+//! cycle in edge order (`cycle[i] -> cycle[i + 1]`, last -> first), so a
+//! malformed dependency list can be diagnosed directly.
+//!
+//! For indexed DAGs (`adj[u]` lists successors) there is `topo_order`, the
+//! `dag_dp` fold driver, and `dag_longest_path` built on top of it.
 
-use std::collections::{HashMap, VecDeque};
+use crate::ds::graph::Adjacency;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::hash::Hash;
 
-pub fn topo_sort<N: Eq + Hash + Clone>(edges: &[(N, N)]) -> Option<Vec<N>> {
-    let mut adj: HashMap<N, Vec<N>> = HashMap::new();
-    let mut indeg: HashMap<N, usize> = HashMap::new();
-    for (u, v) in edges {
-        adj.entry(u.clone()).or_default().push(v.clone());
-        indeg.entry(u.clone()).or_default();
-        *indeg.entry(v.clone()).or_default() += 1;
-    }
-    let mut q: VecDeque<N> = indeg
-        .iter()
-        .filter(|(_, &d)| d == 0)
-        .map(|(n, _)| n.clone())
-        .collect();
+/// Some topological order of the nodes mentioned in `edges`, or `Err(cycle)`.
+pub fn topo_sort<N: Eq + Hash + Clone>(edges: &[(N, N)]) -> Result<Vec<N>, Vec<N>> {
+    Topo::from_edges(edges).solve()
+}
+
+/// Kahn's algorithm with a min-heap of ready nodes: among all nodes whose
+/// dependencies are done, the smallest comes first. O(m log n).
+pub fn topo_sort_lex<N: Ord + Hash + Clone>(edges: &[(N, N)]) -> Result<Vec<N>, Vec<N>> {
+    Topo::from_edges(edges).solve_lex()
+}
+
+/// Kahn's algorithm over a prepared adjacency map and indegree table;
+/// `ready` decides which available node goes next (FIFO or min-heap).
+fn kahn<N: Eq + Hash + Clone, R>(
+    adj: &HashMap<N, Vec<N>>,
+    mut indeg: HashMap<N, usize>,
+    mut ready: R,
+    push: impl Fn(&mut R, N),
+    pop: impl Fn(&mut R) -> Option<N>,
+) -> Result<Vec<N>, Vec<N>> {
+    for (n, _) in indeg.iter().filter(|(_, &d)| d == 0) {
+        push(&mut ready, n.clone());
+    }
     let mut out: Vec<N> = Vec::with_capacity(indeg.len());
-    while let Some(u) = q.pop_front() {
-        out.push(u.clone());
+    while let Some(u) = pop(&mut ready) {
         for v in adj.get(&u).into_iter().flatten() {
             let e = indeg.get_mut(v).unwrap();
             *e -= 1;
             if *e == 0 {
-                q.push_back(v.clone());
+                push(&mut ready, v.clone());
             }
         }
+        out.push(u);
     }
     if out.len() == indeg.len() {
-        Some(out)
+        Ok(out)
+    } else {
+        Err(find_cycle(adj, &indeg))
+    }
+}
+
+/// After Kahn's algorithm stalls, every node with a positive remaining
+/// indegree has a predecessor that is also stuck; walking those predecessor
+/// links must revisit a node, and the loop found is a cycle.
+fn find_cycle<N: Eq + Hash + Clone>(adj: &HashMap<N, Vec<N>>, indeg: &HashMap<N, usize>) -> Vec<N> {
+    let stuck = |n: &N| indeg.get(n).is_some_and(|&d| d > 0);
+    let mut pred: HashMap<&N, &N> = HashMap::new();
+    for (u, vs) in adj {
+        if stuck(u) {
+            for v in vs.iter().filter(|v| stuck(v)) {
+                pred.entry(v).or_insert(u);
+            }
+        }
+    }
+    let mut cur = indeg
+        .keys()
+        .find(|n| stuck(n))
+        .expect("a stuck node exists");
+    let mut pos: HashMap<&N, usize> = HashMap::new();
+    let mut walk: Vec<&N> = Vec::new();
+    while !pos.contains_key(cur) {
+        pos.insert(cur, walk.len());
+        walk.push(cur);
+        cur = pred[cur];
+    }
+    // walk[pos[cur]..] follows predecessor links; reverse for edge order.
+    walk[pos[cur]..].iter().rev().map(|&n| n.clone()).collect()
+}
+
+/// Topological order of an indexed graph covering all of 0..n (isolated
+/// nodes included), or `Err(cycle)` like `topo_sort`.
+pub fn topo_order(adj: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let n = adj.len();
+    let mut indeg = vec![0usize; n];
+    for &v in adj.iter().flatten() {
+        indeg[v] += 1;
+    }
+    let mut q: VecDeque<usize> = (0..n).filter(|&u| indeg[u] == 0).collect();
+    let mut out = Vec::with_capacity(n);
+    while let Some(u) = q.pop_front() {
+        out.push(u);
+        for &v in &adj[u] {
+            indeg[v] -= 1;
+            if indeg[v] == 0 {
+                q.push_back(v);
+            }
+        }
+    }
+    if out.len() == n {
+        Ok(out)
     } else {
-        None
+        let edges: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| adj[u].iter().map(move |&v| (u, v)))
+            .collect();
+        Err(topo_sort(&edges).expect_err("Kahn stalled, so a cycle exists"))
+    }
+}
+
+/// Run a fold over a DAG in topological `order`: each node's value is
+/// `f(node, preds)` where `preds` holds `(predecessor, &value)` for every
+/// incoming edge (repeated for parallel edges). Returns values by node index.
+///
+/// Path counting, longest/shortest paths and "sum over ancestors" puzzles are
+/// all one closure away, e.g. `if u == src { 1 } else { preds.iter().map(|p| *p.1).sum() }`.
+pub fn dag_dp<T>(
+    adj: &[Vec<usize>],
+    order: &[usize],
+    mut f: impl FnMut(usize, &[(usize, &T)]) -> T,
+) -> Vec<T> {
+    let n = adj.len();
+    let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (u, vs) in adj.iter().enumerate() {
+        for &v in vs {
+            preds[v].push(u);
+        }
+    }
+    let mut val: Vec<Option<T>> = (0..n).map(|_| None).collect();
+    for &u in order {
+        let ps: Vec<(usize, &T)> = preds[u]
+            .iter()
+            .map(|&p| (p, val[p].as_ref().expect("order must be topological")))
+            .collect();
+        let x = f(u, &ps);
+        val[u] = Some(x);
+    }
+    val.into_iter()
+        .map(|x| x.expect("order must cover every node"))
+        .collect()
+}
+
+/// Heaviest path in a DAG where each node `u` contributes `weights[u]`.
+/// Returns (total, path) for the best path anywhere in the graph, or
+/// `Err(cycle)` if `adj` is not acyclic.
+pub fn dag_longest_path(
+    n: usize,
+    adj: &[Vec<usize>],
+    weights: &[i64],
+) -> Result<(i64, Vec<usize>), Vec<usize>> {
+    debug_assert_eq!(adj.len(), n);
+    if n == 0 {
+        return Ok((0, Vec::new()));
+    }
+    let order = topo_order(adj)?;
+    // best[u] = (heaviest path ending at u, predecessor on it)
+    let best = dag_dp(
+        adj,
+        &order,
+        |u, preds: &[(usize, &(i64, Option<usize>))]| {
+            let from = preds.iter().max_by_key(|(_, b)| b.0);
+            match from {
+                Some(&(p, b)) if b.0 > 0 => (b.0 + weights[u], Some(p)),
+                _ => (weights[u], None),
+            }
+        },
+    );
+    let end = (0..n).max_by_key(|&u| best[u].0).unwrap();
+    let mut path = vec![end];
+    while let Some(p) = best[*path.last().unwrap()].1 {
+        path.push(p);
+    }
+    path.reverse();
+    Ok((best[end].0, path))
+}
+
+/// Every topological order of a small indexed DAG, in lexicographic order,
+/// stopping after `limit` orders if given. The count can grow factorially, so
+/// keep `n` small or pass a limit. A cyclic graph yields no orders.
+pub fn all_topo_orders(adj: &[Vec<usize>], limit: Option<usize>) -> Vec<Vec<usize>> {
+    fn go(
+        adj: &[Vec<usize>],
+        indeg: &mut [usize],
+        used: &mut [bool],
+        cur: &mut Vec<usize>,
+        out: &mut Vec<Vec<usize>>,
+        limit: usize,
+    ) {
+        if cur.len() == adj.len() {
+            out.push(cur.clone());
+            return;
+        }
+        for u in 0..adj.len() {
+            if out.len() >= limit {
+                return;
+            }
+            if used[u] || indeg[u] != 0 {
+                continue;
+            }
+            used[u] = true;
+            cur.push(u);
+            adj[u].iter().for_each(|&v| indeg[v] -= 1);
+            go(adj, indeg, used, cur, out, limit);
+            adj[u].iter().for_each(|&v| indeg[v] += 1);
+            cur.pop();
+            used[u] = false;
+        }
+    }
+    let n = adj.len();
+    let mut indeg = vec![0usize; n];
+    for &v in adj.iter().flatten() {
+        indeg[v] += 1;
+    }
+    let mut out = Vec::new();
+    let limit = limit.unwrap_or(usize::MAX);
+    if limit > 0 {
+        go(
+            adj,
+            &mut indeg,
+            &mut vec![false; n],
+            &mut Vec::new(),
+            &mut out,
+            limit,
+        );
     }
+    out
 }
 
+/// Incremental dependency graph: edges can be added or removed at any time
+/// and in-degrees are kept up to date, so re-sorting after edits only runs
+/// Kahn's loop instead of rebuilding the graph.
 #[derive(Clone, Debug, Default)]
 pub struct Topo<N: Eq + Hash + Clone> {
-    edges: Vec<(N, N)>,
+    adj: HashMap<N, Vec<N>>,
+    indeg: HashMap<N, usize>, // every known node, including isolated ones
 }
 
 impl<N: Eq + Hash + Clone> Topo<N> {
     pub fn new() -> Self {
-        Self { edges: Vec::new() }
+        Self {
+            adj: HashMap::new(),
+            indeg: HashMap::new(),
+        }
+    }
+    /// Build from an edge list.
+    pub fn from_edges(edges: &[(N, N)]) -> Self {
+        let mut t = Self::new();
+        for (u, v) in edges {
+            t.add_edge(u.clone(), v.clone());
+        }
+        t
+    }
+    /// Build from an `Adjacency`, keeping nodes without edges.
+    pub fn from_adjacency(g: &Adjacency<N>) -> Self {
+        let mut t = Self::new();
+        for u in g.nodes() {
+            t.add_node(u.clone());
+            for v in g.neighbors(u) {
+                t.add_edge(u.clone(), v.clone());
+            }
+        }
+        t
     }
+    /// Ensure `n` takes part in the order even without edges.
+    pub fn add_node(&mut self, n: N) {
+        self.indeg.entry(n).or_default();
+    }
+    /// Add the dependency `u -> v` (u must come before v).
     pub fn add_edge(&mut self, u: N, v: N) {
-        self.edges.push((u, v));
+        self.indeg.entry(u.clone()).or_default();
+        *self.indeg.entry(v.clone()).or_default() += 1;
+        self.adj.entry(u).or_default().push(v);
+    }
+    /// Remove one `u -> v` edge; returns false if there was none.
+    pub fn remove_edge(&mut self, u: &N, v: &N) -> bool {
+        let Some(vs) = self.adj.get_mut(u) else {
+            return false;
+        };
+        let Some(i) = vs.iter().position(|x| x == v) else {
+            return false;
+        };
+        vs.swap_remove(i);
+        *self.indeg.get_mut(v).unwrap() -= 1;
+        true
     }
-    pub fn solve(&self) -> Option<Vec<N>> {
-        topo_sort(&self.edges)
+    /// Current in-degree of `n` (0 for unknown nodes).
+    pub fn indegree(&self, n: &N) -> usize {
+        self.indeg.get(n).copied().unwrap_or(0)
+    }
+    pub fn solve(&self) -> Result<Vec<N>, Vec<N>> {
+        kahn(
+            &self.adj,
+            self.indeg.clone(),
+            VecDeque::new(),
+            |q, n| q.push_back(n),
+            |q| q.pop_front(),
+        )
+    }
+}
+
+impl<N: Ord + Hash + Clone> Topo<N> {
+    /// Like `solve`, but ties are broken by taking the smallest ready node.
+    pub fn solve_lex(&self) -> Result<Vec<N>, Vec<N>> {
+        kahn(
+            &self.adj,
+            self.indeg.clone(),
+            BinaryHeap::new(),
+            |h, n| h.push(Reverse(n)),
+            |h| h.pop().map(|Reverse(n)| n),
+        )
     }
 }
 
@@ -66,11 +332,87 @@ mod tests {
     #[test]
     fn detect_cycle() {
         let edges = [(1, 2), (2, 3), (3, 1)];
-        assert!(topo_sort(&edges).is_none());
+        assert!(topo_sort(&edges).is_err());
         let mut t = Topo::new();
         t.add_edge(1, 2);
         t.add_edge(2, 3);
         t.add_edge(3, 1);
-        assert!(t.solve().is_none());
+        assert!(t.solve().is_err());
+    }
+    #[test]
+    fn lexicographic_ties() {
+        // AoC 2018 day 7 example.
+        let edges = [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ];
+        let order: String = topo_sort_lex(&edges).unwrap().into_iter().collect();
+        assert_eq!(order, "CABDFE");
+        let mut t = Topo::new();
+        t.add_edge('b', 'c');
+        t.add_edge('a', 'c');
+        assert_eq!(t.solve_lex(), Ok(vec!['a', 'b', 'c']));
+        assert!(topo_sort_lex(&[(1, 2), (2, 1)]).is_err());
+    }
+    #[test]
+    fn reports_the_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1, plus an unrelated tail 3 -> 4.
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)];
+        let mut cycle = topo_sort(&edges).unwrap_err();
+        let k = cycle.iter().position(|&x| x == 1).unwrap();
+        cycle.rotate_left(k);
+        assert_eq!(cycle, vec![1, 2, 3]);
+        assert_eq!(topo_sort_lex(&[(7, 7)]), Err(vec![7]));
+    }
+    #[test]
+    fn dag_dp_counts_paths_and_longest_path() {
+        // you -> {a, b}; a -> {b, out}; b -> out   (day 11 style)
+        let (you, a, b, out) = (0, 1, 2, 3);
+        let adj = vec![vec![a, b], vec![b, out], vec![out], vec![]];
+        let order = topo_order(&adj).unwrap();
+        let ways = dag_dp(&adj, &order, |u, preds| {
+            if u == you {
+                1u64
+            } else {
+                preds.iter().map(|p| *p.1).sum()
+            }
+        });
+        assert_eq!(ways[out], 3);
+        let (w, path) = dag_longest_path(4, &adj, &[1, 5, 2, 1]).unwrap();
+        assert_eq!((w, path), (9, vec![you, a, b, out]));
+        assert!(dag_longest_path(2, &[vec![1], vec![0]], &[1, 1]).is_err());
+    }
+    #[test]
+    fn enumerate_all_orders() {
+        // 0 -> 2, 1 -> 2, 2 -> 3: 0 and 1 may swap.
+        let adj = vec![vec![2], vec![2], vec![3], vec![]];
+        assert_eq!(
+            all_topo_orders(&adj, None),
+            vec![vec![0, 1, 2, 3], vec![1, 0, 2, 3]]
+        );
+        assert_eq!(all_topo_orders(&adj, Some(1)).len(), 1);
+        // Three unrelated nodes: 3! orders.
+        assert_eq!(all_topo_orders(&[vec![], vec![], vec![]], None).len(), 6);
+        assert!(all_topo_orders(&[vec![1], vec![0]], None).is_empty());
+    }
+    #[test]
+    fn incremental_edits_and_adjacency() {
+        let mut g = Adjacency::new();
+        g.add_edge("b", "c");
+        g.add_edge("a", "b");
+        g.add_node("z");
+        let mut t = Topo::from_adjacency(&g);
+        assert_eq!(t.solve_lex(), Ok(vec!["a", "b", "c", "z"]));
+        t.add_edge("c", "a");
+        assert!(t.solve().is_err());
+        assert_eq!(t.indegree(&"a"), 1);
+        assert!(t.remove_edge(&"c", &"a"));
+        assert!(!t.remove_edge(&"c", &"a"));
+        assert_eq!(t.solve_lex(), Ok(vec!["a", "b", "c", "z"]));
     }
 }
@@ -0,0 +1,544 @@
+//! Treaps: randomized balanced BSTs in two flavors.
+//!
+//! - `TreapMap<K, V>`: ordered map with O(log n) expected insert/get/remove,
+//!   plus order statistics (`nth`, `rank`). A balanced alternative to `BstMap`.
+//! - `ImplicitTreap<T>`: a sequence indexed by position. Every node's key is
+//!   implicitly "number of elements to its left", so `insert`/`remove` at an
+//!   index, `split_off`/`append`, and `reverse(range)` are all O(log n)
+//!   expected. Reversal is lazy: a flag on a subtree root is pushed down only
+//!   when the subtree is restructured.
+//!
+//! Both are built from the classic `split`/`merge` pair over `Option<Box<_>>`
+//! links. Heap priorities come from a small xorshift generator with a fixed
+//! seed, so behavior (and performance) is reproducible between runs.
+
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Deterministic xorshift64 stream for node priorities.
+#[derive(Clone, Debug)]
+struct Prio(u64);
+
+impl Prio {
+    fn new() -> Self {
+        Prio(0x9E37_79B9_7F4A_7C15)
+    }
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ordered map
+// ---------------------------------------------------------------------------
+
+type MapLink<K, V> = Option<Box<MapNode<K, V>>>;
+
+#[derive(Clone, Debug)]
+struct MapNode<K, V> {
+    key: K,
+    val: V,
+    prio: u64,
+    size: usize,
+    left: MapLink<K, V>,
+    right: MapLink<K, V>,
+}
+
+fn map_size<K, V>(t: &MapLink<K, V>) -> usize {
+    t.as_ref().map_or(0, |n| n.size)
+}
+
+fn map_update<K, V>(n: &mut MapNode<K, V>) {
+    n.size = 1 + map_size(&n.left) + map_size(&n.right);
+}
+
+/// Split into (keys where `go_left(key)`, the rest); `go_left` must be monotone.
+fn map_split<K, V>(
+    t: MapLink<K, V>,
+    go_left: &dyn Fn(&K) -> bool,
+) -> (MapLink<K, V>, MapLink<K, V>) {
+    match t {
+        None => (None, None),
+        Some(mut n) => {
+            if go_left(&n.key) {
+                let (a, b) = map_split(n.right.take(), go_left);
+                n.right = a;
+                map_update(&mut n);
+                (Some(n), b)
+            } else {
+                let (a, b) = map_split(n.left.take(), go_left);
+                n.left = b;
+                map_update(&mut n);
+                (a, Some(n))
+            }
+        }
+    }
+}
+
+fn map_merge<K, V>(a: MapLink<K, V>, b: MapLink<K, V>) -> MapLink<K, V> {
+    match (a, b) {
+        (None, t) | (t, None) => t,
+        (Some(mut x), Some(mut y)) => {
+            if x.prio > y.prio {
+                x.right = map_merge(x.right.take(), Some(y));
+                map_update(&mut x);
+                Some(x)
+            } else {
+                y.left = map_merge(Some(x), y.left.take());
+                map_update(&mut y);
+                Some(y)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TreapMap<K: Ord, V> {
+    root: MapLink<K, V>,
+    rng: Prio,
+}
+
+impl<K: Ord, V> Default for TreapMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> TreapMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rng: Prio::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        map_size(&self.root)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut cur = self.root.as_ref();
+        while let Some(n) = cur {
+            match key.cmp(&n.key) {
+                Ordering::Less => cur = n.left.as_ref(),
+                Ordering::Greater => cur = n.right.as_ref(),
+                Ordering::Equal => return Some(&n.val),
+            }
+        }
+        None
+    }
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut cur = self.root.as_mut();
+        while let Some(n) = cur {
+            match key.cmp(&n.key) {
+                Ordering::Less => cur = n.left.as_mut(),
+                Ordering::Greater => cur = n.right.as_mut(),
+                Ordering::Equal => return Some(&mut n.val),
+            }
+        }
+        None
+    }
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+    /// Insert or replace; returns the previous value for `key`.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            return Some(std::mem::replace(v, val));
+        }
+        let (l, r) = map_split(self.root.take(), &|k| *k < key);
+        let node = Box::new(MapNode {
+            key,
+            val,
+            prio: self.rng.next(),
+            size: 1,
+            left: None,
+            right: None,
+        });
+        self.root = map_merge(map_merge(l, Some(node)), r);
+        None
+    }
+    /// Remove `key`, returning its value if present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (l, rest) = map_split(self.root.take(), &|k| k < key);
+        let (mid, r) = map_split(rest, &|k| k <= key);
+        self.root = map_merge(l, r);
+        mid.map(|n| n.val)
+    }
+    /// The `i`-th smallest entry (0-based).
+    pub fn nth(&self, mut i: usize) -> Option<(&K, &V)> {
+        let mut cur = self.root.as_ref();
+        while let Some(n) = cur {
+            let ls = map_size(&n.left);
+            match i.cmp(&ls) {
+                Ordering::Less => cur = n.left.as_ref(),
+                Ordering::Equal => return Some((&n.key, &n.val)),
+                Ordering::Greater => {
+                    i -= ls + 1;
+                    cur = n.right.as_ref();
+                }
+            }
+        }
+        None
+    }
+    /// Number of keys strictly less than `key`.
+    pub fn rank(&self, key: &K) -> usize {
+        let mut cur = self.root.as_ref();
+        let mut r = 0;
+        while let Some(n) = cur {
+            if n.key < *key {
+                r += map_size(&n.left) + 1;
+                cur = n.right.as_ref();
+            } else {
+                cur = n.left.as_ref();
+            }
+        }
+        r
+    }
+    /// In-order iterator over `(key, value)`.
+    pub fn iter(&self) -> TreapMapIter<'_, K, V> {
+        let mut it = TreapMapIter { stack: Vec::new() };
+        it.push_left(self.root.as_deref());
+        it
+    }
+}
+
+/// In-order iterator for `TreapMap`.
+pub struct TreapMapIter<'a, K, V> {
+    stack: Vec<&'a MapNode<K, V>>,
+}
+
+impl<'a, K, V> TreapMapIter<'a, K, V> {
+    fn push_left(&mut self, mut cur: Option<&'a MapNode<K, V>>) {
+        while let Some(n) = cur {
+            self.stack.push(n);
+            cur = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for TreapMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = self.stack.pop()?;
+        self.push_left(n.right.as_deref());
+        Some((&n.key, &n.val))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Implicit-key sequence
+// ---------------------------------------------------------------------------
+
+type SeqLink<T> = Option<Box<SeqNode<T>>>;
+
+#[derive(Clone, Debug)]
+struct SeqNode<T> {
+    val: T,
+    prio: u64,
+    size: usize,
+    rev: bool, // children (recursively) still need swapping
+    left: SeqLink<T>,
+    right: SeqLink<T>,
+}
+
+fn seq_size<T>(t: &SeqLink<T>) -> usize {
+    t.as_ref().map_or(0, |n| n.size)
+}
+
+fn seq_update<T>(n: &mut SeqNode<T>) {
+    n.size = 1 + seq_size(&n.left) + seq_size(&n.right);
+}
+
+/// Apply a pending reversal to `n`'s children.
+fn seq_push<T>(n: &mut SeqNode<T>) {
+    if n.rev {
+        std::mem::swap(&mut n.left, &mut n.right);
+        for c in [&mut n.left, &mut n.right].into_iter().flatten() {
+            c.rev ^= true;
+        }
+        n.rev = false;
+    }
+}
+
+/// Split into (first `k` elements, the rest).
+fn seq_split<T>(t: SeqLink<T>, k: usize) -> (SeqLink<T>, SeqLink<T>) {
+    match t {
+        None => (None, None),
+        Some(mut n) => {
+            seq_push(&mut n);
+            let ls = seq_size(&n.left);
+            if k <= ls {
+                let (a, b) = seq_split(n.left.take(), k);
+                n.left = b;
+                seq_update(&mut n);
+                (a, Some(n))
+            } else {
+                let (a, b) = seq_split(n.right.take(), k - ls - 1);
+                n.right = a;
+                seq_update(&mut n);
+                (Some(n), b)
+            }
+        }
+    }
+}
+
+fn seq_merge<T>(a: SeqLink<T>, b: SeqLink<T>) -> SeqLink<T> {
+    match (a, b) {
+        (None, t) | (t, None) => t,
+        (Some(mut x), Some(mut y)) => {
+            if x.prio > y.prio {
+                seq_push(&mut x);
+                x.right = seq_merge(x.right.take(), Some(y));
+                seq_update(&mut x);
+                Some(x)
+            } else {
+                seq_push(&mut y);
+                y.left = seq_merge(Some(x), y.left.take());
+                seq_update(&mut y);
+                Some(y)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImplicitTreap<T> {
+    root: SeqLink<T>,
+    rng: Prio,
+}
+
+impl<T> Default for ImplicitTreap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ImplicitTreap<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rng: Prio::new(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        seq_size(&self.root)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+    fn node(&mut self, val: T) -> SeqLink<T> {
+        Some(Box::new(SeqNode {
+            val,
+            prio: self.rng.next(),
+            size: 1,
+            rev: false,
+            left: None,
+            right: None,
+        }))
+    }
+    /// Append `val` at the end.
+    pub fn push_back(&mut self, val: T) {
+        let n = self.node(val);
+        self.root = seq_merge(self.root.take(), n);
+    }
+    /// Insert `val` so it ends up at position `idx` (panics if `idx > len`).
+    pub fn insert(&mut self, idx: usize, val: T) {
+        assert!(idx <= self.len(), "insert index out of bounds");
+        let (l, r) = seq_split(self.root.take(), idx);
+        let n = self.node(val);
+        self.root = seq_merge(seq_merge(l, n), r);
+    }
+    /// Remove and return the element at `idx` (None if out of bounds).
+    pub fn remove(&mut self, idx: usize) -> Option<T> {
+        if idx >= self.len() {
+            return None;
+        }
+        let (l, rest) = seq_split(self.root.take(), idx);
+        let (mid, r) = seq_split(rest, 1);
+        self.root = seq_merge(l, r);
+        mid.map(|n| n.val)
+    }
+    /// Element at `idx` (None if out of bounds).
+    pub fn get(&self, mut idx: usize) -> Option<&T> {
+        let mut cur = self.root.as_ref();
+        let mut flip = false;
+        while let Some(n) = cur {
+            flip ^= n.rev;
+            let (l, r) = if flip {
+                (&n.right, &n.left)
+            } else {
+                (&n.left, &n.right)
+            };
+            let ls = seq_size(l);
+            match idx.cmp(&ls) {
+                Ordering::Less => cur = l.as_ref(),
+                Ordering::Equal => return Some(&n.val),
+                Ordering::Greater => {
+                    idx -= ls + 1;
+                    cur = r.as_ref();
+                }
+            }
+        }
+        None
+    }
+    /// Reverse the elements in `range` (panics if the range exceeds `len`).
+    pub fn reverse(&mut self, range: Range<usize>) {
+        assert!(range.end <= self.len(), "reverse range out of bounds");
+        if range.start >= range.end {
+            return;
+        }
+        let (l, rest) = seq_split(self.root.take(), range.start);
+        let (mut mid, r) = seq_split(rest, range.end - range.start);
+        if let Some(m) = mid.as_mut() {
+            m.rev ^= true;
+        }
+        self.root = seq_merge(seq_merge(l, mid), r);
+    }
+    /// Split into `[0, at)` (kept) and `[at, len)` (returned). Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> ImplicitTreap<T> {
+        assert!(at <= self.len(), "split index out of bounds");
+        let (l, r) = seq_split(self.root.take(), at);
+        self.root = l;
+        ImplicitTreap {
+            root: r,
+            rng: Prio(self.rng.next()),
+        }
+    }
+    /// Move all elements of `other` to the end of `self`.
+    pub fn append(&mut self, other: ImplicitTreap<T>) {
+        self.root = seq_merge(self.root.take(), other.root);
+    }
+}
+
+impl<T: Clone> ImplicitTreap<T> {
+    /// Snapshot of the sequence in order.
+    pub fn to_vec(&self) -> Vec<T> {
+        fn walk<T: Clone>(t: &SeqLink<T>, flip: bool, out: &mut Vec<T>) {
+            if let Some(n) = t {
+                let flip = flip ^ n.rev;
+                let (l, r) = if flip {
+                    (&n.right, &n.left)
+                } else {
+                    (&n.left, &n.right)
+                };
+                walk(l, flip, out);
+                out.push(n.val.clone());
+                walk(r, flip, out);
+            }
+        }
+        let mut out = Vec::with_capacity(self.len());
+        walk(&self.root, false, &mut out);
+        out
+    }
+}
+
+impl<T> FromIterator<T> for ImplicitTreap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(it: I) -> Self {
+        let mut t = ImplicitTreap::new();
+        for x in it {
+            t.push_back(x);
+        }
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImplicitTreap, TreapMap};
+
+    #[test]
+    fn map_insert_get_remove() {
+        let mut m = TreapMap::new();
+        assert!(m.is_empty());
+        for k in [5, 1, 9, 3, 7] {
+            assert_eq!(m.insert(k, k * 10), None);
+        }
+        assert_eq!(m.insert(3, 33), Some(30));
+        assert_eq!(m.len(), 5);
+        assert_eq!(m.get(&3), Some(&33));
+        if let Some(v) = m.get_mut(&9) {
+            *v += 1;
+        }
+        assert_eq!(m.get(&9), Some(&91));
+        assert_eq!(m.remove(&5), Some(50));
+        assert_eq!(m.remove(&5), None);
+        assert!(!m.contains_key(&5) && m.contains_key(&7));
+        let keys: Vec<_> = m.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, vec![1, 3, 7, 9]);
+    }
+
+    #[test]
+    fn map_order_statistics_sorted_inserts() {
+        // Sorted inserts would degrade an unbalanced BST; the treap stays shallow.
+        let mut m = TreapMap::new();
+        for k in 0..2000 {
+            m.insert(k, ());
+        }
+        assert_eq!(m.nth(0).map(|(k, _)| *k), Some(0));
+        assert_eq!(m.nth(1234).map(|(k, _)| *k), Some(1234));
+        assert!(m.nth(2000).is_none());
+        assert_eq!(m.rank(&500), 500);
+        assert_eq!(m.rank(&-1), 0);
+        assert_eq!(m.rank(&5000), 2000);
+    }
+
+    #[test]
+    fn sequence_insert_remove_get() {
+        let mut s: ImplicitTreap<char> = "ace".chars().collect();
+        s.insert(1, 'b');
+        s.insert(3, 'd');
+        s.insert(5, 'f');
+        assert_eq!(s.to_vec(), vec!['a', 'b', 'c', 'd', 'e', 'f']);
+        assert_eq!(s.get(4), Some(&'e'));
+        assert_eq!(s.get(6), None);
+        assert_eq!(s.remove(0), Some('a'));
+        assert_eq!(s.remove(10), None);
+        assert_eq!(s.len(), 5);
+    }
+
+    #[test]
+    fn sequence_reverse_matches_vec() {
+        let n = 200;
+        let mut s: ImplicitTreap<usize> = (0..n).collect();
+        let mut v: Vec<usize> = (0..n).collect();
+        let mut x = 7usize;
+        for _ in 0..300 {
+            x = (x * 1103515245 + 12345) % (1 << 31);
+            let a = x % n;
+            let b = (x / n) % n;
+            let (l, r) = (a.min(b), a.max(b) + 1);
+            s.reverse(l..r);
+            v[l..r].reverse();
+            // interleave a move to exercise split/merge under pending flags
+            let from = (x / 7) % n;
+            let to = (x / 11) % n;
+            let e = s.remove(from).unwrap();
+            s.insert(to, e);
+            let e = v.remove(from);
+            v.insert(to, e);
+        }
+        assert_eq!(s.to_vec(), v);
+        assert!((0..n).all(|i| s.get(i) == Some(&v[i])));
+    }
+
+    #[test]
+    fn sequence_split_off_and_append() {
+        let mut a: ImplicitTreap<i32> = (1..=6).collect();
+        a.reverse(0..6);
+        let b = a.split_off(2);
+        assert_eq!(a.to_vec(), vec![6, 5]);
+        assert_eq!(b.to_vec(), vec![4, 3, 2, 1]);
+        let mut b = b;
+        b.append(a);
+        assert_eq!(b.to_vec(), vec![4, 3, 2, 1, 6, 5]);
+        b.reverse(3..3); // empty range is a no-op
+        assert_eq!(b.len(), 6);
+    }
+}
@@ -0,0 +1,479 @@
+//! Tree utilities over indexed, undirected adjacency lists.
+//!
+//! Trees are given as `adj[u]` neighbor lists on nodes 0..n with every edge
+//! listed in both directions. All traversals use explicit stacks, so deep
+//! (path-like) trees do not overflow the call stack.
+//!
+//! - `euler_tour(adj, root)`: entry/exit times so that every subtree is a
+//!   contiguous range of the flattened order, ready for `Fenwick` range sums.
+//! - `tree_diameter(adj)` / `tree_centers(adj)`: longest path via double BFS
+//!   and the middle node(s) of it, a natural root choice.
+//! - `Tree`: a rooted view (parent, children, depth, subtree size, pre/post
+//!   order) built once from an edge list.
+//! - `tree_dp(tree, f)`: bottom-up fold in post-order without recursion.
+//! - `reroot(...)`: the same kind of answer for every possible root in O(n).
+//! - `cartesian_tree(&a)`: the min-rooted binary tree over an array's
+//!   positions, built in O(n) with a monotonic stack.
+
+use crate::ds::search::{bfs_with_prev, reconstruct_path};
+
+/// Entry/exit times of a DFS from the root (see `euler_tour`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EulerTour {
+    /// `tin[u]`: position of `u` in `order`.
+    pub tin: Vec<usize>,
+    /// `tout[u]`: last position of `u`'s subtree in `order` (inclusive).
+    pub tout: Vec<usize>,
+    /// Nodes in DFS preorder; subtree of `u` is `order[tin[u]..=tout[u]]`.
+    pub order: Vec<usize>,
+}
+
+impl EulerTour {
+    /// True if `a` is an ancestor of `b` (or `a == b`).
+    pub fn is_ancestor(&self, a: usize, b: usize) -> bool {
+        self.tin[a] <= self.tin[b] && self.tout[b] <= self.tout[a]
+    }
+    /// Number of nodes in the subtree of `u`.
+    pub fn subtree_size(&self, u: usize) -> usize {
+        self.tout[u] - self.tin[u] + 1
+    }
+}
+
+/// Flatten the tree rooted at `root` into preorder with entry/exit times.
+/// Nodes not connected to `root` keep `tin == tout == usize::MAX`.
+pub fn euler_tour(adj: &[Vec<usize>], root: usize) -> EulerTour {
+    let n = adj.len();
+    let mut tin = vec![usize::MAX; n];
+    let mut tout = vec![usize::MAX; n];
+    let mut order = Vec::with_capacity(n);
+    let mut st: Vec<(usize, usize, usize)> = vec![(root, usize::MAX, 0)]; // (node, parent, next edge)
+    tin[root] = 0;
+    order.push(root);
+    while let Some(top) = st.last_mut() {
+        let (u, p, i) = *top;
+        if i < adj[u].len() {
+            top.2 += 1;
+            let v = adj[u][i];
+            if v != p {
+                tin[v] = order.len();
+                order.push(v);
+                st.push((v, u, 0));
+            }
+        } else {
+            tout[u] = order.len() - 1;
+            st.pop();
+        }
+    }
+    EulerTour { tin, tout, order }
+}
+
+/// True if every node of `adj` is reachable from `root`.
+fn reaches_all(adj: &[Vec<usize>], root: usize) -> bool {
+    let mut seen = vec![false; adj.len()];
+    seen[root] = true;
+    let (mut st, mut count) = (vec![root], 1);
+    while let Some(u) = st.pop() {
+        for &v in &adj[u] {
+            if !std::mem::replace(&mut seen[v], true) {
+                count += 1;
+                st.push(v);
+            }
+        }
+    }
+    count == adj.len()
+}
+
+/// A rooted tree on nodes 0..n with the usual per-node facts precomputed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tree {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    order: Vec<usize>, // preorder; parents before children
+}
+
+impl Tree {
+    /// Root the tree given by undirected `edges` on nodes 0..n at `root`.
+    /// Panics if the edges do not connect all n nodes into a tree.
+    pub fn from_edges(n: usize, edges: &[(usize, usize)], root: usize) -> Self {
+        let mut adj = vec![Vec::new(); n];
+        for &(a, b) in edges {
+            adj[a].push(b);
+            adj[b].push(a);
+        }
+        Self::from_adjacency(&adj, root)
+    }
+    /// Root an undirected adjacency list at `root` (same requirements).
+    pub fn from_adjacency(adj: &[Vec<usize>], root: usize) -> Self {
+        let n = adj.len();
+        // Checked before the tour, which has no visited set and would loop
+        // forever on a cycle: n - 1 edges reaching all n nodes form a tree.
+        let edge_ends: usize = adj.iter().map(Vec::len).sum();
+        assert!(
+            root < n && edge_ends + 2 == 2 * n && reaches_all(adj, root),
+            "edges must form a single tree"
+        );
+        let tour = euler_tour(adj, root);
+        let mut parent = vec![None; n];
+        let mut children = vec![Vec::new(); n];
+        let mut depth = vec![0; n];
+        for &u in &tour.order {
+            for &v in &adj[u] {
+                if Some(v) != parent[u] {
+                    parent[v] = Some(u);
+                    depth[v] = depth[u] + 1;
+                    children[u].push(v);
+                }
+            }
+        }
+        let size = (0..n).map(|u| tour.subtree_size(u)).collect();
+        Self {
+            root,
+            parent,
+            children,
+            depth,
+            size,
+            order: tour.order,
+        }
+    }
+    pub fn root(&self) -> usize {
+        self.root
+    }
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+    /// Parent of `v` (None for the root).
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+    /// Edges between `v` and the root.
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+    /// Nodes in the subtree of `v`, including `v`.
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.size[v]
+    }
+    /// Preorder: every parent before its children.
+    pub fn pre_order(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.order.iter().copied()
+    }
+    /// Post-order: every node after all of its children.
+    pub fn post_order(&self) -> impl Iterator<Item = usize> + '_ {
+        self.order.iter().rev().copied()
+    }
+}
+
+/// Bottom-up fold over a rooted tree: `f(node, child_results)` sees the
+/// values already computed for each child (in `children(node)` order).
+/// Runs in post-order without recursion; returns values by node index.
+pub fn tree_dp<T>(tree: &Tree, mut f: impl FnMut(usize, &[&T]) -> T) -> Vec<T> {
+    let mut val: Vec<Option<T>> = (0..tree.len()).map(|_| None).collect();
+    for u in tree.post_order() {
+        let kids: Vec<&T> = tree
+            .children(u)
+            .iter()
+            .map(|&c| val[c].as_ref().expect("children are folded first"))
+            .collect();
+        let x = f(u, &kids);
+        val[u] = Some(x);
+    }
+    val.into_iter()
+        .map(|x| x.expect("every node visited"))
+        .collect()
+}
+
+/// Re-rooting DP: the answer for every node as if the tree were rooted there.
+///
+/// Values are aggregated per node over its neighbors' contributions:
+/// - `combine`/`remove` form an invertible aggregation with `identity`
+///   (sums, counts, xor, ...; `remove(combine(a, b), b) == a`)
+/// - `lift(acc, v)` turns the aggregate at `v` (over all its neighbors but
+///   one) into what `v` contributes across the edge to that neighbor
+///
+/// Returns `ans[u]` = aggregate over every neighbor of `u`. Two passes
+/// (post-order, then preorder), O(n) closure calls, no recursion.
+pub fn reroot<T: Clone>(
+    tree: &Tree,
+    identity: T,
+    combine: impl Fn(&T, &T) -> T,
+    remove: impl Fn(&T, &T) -> T,
+    lift: impl Fn(&T, usize) -> T,
+) -> Vec<T> {
+    // down[u]: aggregate over u's children only.
+    let mut down = vec![identity.clone(); tree.len()];
+    for u in tree.post_order() {
+        let acc = tree.children(u).iter().fold(identity.clone(), |acc, &c| {
+            combine(&acc, &lift(&down[c], c))
+        });
+        down[u] = acc;
+    }
+    let mut full = down.clone();
+    for u in tree.pre_order() {
+        for &c in tree.children(u) {
+            let without_c = remove(&full[u], &lift(&down[c], c));
+            full[c] = combine(&down[c], &lift(&without_c, u));
+        }
+    }
+    full
+}
+
+/// Min-based Cartesian tree over array positions (see `cartesian_tree`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CartesianTree {
+    /// Position of the overall minimum (None for an empty array).
+    pub root: Option<usize>,
+    pub parent: Vec<Option<usize>>,
+    pub left: Vec<Option<usize>>,
+    pub right: Vec<Option<usize>>,
+}
+
+impl CartesianTree {
+    /// For every position `i`, the inclusive index range covered by its
+    /// subtree: the widest window in which `a[i]` is the (leftmost) minimum.
+    pub fn spans(&self) -> Vec<(usize, usize)> {
+        let n = self.parent.len();
+        let mut span: Vec<(usize, usize)> = (0..n).map(|i| (i, i)).collect();
+        // Children before parents: walk a preorder backwards.
+        let mut pre = Vec::with_capacity(n);
+        let mut st: Vec<usize> = self.root.into_iter().collect();
+        while let Some(u) = st.pop() {
+            pre.push(u);
+            st.extend(self.left[u].iter().chain(&self.right[u]));
+        }
+        for &u in pre.iter().rev() {
+            if let Some(l) = self.left[u] {
+                span[u].0 = span[l].0;
+            }
+            if let Some(r) = self.right[u] {
+                span[u].1 = span[r].1;
+            }
+        }
+        span
+    }
+}
+
+/// Build the min-based Cartesian tree of `a` in O(n): the root is the
+/// position of the minimum, its left/right subtrees are the Cartesian trees of
+/// the parts before/after it. Among equal values the leftmost is the ancestor.
+pub fn cartesian_tree<T: Ord>(a: &[T]) -> CartesianTree {
+    let n = a.len();
+    let mut parent = vec![None; n];
+    let mut left = vec![None; n];
+    let mut right = vec![None; n];
+    let mut st: Vec<usize> = Vec::new(); // right spine, values non-decreasing
+    for i in 0..n {
+        let mut last = None;
+        while let Some(&top) = st.last() {
+            if a[top] <= a[i] {
+                break;
+            }
+            last = st.pop();
+        }
+        if let Some(l) = last {
+            left[i] = Some(l);
+            parent[l] = Some(i);
+        }
+        if let Some(&top) = st.last() {
+            right[top] = Some(i);
+            parent[i] = Some(top);
+        }
+        st.push(i);
+    }
+    CartesianTree {
+        root: st.first().copied(),
+        parent,
+        left,
+        right,
+    }
+}
+
+/// Farthest node from `start` (smallest index on ties), with its distance.
+fn farthest(adj: &[Vec<usize>], start: usize) -> (usize, i64, Vec<Option<usize>>) {
+    let (dist, prev) = bfs_with_prev(adj.len(), adj, start);
+    let (far, d) = dist.iter().enumerate().fold(
+        (start, 0),
+        |best, (u, &d)| if d > best.1 { (u, d) } else { best },
+    );
+    (far, d, prev)
+}
+
+/// Longest path (in edges) of the tree containing node 0, with its endpoints
+/// `(length, a, b)`; None for an empty tree. Two BFS passes: the farthest
+/// node from anywhere is one end of a diameter, and the farthest node from
+/// it is the other.
+pub fn tree_diameter(adj: &[Vec<usize>]) -> Option<(usize, usize, usize)> {
+    if adj.is_empty() {
+        return None;
+    }
+    let (a, _, _) = farthest(adj, 0);
+    let (b, d, _) = farthest(adj, a);
+    Some((d as usize, a, b))
+}
+
+/// The one or two centers of the tree containing node 0: the middle of any
+/// diameter, i.e. the nodes minimizing the distance to the farthest node.
+/// Empty for an empty tree.
+pub fn tree_centers(adj: &[Vec<usize>]) -> Vec<usize> {
+    if adj.is_empty() {
+        return Vec::new();
+    }
+    let (a, _, _) = farthest(adj, 0);
+    let (b, d, prev) = farthest(adj, a);
+    let path = reconstruct_path(&prev, b);
+    let d = d as usize;
+    if d.is_multiple_of(2) {
+        vec![path[d / 2]]
+    } else {
+        let mut c = vec![path[d / 2], path[d / 2 + 1]];
+        c.sort_unstable();
+        c
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cartesian_tree, euler_tour, reroot, tree_centers, tree_diameter, tree_dp, Tree};
+    use crate::ds::fenwick::Fenwick;
+
+    #[test]
+    fn subtree_sums_over_the_tour() {
+        //      0
+        //    /   \
+        //   1     2
+        //  / \
+        // 3   4
+        let mut adj = vec![vec![]; 5];
+        for (a, b) in [(0, 1), (0, 2), (1, 3), (1, 4)] {
+            adj[a].push(b);
+            adj[b].push(a);
+        }
+        let t = euler_tour(&adj, 0);
+        assert_eq!(t.order, vec![0, 1, 3, 4, 2]);
+        assert_eq!((t.subtree_size(1), t.subtree_size(0)), (3, 5));
+        assert!(t.is_ancestor(1, 4) && !t.is_ancestor(2, 4));
+        let value = [10, 20, 30, 40, 50];
+        let mut f = Fenwick::new(5);
+        for (u, &x) in value.iter().enumerate() {
+            f.add(t.tin[u], x);
+        }
+        assert_eq!(f.sum_range(t.tin[1], t.tout[1]), 20 + 40 + 50);
+        f.add(t.tin[3], 1);
+        assert_eq!(f.sum_range(t.tin[1], t.tout[1]), 111);
+    }
+
+    #[test]
+    fn diameter_and_centers() {
+        // 0 - 1 - 2 - 3 - 4 with a branch 2 - 5 - 6 - 7
+        let mut adj = vec![vec![]; 8];
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 4), (2, 5), (5, 6), (6, 7)] {
+            adj[a].push(b);
+            adj[b].push(a);
+        }
+        let (d, a, b) = tree_diameter(&adj).unwrap();
+        assert_eq!(d, 5);
+        let mut ends = [a, b];
+        ends.sort_unstable();
+        assert!(ends == [0, 7] || ends == [4, 7]);
+        assert_eq!(tree_centers(&adj), vec![2, 5]);
+        assert_eq!(tree_centers(&[vec![]]), vec![0]);
+        assert_eq!(tree_diameter(&[]), None);
+        assert!(tree_centers(&[]).is_empty());
+    }
+
+    #[test]
+    fn rooted_tree_queries() {
+        let t = Tree::from_edges(6, &[(0, 1), (1, 2), (1, 3), (3, 4), (0, 5)], 1);
+        assert_eq!(t.root(), 1);
+        assert_eq!(t.parent(1), None);
+        assert_eq!(t.parent(0), Some(1));
+        assert_eq!(t.children(1), &[0, 2, 3]);
+        assert_eq!((t.depth(5), t.depth(4)), (2, 2));
+        assert_eq!(
+            (t.subtree_size(1), t.subtree_size(0), t.subtree_size(3)),
+            (6, 2, 2)
+        );
+        let post: Vec<usize> = t.post_order().collect();
+        let pos = |x| post.iter().position(|&y| y == x).unwrap();
+        assert!(pos(4) < pos(3) && pos(5) < pos(0) && pos(0) < pos(1));
+        assert_eq!(post.last(), Some(&1));
+        assert_eq!(t.len(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "edges must form a single tree")]
+    fn cycle_is_rejected() {
+        // Right edge count, but a triangle plus an isolated node.
+        Tree::from_edges(4, &[(0, 1), (1, 2), (2, 0)], 0);
+    }
+
+    #[test]
+    fn tree_dp_directory_sizes() {
+        // "/" (0) holds a (1) and file 2; a holds files 3 and 4.
+        let t = Tree::from_edges(5, &[(0, 1), (0, 2), (1, 3), (1, 4)], 0);
+        let file_size = [0, 0, 100, 20, 30];
+        let total = tree_dp(&t, |u, kids: &[&u64]| {
+            file_size[u] + kids.iter().copied().sum::<u64>()
+        });
+        assert_eq!((total[0], total[1]), (150, 50));
+        // Height of each subtree.
+        let h = tree_dp(&t, |_, kids: &[&usize]| {
+            kids.iter().map(|&&k| k + 1).max().unwrap_or(0)
+        });
+        assert_eq!(h[0], 2);
+        // A path of 100k nodes does not overflow the stack.
+        let n = 100_000;
+        let edges: Vec<_> = (1..n).map(|i| (i - 1, i)).collect();
+        let deep = Tree::from_edges(n, &edges, 0);
+        assert_eq!(
+            tree_dp(&deep, |_, k: &[&usize]| 1 + k
+                .iter()
+                .copied()
+                .sum::<usize>())[0],
+            n
+        );
+    }
+
+    #[test]
+    fn reroot_sum_of_distances() {
+        // Sum of distances from every node (LeetCode 834 example).
+        let t = Tree::from_edges(6, &[(0, 1), (0, 2), (2, 3), (2, 4), (2, 5)], 0);
+        let ans = reroot(
+            &t,
+            (0i64, 0i64), // (sum of distances, node count) over a neighbor side
+            |a, b| (a.0 + b.0, a.1 + b.1),
+            |a, b| (a.0 - b.0, a.1 - b.1),
+            |acc, _| (acc.0 + acc.1 + 1, acc.1 + 1),
+        );
+        let sums: Vec<i64> = ans.iter().map(|a| a.0).collect();
+        assert_eq!(sums, vec![8, 12, 6, 10, 10, 10]);
+    }
+
+    #[test]
+    fn cartesian_tree_and_spans() {
+        let a = [3, 1, 4, 1, 5, 9, 2, 6];
+        let t = cartesian_tree(&a);
+        assert_eq!(t.root, Some(1));
+        assert_eq!((t.left[1], t.right[1]), (Some(0), Some(3)));
+        assert_eq!(t.right[3], Some(6));
+        assert_eq!(t.left[6], Some(4));
+        assert_eq!(t.parent[5], Some(4));
+        // Largest rectangle in a histogram: max over a[i] * span width.
+        let h = [2, 1, 5, 6, 2, 3];
+        let spans = cartesian_tree(&h).spans();
+        let best = (0..h.len())
+            .map(|i| h[i] * (spans[i].1 - spans[i].0 + 1))
+            .max();
+        assert_eq!(best, Some(10));
+        assert_eq!(cartesian_tree::<i32>(&[]).root, None);
+    }
+}
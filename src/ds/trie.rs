@@ -0,0 +1,184 @@
+//! Trie (prefix tree) over `char`s with per-node counters.
+//!
+//! Nodes live in a `Vec` arena and refer to their children by index, which
+//! keeps the structure easy to clone and lets other algorithms (e.g.
+//! Aho-Corasick) attach extra per-node data by index.
+//!
+//! Counters
+//! - `pass`: how many inserted words go through the node (prefix count)
+//! - `end`: how many inserted words end exactly at the node
+//!
+//! Children are kept in a `BTreeMap`, so enumerating words yields them in
+//! lexicographic order.
+
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TrieNode {
+    pub(crate) children: BTreeMap<char, usize>,
+    pub(crate) pass: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trie {
+    pub(crate) nodes: Vec<TrieNode>, // nodes[0] is the root
+    words: usize,                    // distinct words
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+            words: 0,
+        }
+    }
+    /// Insert `word` (duplicates increase its count).
+    pub fn insert(&mut self, word: &str) {
+        self.insert_node(word);
+    }
+    /// Insert `word` and return the index of its terminal node.
+    pub(crate) fn insert_node(&mut self, word: &str) -> usize {
+        let mut cur = 0;
+        self.nodes[0].pass += 1;
+        for c in word.chars() {
+            cur = match self.nodes[cur].children.get(&c) {
+                Some(&next) => next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[cur].children.insert(c, next);
+                    next
+                }
+            };
+            self.nodes[cur].pass += 1;
+        }
+        if self.nodes[cur].end == 0 {
+            self.words += 1;
+        }
+        self.nodes[cur].end += 1;
+        cur
+    }
+    /// Node reached by following `s` from the root, if any.
+    fn walk(&self, s: &str) -> Option<usize> {
+        let mut cur = 0;
+        for c in s.chars() {
+            cur = *self.nodes[cur].children.get(&c)?;
+        }
+        Some(cur)
+    }
+    /// True if `word` was inserted.
+    pub fn contains(&self, word: &str) -> bool {
+        self.count(word) > 0
+    }
+    /// True if some inserted word starts with `prefix`.
+    pub fn contains_prefix(&self, prefix: &str) -> bool {
+        self.count_prefix(prefix) > 0
+    }
+    /// Number of times `word` was inserted.
+    pub fn count(&self, word: &str) -> usize {
+        self.walk(word).map_or(0, |n| self.nodes[n].end)
+    }
+    /// Number of inserted words (with multiplicity) starting with `prefix`.
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.walk(prefix).map_or(0, |n| self.nodes[n].pass)
+    }
+    /// Lengths (in chars) of every inserted word that is a prefix of `s`, ascending.
+    ///
+    /// This is the building block for "can `s` be composed from these words"
+    /// DP: from each position, jump ahead by each returned length.
+    pub fn prefix_matches(&self, s: &str) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut cur = 0;
+        if self.nodes[0].end > 0 {
+            out.push(0);
+        }
+        for (i, c) in s.chars().enumerate() {
+            match self.nodes[cur].children.get(&c) {
+                Some(&next) => cur = next,
+                None => break,
+            }
+            if self.nodes[cur].end > 0 {
+                out.push(i + 1);
+            }
+        }
+        out
+    }
+    /// All distinct inserted words starting with `prefix`, in lexicographic order.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut out = Vec::new();
+        if let Some(start) = self.walk(prefix) {
+            let mut buf = prefix.to_string();
+            self.collect(start, &mut buf, &mut out);
+        }
+        out
+    }
+    fn collect(&self, n: usize, buf: &mut String, out: &mut Vec<String>) {
+        if self.nodes[n].end > 0 {
+            out.push(buf.clone());
+        }
+        for (&c, &child) in &self.nodes[n].children {
+            buf.push(c);
+            self.collect(child, buf, out);
+            buf.pop();
+        }
+    }
+    /// Number of distinct words stored.
+    pub fn len(&self) -> usize {
+        self.words
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trie;
+
+    #[test]
+    fn insert_contains_and_counts() {
+        let mut t = Trie::new();
+        assert!(t.is_empty());
+        for w in ["car", "cart", "care", "dog", "car"] {
+            t.insert(w);
+        }
+        assert_eq!(t.len(), 4);
+        assert!(t.contains("car") && t.contains("dog"));
+        assert!(!t.contains("ca") && !t.contains("cat"));
+        assert!(t.contains_prefix("ca") && !t.contains_prefix("x"));
+        assert_eq!(t.count("car"), 2);
+        assert_eq!(t.count_prefix("car"), 4);
+        assert_eq!(t.count_prefix(""), 5);
+    }
+
+    #[test]
+    fn words_with_prefix_sorted() {
+        let mut t = Trie::new();
+        for w in ["care", "car", "dog", "cart"] {
+            t.insert(w);
+        }
+        assert_eq!(t.words_with_prefix("car"), vec!["car", "care", "cart"]);
+        assert_eq!(t.words_with_prefix(""), vec!["car", "care", "cart", "dog"]);
+        assert!(t.words_with_prefix("z").is_empty());
+    }
+
+    #[test]
+    fn prefix_matches_for_composition() {
+        let mut t = Trie::new();
+        for w in ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"] {
+            t.insert(w);
+        }
+        assert_eq!(t.prefix_matches("brwrr"), vec![1, 2]);
+        assert_eq!(t.prefix_matches("bwurrg"), vec![1, 3]);
+        assert!(t.prefix_matches("x").is_empty());
+        t.insert("");
+        assert_eq!(t.prefix_matches("x"), vec![0]);
+    }
+}
@@ -0,0 +1,87 @@
+//! Download puzzle inputs from adventofcode.com into `inputs/dayXX.txt`.
+//!
+//! Requires the `fetch` feature and an `AOC_SESSION` env var holding the
+//! value of the site's `session` cookie. Inputs already on disk are reused,
+//! and requests are spaced at least [`MIN_INTERVAL`] apart.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aoc25::days::util;
+
+/// Puzzle year the inputs are fetched for.
+const YEAR: u32 = 2025;
+/// Minimum gap between two requests to the site.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+/// Identifies the tool to the site operators, as they ask for.
+const USER_AGENT: &str = "github.com/emmess1/aoc25 input fetcher";
+/// Records the time of the last request (seconds since the epoch).
+const STAMP_FILE: &str = "inputs/.last_fetch";
+
+/// Fetch the input for `day` (e.g. "day05" or "5") unless it is already
+/// cached; `force` re-downloads it. Returns the path of the input file.
+pub fn fetch_input(day: &str, force: bool) -> Result<PathBuf, Box<dyn Error>> {
+    let id = util::normalize_day(day);
+    let num: u32 = id["day".len()..].parse()?;
+    if !(1..=12).contains(&num) {
+        return Err(format!("Unknown day: {}", day).into());
+    }
+    let path = PathBuf::from(format!("inputs/{}.txt", id));
+    if !force && is_cached(&path) {
+        eprintln!(
+            "{} already present, skipping (use --force to re-download)",
+            path.display()
+        );
+        return Ok(path);
+    }
+    let session = std::env::var("AOC_SESSION")
+        .map_err(|_| "AOC_SESSION is not set (copy the `session` cookie from your browser)")?;
+
+    wait_for_rate_limit();
+    let url = format!("https://adventofcode.com/{}/day/{}/input", YEAR, num);
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session.trim()))
+        .set("User-Agent", USER_AGENT)
+        .call();
+    record_request();
+    let body = response
+        .map_err(|e| format!("download failed: {}", e))?
+        .into_string()?;
+
+    fs::create_dir_all("inputs")?;
+    fs::write(&path, body)?;
+    eprintln!("Saved {}", path.display());
+    Ok(path)
+}
+
+fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Sleep until [`MIN_INTERVAL`] has passed since the last recorded request.
+fn wait_for_rate_limit() {
+    let Some(last) = fs::read_to_string(STAMP_FILE)
+        .ok()
+        .and_then(|s| s.trim().parse::<u64>().ok())
+    else {
+        return;
+    };
+    let elapsed = Duration::from_secs(now_secs().saturating_sub(last));
+    if let Some(wait) = MIN_INTERVAL.checked_sub(elapsed) {
+        eprintln!("Rate limiting: waiting {:.1?}", wait);
+        std::thread::sleep(wait);
+    }
+}
+
+fn record_request() {
+    let _ = fs::create_dir_all("inputs");
+    let _ = fs::write(STAMP_FILE, now_secs().to_string());
+}
@@ -9,6 +9,14 @@
 //! - `tree_map`: A basic ordered map implemented as an unbalanced binary
 //!   search tree (BST). It is easy to follow but not balanced, so operations
 //!   can degrade to O(n) in the worst case.
+//! - `profile`: Named timing spans (`profile::span("parse")`) recorded when
+//!   enabled and exported as Chrome trace JSON for flame-chart viewers.
+//! - `days`: The puzzle solutions themselves (`days::solver`, `days::run_day`),
+//!   shared by the runner binary, the benches, the regression tests and the
+//!   fuzz targets.
+//! - `solvers`: Puzzle-shaped optimizers that outgrew a single day, e.g.
+//!   `solvers::button_presses::min_presses` (fewest non-negative integer
+//!   presses reaching exact counter targets).
 //!
 //! Design notes
 //! - All implementations prioritize readable, idiomatic Rust over micro-
@@ -22,34 +30,90 @@
 //!
 //! See the unit tests and integration tests for usage examples.
 
+pub mod days;
 pub mod ds;
+pub mod profile;
+pub mod solvers;
 
-pub use ds::array_list::ArrayList;
+pub use ds::aho_corasick::AhoCorasick;
+pub use ds::array_list::{ArrayList, GridView};
+pub use ds::automaton::GridAutomaton;
+pub use ds::beams::BeamFrontier;
 pub use ds::bitmask::BitMask;
+pub use ds::circular_buffer::CircularBuffer;
+pub use ds::combinatorics::{
+    binom, combinations, next_permutation, permutations, Combinations, Factorials, Permutations,
+};
 pub use ds::coords::{ComplexI, Point, Point3};
+pub use ds::cycle::{find_cycle, state_after};
 pub use ds::dense_grid::DenseGrid2D;
+pub use ds::diff::{Diff1D, Diff2D};
+pub use ds::dlx::Dlx;
 pub use ds::doubly_linked_list::DoublyLinkedList;
 pub use ds::dsu::DisjointSet;
 pub use ds::fcov;
 pub use ds::fenwick::Fenwick;
 pub use ds::freq_map::FreqMap;
-pub use ds::graph::Adjacency;
+pub use ds::geometry::{
+    boundary_points, interior_points, locate_point, perimeter, point_in_polygon,
+    segment_intersection, segments_intersect, shoelace_area, shoelace_area2, signed_area2,
+    Location, RectilinearRegion, SegmentIntersection,
+};
+pub use ds::graph::{count_paths_dag, reverse_graph, Adjacency, IndexedGraph, WeightedAdjacency};
 pub use ds::hash_map::SimpleHashMap;
 pub use ds::hash_set_ext::HashSetExt;
 pub use ds::heap::{MaxHeap, MinHeap};
 pub use ds::indexed_heap::IndexedMinHeap;
 pub use ds::intervals::{Interval, IntervalSet};
+pub use ds::linalg::{rational, rref, solve_gf2, Gf2Solution, Rref};
 pub use ds::linked_list::LinkedList;
-pub use ds::monotonic_queue::{MonotonicQueueMax, MonotonicQueueMin};
+pub use ds::math::digits::{
+    ceil_div, digit_len_bounds, from_digits, num_digits, pow10_table, repeat_multiplier,
+    scaled_range, sum_range, to_digits,
+};
+pub use ds::math::{ext_gcd, gcd, lcm};
+pub use ds::matrix::Matrix;
+pub use ds::modint::{Mod1e9p7, Mod998, ModInt};
+pub use ds::monotonic_queue::{
+    IndexedMonotonicMax, IndexedMonotonicMin, MonotonicQueueMax, MonotonicQueueMaxBy,
+    MonotonicQueueMin, MonotonicQueueMinBy,
+};
+pub use ds::mst::{mst_kruskal, mst_prim};
 pub use ds::neighbors::{DELTAS4, DELTAS8};
 pub use ds::parsing::{
-    parse_grid_chars, parse_grid_digits, parse_ints_whitespace, parse_lines_i64,
+    chunks_str, column_blocks, expand_tabs, extract_ints, normalize_grid, parse_blocks_with,
+    parse_grid_chars, parse_grid_digits, parse_ints_whitespace, parse_key_list, parse_key_lists,
+    parse_kv_map, parse_kv_pairs, parse_lines, parse_lines_i64, parse_points2, parse_points3,
+    parse_split, split_blocks, split_on_multiple, try_parse_grid_chars, try_parse_grid_digits,
+    try_parse_ints_whitespace, try_parse_lines_i64, windows_str, Input, ParseError,
 };
+pub use ds::primes::{divisors, factorize, is_prime, sieve};
 pub use ds::queue::{Deque, Queue};
-pub use ds::scc::tarjan_scc;
-pub use ds::search::{astar_indexed, bfs_distances, dfs_preorder, dijkstra_indexed};
+pub use ds::ratio::Ratio;
+pub use ds::ring::{ModRing, Turn};
+pub use ds::scc::{condensation, kosaraju_scc, tarjan_scc, Condensation};
+#[cfg(feature = "rayon")]
+pub use ds::search::delta_stepping;
+pub use ds::search::{
+    astar, astar_indexed, beam_search, bellman_ford, bfs01_indexed, bfs_distances, bfs_hash,
+    bfs_with_prev, dfs_preorder, dfs_visit, dijkstra_bucket, dijkstra_hash, dijkstra_indexed,
+    grid_bfs, grid_dijkstra, reconstruct_path, Connectivity, DfsVisitor,
+};
+pub use ds::sliding_window::SlidingWindow;
 pub use ds::sparse_grid::SparseGrid;
-pub use ds::stack::Stack;
-pub use ds::string_alg::{kmp_search, z_function, RollingHash};
-pub use ds::topo::{topo_sort, Topo};
+pub use ds::spatial_hash::{SpatialHash, SpatialHash3};
+pub use ds::stack::{MaxStack, MinStack, Stack};
+pub use ds::string_alg::{
+    fast_search, grid_hash, is_repetition, kmp_search, lcs, longest_common_substring,
+    smallest_period, z_function, DoubleHash, RollingHash, RollingHash2,
+};
+pub use ds::topo::{
+    all_topo_orders, dag_dp, dag_longest_path, topo_order, topo_sort, topo_sort_lex, Topo,
+};
+pub use ds::treap::{ImplicitTreap, TreapMap};
+pub use ds::tree::{
+    cartesian_tree, euler_tour, reroot, tree_centers, tree_diameter, tree_dp, CartesianTree,
+    EulerTour, Tree,
+};
 pub use ds::tree_map::BstMap;
+pub use ds::trie::Trie;
@@ -1,10 +1,269 @@
-mod days;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod report;
+mod scaffold;
+mod watch;
+
+use std::time::{Duration, Instant};
+
+use aoc25::days;
+
+const USAGE: &str = "Usage: cargo run -- <day|all> [--time] [--part N] [--timeout SECS] [--profile[=FILE]] [--report FILE] [--example] [--no-color] [--watch]  (e.g., day01, 1, or all)\n       cargo run -- verify [day]\n       cargo run -- new <day>\n       cargo run --features fetch -- fetch <day|all> [--force]";
+
+/// Default output of `--profile`: a Chrome trace (open in chrome://tracing,
+/// ui.perfetto.dev or speedscope).
+const PROFILE_FILE: &str = "profile.json";
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let day = args.get(1).map(|s| s.as_str()).unwrap_or("day01");
-    if let Err(e) = days::run_day(day) {
-        eprintln!("{}\nUsage: cargo run -- <day>  (e.g., day01 or 1)", e);
+    // Solver traces (`log::debug!` etc.) are off unless `RUST_LOG` asks for
+    // them, e.g. `RUST_LOG=debug` or `RUST_LOG=aoc25::days::day12=trace`.
+    env_logger::init();
+    let mut positional = Vec::new();
+    let mut opts = days::util::RunOptions::default();
+    let mut force = false;
+    let mut no_color = false;
+    let mut watch = false;
+    let mut profile = None;
+    let mut report_path = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--time" => opts.time = true,
+            "--example" => opts.example = true,
+            "--force" => force = true,
+            "--no-color" => no_color = true,
+            "--watch" => watch = true,
+            "--part" => opts.part = Some(parse_part(args.next())),
+            a if a.starts_with("--part=") => {
+                opts.part = Some(parse_part(Some(a["--part=".len()..].to_owned())))
+            }
+            "--profile" => profile = Some(PROFILE_FILE.to_string()),
+            a if a.starts_with("--profile=") => profile = Some(a["--profile=".len()..].to_owned()),
+            "--report" => report_path = Some(parse_path("--report", args.next())),
+            a if a.starts_with("--report=") => {
+                report_path = Some(parse_path(
+                    "--report",
+                    Some(a["--report=".len()..].to_owned()),
+                ))
+            }
+            "--timeout" => opts.timeout = Some(parse_timeout(args.next())),
+            a if a.starts_with("--timeout=") => {
+                opts.timeout = Some(parse_timeout(Some(a["--timeout=".len()..].to_owned())))
+            }
+            a if a.starts_with("--") => {
+                eprintln!("Unknown flag: {}\n{}", a, USAGE);
+                std::process::exit(1);
+            }
+            _ => positional.push(arg),
+        }
+    }
+    if report_path.is_some() && positional.first().map(String::as_str) != Some("all") {
+        eprintln!("--report only applies to `all`\n{}", USAGE);
+        std::process::exit(1);
+    }
+    if profile.is_some() {
+        aoc25::profile::enable();
+    }
+    let table = report::Table::new(opts.time, no_color);
+    let result = match positional.first().map(String::as_str) {
+        Some("fetch") => fetch(positional.get(1).map_or("all", String::as_str), force),
+        Some("new") => match positional.get(1) {
+            Some(day) => scaffold::new_day(day),
+            None => Err("new: missing day (e.g., day13)".into()),
+        },
+        Some("verify") => verify(positional.get(1).map(String::as_str), &opts, &table),
+        Some("all") => run_all(&opts, &table, report_path.as_deref()),
+        day if watch => watch::watch(day.unwrap_or("day01"), &opts, &table),
+        day => days::run_day(day.unwrap_or("day01"), &opts).map(|r| {
+            println!("{}\n{}", table.header(), table.row(&r));
+        }),
+    };
+    if let Some(path) = profile {
+        match aoc25::profile::write_chrome_trace(&path) {
+            Ok(()) => eprintln!("{}", table.note(&format!("Profile written to {}", path))),
+            Err(e) => eprintln!("cannot write profile {}: {}", path, e),
+        }
+    }
+    if let Err(e) = result {
+        eprintln!("{}\n{}", e, USAGE);
         std::process::exit(1);
     }
 }
+
+/// Run every registered day in order as rows of one summary table (status,
+/// answers, wall time), closed by a total line. With `report` the summary is
+/// also written there as markdown.
+///
+/// A failing day (e.g. missing input) is reported and skipped rather than
+/// aborting the rest; the result is an error if any day failed.
+fn run_all(
+    opts: &days::util::RunOptions,
+    table: &report::Table,
+    report: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut summary = report::Summary::default();
+    println!("{}", table.summary_header());
+    for &day in days::DAYS {
+        let path = days::util::input_path(day, if opts.example { "_example" } else { "" });
+        let start = Instant::now();
+        let outcome = if !std::path::Path::new(&path).exists() {
+            report::DayOutcome::skipped(day, report::Status::MissingInput, path, Duration::ZERO)
+        } else {
+            match days::run_day(day, opts) {
+                Ok(result) => report::DayOutcome::ran(result, start.elapsed()),
+                Err(e) => {
+                    let status = if e.is::<days::util::TimedOut>() {
+                        report::Status::Timeout
+                    } else {
+                        report::Status::Failed
+                    };
+                    report::DayOutcome::skipped(day, status, e.to_string(), start.elapsed())
+                }
+            }
+        };
+        println!("{}", table.summary_row(&outcome));
+        summary.days.push(outcome);
+    }
+    println!("{}", table.summary_total(&summary));
+    if let Some(path) = report {
+        std::fs::write(path, summary.markdown())
+            .map_err(|e| format!("cannot write report {}: {}", path, e))?;
+        println!("{}", table.note(&format!("Report written to {}", path)));
+    }
+    let failed: Vec<String> = summary
+        .not_ok()
+        .map(|d| format!("{} ({})", d.day, d.status.label()))
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        println!("\nfailed: {}", failed.join(", "));
+        Err(format!("{} day(s) failed", failed.len()).into())
+    }
+}
+
+/// Run each day with recorded answers (or just `day`) and compare against
+/// `answers.toml`, printing one status line per day.
+fn verify(
+    day: Option<&str>,
+    opts: &days::util::RunOptions,
+    table: &report::Table,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let answers = days::answers::load()?;
+    let selected: Vec<String> = match day {
+        Some(day) => vec![days::util::normalize_day(day)],
+        None => answers.keys().cloned().collect(),
+    };
+    // Answers are recorded for the real inputs, so --example is ignored here.
+    let real = days::util::RunOptions {
+        example: false,
+        ..*opts
+    };
+    let mut failed = 0;
+    for day in &selected {
+        let Some(expected) = answers.get(day) else {
+            println!("{}: no recorded answers", day);
+            continue;
+        };
+        let outcome =
+            days::run_day(day, &real).map(|result| days::answers::mismatches(&result, expected));
+        match outcome {
+            Ok(wrong) if wrong.is_empty() => println!("{}: {}", day, table.status(true, "ok")),
+            Ok(wrong) => {
+                failed += 1;
+                println!(
+                    "{}: {} {}",
+                    day,
+                    table.status(false, "MISMATCH"),
+                    wrong.join("; ")
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{}: {}", day, failure(table, e.as_ref()));
+            }
+        }
+    }
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} day(s) failed verification",
+            failed,
+            selected.len()
+        )
+        .into())
+    }
+}
+
+/// Parse the value of `--part`, exiting with usage on anything but 1 or 2.
+fn parse_part(value: Option<String>) -> u8 {
+    match value.as_deref().map(str::trim) {
+        Some("1") => 1,
+        Some("2") => 2,
+        other => {
+            eprintln!(
+                "Invalid --part: {}\n{}",
+                other.unwrap_or("<missing>"),
+                USAGE
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Value of a flag that takes a file path, exiting with usage if missing.
+fn parse_path(flag: &str, value: Option<String>) -> String {
+    match value {
+        Some(path) if !path.trim().is_empty() => path,
+        _ => {
+            eprintln!("Missing file for {}\n{}", flag, USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Parse the value of `--timeout` (seconds, fractions allowed), exiting
+/// with usage unless it is a positive number.
+fn parse_timeout(value: Option<String>) -> Duration {
+    let secs = value.as_deref().map(str::trim);
+    match secs.and_then(|s| s.parse::<f64>().ok()) {
+        Some(s) if s > 0.0 && s.is_finite() => Duration::from_secs_f64(s),
+        _ => {
+            eprintln!(
+                "Invalid --timeout: {}\n{}",
+                secs.unwrap_or("<missing>"),
+                USAGE
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Status cell for a day that did not produce answers: `TIMEOUT ...` for
+/// a day cut off by `--timeout`, `error: ...` otherwise.
+fn failure(table: &report::Table, e: &(dyn std::error::Error + 'static)) -> String {
+    if e.is::<days::util::TimedOut>() {
+        table.status(false, &e.to_string())
+    } else {
+        table.status(false, &format!("error: {}", e))
+    }
+}
+
+/// Download inputs for one day, or every registered day with `all`.
+#[cfg(feature = "fetch")]
+fn fetch(day: &str, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if day == "all" {
+        for day in days::DAYS {
+            fetch::fetch_input(day, force)?;
+        }
+        Ok(())
+    } else {
+        fetch::fetch_input(day, force).map(|_| ())
+    }
+}
+
+#[cfg(not(feature = "fetch"))]
+fn fetch(_day: &str, _force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    Err("the fetch subcommand needs the `fetch` feature (cargo run --features fetch -- fetch <day>)".into())
+}
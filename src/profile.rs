@@ -0,0 +1,156 @@
+//! Named timing spans, exported as a Chrome trace.
+//!
+//! `span("name")` returns a guard that records how long it lived once
+//! recording is switched on with `enable()`; until then it costs one atomic
+//! load. Spans nest naturally (a guard created while another is alive on the
+//! same thread shows up inside it), and each thread gets its own track.
+//!
+//! `chrome_trace()` renders everything recorded so far in the Trace Event
+//! JSON format, which `chrome://tracing`, <https://ui.perfetto.dev> and
+//! speedscope open as a flame chart.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD: Cell<u64> = const { Cell::new(0) };
+}
+
+/// One finished span: offsets are from the moment recording was enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    pub name: String,
+    pub start: Duration,
+    pub duration: Duration,
+    /// Small per-thread id, in order of each thread's first span.
+    pub thread: u64,
+}
+
+/// Start recording spans.
+pub fn enable() {
+    EPOCH.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Time the rest of the enclosing scope (or until the guard is dropped)
+/// under `name`.
+pub fn span(name: impl Into<Cow<'static, str>>) -> Span {
+    let open = is_enabled().then(|| (name.into(), Instant::now()));
+    Span { open }
+}
+
+/// Guard returned by [`span`]; records its event when dropped.
+#[must_use = "a span measures until it is dropped"]
+pub struct Span {
+    open: Option<(Cow<'static, str>, Instant)>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some((name, start)) = self.open.take() else {
+            return;
+        };
+        let duration = start.elapsed();
+        let epoch = *EPOCH.get().expect("set by enable");
+        let thread = THREAD.with(|id| {
+            if id.get() == 0 {
+                id.set(NEXT_THREAD.fetch_add(1, Ordering::Relaxed));
+            }
+            id.get()
+        });
+        let event = Event {
+            name: name.into_owned(),
+            start: start.saturating_duration_since(epoch),
+            duration,
+            thread,
+        };
+        EVENTS.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+    }
+}
+
+/// Every span recorded so far, in the order they finished.
+pub fn events() -> Vec<Event> {
+    EVENTS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// `events` as Trace Event JSON ("complete" events, times in µs).
+pub fn chrome_trace(events: &[Event]) -> String {
+    let mut out = String::from("{\"traceEvents\":[");
+    for (i, e) in events.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            "\n{{\"name\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+            escape(&e.name),
+            e.start.as_secs_f64() * 1e6,
+            e.duration.as_secs_f64() * 1e6,
+            e.thread
+        );
+    }
+    out.push_str("\n],\"displayTimeUnit\":\"ms\"}\n");
+    out
+}
+
+/// Write everything recorded so far to `path` as a Chrome trace.
+pub fn write_chrome_trace(path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::write(path, chrome_trace(&events()))
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_nest_and_render_as_trace_events() {
+        enable();
+        {
+            let _outer = span("outer");
+            let _inner = span(format!("inner \"{}\"", 1));
+        }
+        let recorded = events();
+        let outer = recorded.iter().find(|e| e.name == "outer").unwrap();
+        let inner = recorded
+            .iter()
+            .find(|e| e.name.starts_with("inner"))
+            .unwrap();
+        assert_eq!(outer.thread, inner.thread);
+        assert!(outer.start <= inner.start);
+        assert!(inner.start + inner.duration <= outer.start + outer.duration);
+
+        let json = chrome_trace(std::slice::from_ref(inner));
+        assert!(json.starts_with("{\"traceEvents\":["));
+        assert!(json.contains("\"name\":\"inner \\\"1\\\"\",\"ph\":\"X\""));
+    }
+}
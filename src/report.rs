@@ -0,0 +1,369 @@
+//! Aligned, optionally colored table output for the runner.
+//!
+//! Colors are on by default when stdout is a terminal; `--no-color` or the
+//! `NO_COLOR` env var turns them off.
+//!
+//! `run all` collects a [`Summary`] of every day's outcome, printed as the
+//! closing table and optionally saved as a markdown report.
+
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use aoc25::days::DayResult;
+
+const DAY_WIDTH: usize = 5;
+const ANSWER_WIDTH: usize = 18;
+const TIME_WIDTH: usize = 10;
+const STATUS_WIDTH: usize = 13;
+
+/// Parts slower than this are highlighted in the time columns.
+const SLOW: Duration = Duration::from_secs(1);
+
+const BOLD: &str = "1";
+const DIM: &str = "2";
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const CYAN: &str = "36";
+
+/// Table layout shared by every row printed during one invocation.
+#[derive(Debug, Clone, Copy)]
+pub struct Table {
+    /// Emit ANSI color codes.
+    pub color: bool,
+    /// Include the per-part time columns.
+    pub time: bool,
+}
+
+impl Table {
+    /// Colors follow the terminal unless `no_color` or `NO_COLOR` is set.
+    pub fn new(time: bool, no_color: bool) -> Self {
+        let color =
+            !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal();
+        Table { color, time }
+    }
+
+    /// Wrap `text` in the ANSI `code` when colors are enabled.
+    pub fn paint(&self, text: &str, code: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Column titles.
+    pub fn header(&self) -> String {
+        let mut cols = vec![
+            format!("{:<DAY_WIDTH$}", "Day"),
+            format!("{:<ANSWER_WIDTH$}", "Part 1"),
+            format!("{:<ANSWER_WIDTH$}", "Part 2"),
+        ];
+        if self.time {
+            cols.push(format!("{:>TIME_WIDTH$}", "Time 1"));
+            cols.push(format!("{:>TIME_WIDTH$}", "Time 2"));
+        }
+        self.paint(cols.join("  ").trim_end(), BOLD)
+    }
+
+    /// One day's answers; parts that did not run show as `-`.
+    pub fn row(&self, result: &DayResult) -> String {
+        let answer = |a: &Option<String>| match a {
+            Some(a) => self.paint(&format!("{:<ANSWER_WIDTH$}", a), GREEN),
+            None => self.paint(&format!("{:<ANSWER_WIDTH$}", "-"), DIM),
+        };
+        let mut cols = vec![
+            self.paint(&format!("{:<DAY_WIDTH$}", result.day), CYAN),
+            answer(&result.part1),
+            answer(&result.part2),
+        ];
+        if self.time {
+            cols.push(self.elapsed(result.timings.part1));
+            cols.push(self.elapsed(result.timings.part2));
+        }
+        cols.join("  ").trim_end().to_string()
+    }
+
+    fn elapsed(&self, t: Option<Duration>) -> String {
+        match t {
+            Some(t) => {
+                let code = if t >= SLOW { RED } else { YELLOW };
+                self.paint(&format!("{:>TIME_WIDTH$}", format!("{:.2?}", t)), code)
+            }
+            None => self.paint(&format!("{:>TIME_WIDTH$}", "-"), DIM),
+        }
+    }
+
+    /// De-emphasized informational text.
+    pub fn note(&self, text: &str) -> String {
+        self.paint(text, DIM)
+    }
+
+    /// Status word for `verify`: green when ok, red otherwise.
+    pub fn status(&self, ok: bool, text: &str) -> String {
+        self.paint(text, if ok { GREEN } else { RED })
+    }
+
+    /// Column titles of the `run all` summary; with `time` the per-part
+    /// times follow the day's total.
+    pub fn summary_header(&self) -> String {
+        let mut cols = vec![
+            format!("{:<DAY_WIDTH$}", "Day"),
+            format!("{:<STATUS_WIDTH$}", "Status"),
+            format!("{:<ANSWER_WIDTH$}", "Part 1"),
+            format!("{:<ANSWER_WIDTH$}", "Part 2"),
+            format!("{:>TIME_WIDTH$}", "Time"),
+        ];
+        if self.time {
+            cols.push(format!("{:>TIME_WIDTH$}", "Time 1"));
+            cols.push(format!("{:>TIME_WIDTH$}", "Time 2"));
+        }
+        self.paint(&cols.join("  "), BOLD)
+    }
+
+    /// One day of the summary: answers and wall time when it ran, the
+    /// reason in place of the answers when it did not.
+    pub fn summary_row(&self, outcome: &DayOutcome) -> String {
+        let status = self.paint(
+            &format!("{:<STATUS_WIDTH$}", outcome.status.label()),
+            match outcome.status {
+                Status::Ok => GREEN,
+                Status::MissingInput => YELLOW,
+                Status::Failed | Status::Timeout => RED,
+            },
+        );
+        let mut cols = vec![
+            self.paint(&format!("{:<DAY_WIDTH$}", outcome.day), CYAN),
+            status,
+        ];
+        match &outcome.result {
+            Some(result) => {
+                let answer = |a: &Option<String>| {
+                    let a = a.as_deref().unwrap_or("-");
+                    format!("{:<ANSWER_WIDTH$}", a)
+                };
+                cols.push(answer(&result.part1));
+                cols.push(answer(&result.part2));
+                cols.push(self.elapsed(Some(outcome.elapsed)));
+                if self.time {
+                    cols.push(self.elapsed(result.timings.part1));
+                    cols.push(self.elapsed(result.timings.part2));
+                }
+            }
+            None => cols.push(self.note(outcome.detail.as_deref().unwrap_or(""))),
+        }
+        cols.join("  ").trim_end().to_string()
+    }
+
+    /// Closing line of the summary: how many days ran and the total time.
+    pub fn summary_total(&self, summary: &Summary) -> String {
+        let ran = format!("{}/{} ok", summary.ok(), summary.days.len());
+        let line = format!(
+            "{:<DAY_WIDTH$}  {:<STATUS_WIDTH$}  {:<ANSWER_WIDTH$}  {:<ANSWER_WIDTH$}  {:>TIME_WIDTH$}",
+            "Total",
+            ran,
+            "",
+            "",
+            format!("{:.2?}", summary.total())
+        );
+        self.paint(&line, BOLD)
+    }
+}
+
+/// How one day of `run all` ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// No input file to run on.
+    MissingInput,
+    /// Cut off by `--timeout`.
+    Timeout,
+    /// Parse error, unknown day or panic.
+    Failed,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::MissingInput => "missing input",
+            Status::Timeout => "timeout",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+/// One day's entry in the summary.
+#[derive(Debug, Clone)]
+pub struct DayOutcome {
+    pub day: String,
+    pub status: Status,
+    /// Answers, for days that ran.
+    pub result: Option<DayResult>,
+    /// Why the day did not run (error message or missing path).
+    pub detail: Option<String>,
+    /// Wall time of the whole day, input reading included.
+    pub elapsed: Duration,
+}
+
+impl DayOutcome {
+    pub fn ran(result: DayResult, elapsed: Duration) -> Self {
+        DayOutcome {
+            day: result.day.clone(),
+            status: Status::Ok,
+            result: Some(result),
+            detail: None,
+            elapsed,
+        }
+    }
+
+    pub fn skipped(day: &str, status: Status, detail: String, elapsed: Duration) -> Self {
+        DayOutcome {
+            day: day.to_string(),
+            status,
+            result: None,
+            detail: Some(detail),
+            elapsed,
+        }
+    }
+}
+
+/// Every day's outcome from one `run all`, in run order.
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub days: Vec<DayOutcome>,
+}
+
+impl Summary {
+    /// Days that produced answers.
+    pub fn ok(&self) -> usize {
+        self.days.iter().filter(|d| d.status == Status::Ok).count()
+    }
+
+    /// Days that did not, with their status.
+    pub fn not_ok(&self) -> impl Iterator<Item = &DayOutcome> {
+        self.days.iter().filter(|d| d.status != Status::Ok)
+    }
+
+    /// Wall time of all days together.
+    pub fn total(&self) -> Duration {
+        self.days.iter().map(|d| d.elapsed).sum()
+    }
+
+    /// The summary as a markdown document with one table row per day; days
+    /// that did not run explain why in the Detail column.
+    pub fn markdown(&self) -> String {
+        // Table cells are single-line and `|`-delimited.
+        let cell = |s: &str| {
+            s.trim_end()
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>")
+        };
+        let mut out = String::from("# Advent of Code 2025 run summary\n\n");
+        out.push_str("| Day | Status | Part 1 | Part 2 | Time | Detail |\n");
+        out.push_str("|-----|--------|--------|--------|-----:|--------|\n");
+        for d in &self.days {
+            let (part1, part2, time) = match &d.result {
+                Some(r) => (
+                    r.part1.as_deref().unwrap_or("-"),
+                    r.part2.as_deref().unwrap_or("-"),
+                    format!("{:.2?}", d.elapsed),
+                ),
+                None => ("", "", String::new()),
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                d.day,
+                d.status.label(),
+                cell(part1),
+                cell(part2),
+                time,
+                cell(d.detail.as_deref().unwrap_or(""))
+            ));
+        }
+        out.push_str(&format!(
+            "| **Total** | {}/{} ok | | | {:.2?} | |\n",
+            self.ok(),
+            self.days.len(),
+            self.total()
+        ));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_align_with_header_and_color_is_optional() {
+        let result = DayResult {
+            day: "day12".into(),
+            part1: Some("440".into()),
+            ..DayResult::default()
+        };
+        let plain = Table {
+            color: false,
+            time: false,
+        };
+        assert_eq!(
+            plain.header(),
+            format!("Day    Part 1{}Part 2", " ".repeat(14))
+        );
+        assert_eq!(plain.row(&result), format!("day12  440{}-", " ".repeat(17)));
+
+        let colored = Table {
+            color: true,
+            time: true,
+        };
+        let row = colored.row(&result);
+        assert!(row.starts_with("\x1b[36mday12\x1b[0m"));
+        assert!(row.ends_with(&format!("\x1b[2m{:>10}\x1b[0m", "-")));
+    }
+
+    #[test]
+    fn summary_lists_every_day_and_totals_time() {
+        let ran = DayResult {
+            day: "day01".into(),
+            part1: Some("3".into()),
+            part2: Some("6".into()),
+            ..DayResult::default()
+        };
+        let summary = Summary {
+            days: vec![
+                DayOutcome::ran(ran, Duration::from_millis(2)),
+                DayOutcome::skipped(
+                    "day02",
+                    Status::MissingInput,
+                    "inputs/day02.txt".into(),
+                    Duration::ZERO,
+                ),
+                DayOutcome::skipped(
+                    "day03",
+                    Status::Failed,
+                    "inputs/day03.txt:2:1: bad | input\nexpected a digit".into(),
+                    Duration::from_millis(1),
+                ),
+            ],
+        };
+        assert_eq!(summary.ok(), 1);
+        assert_eq!(summary.total(), Duration::from_millis(3));
+        let plain = Table {
+            color: false,
+            time: false,
+        };
+        assert_eq!(
+            plain.summary_row(&summary.days[1]),
+            "day02  missing input  inputs/day02.txt"
+        );
+        assert!(plain.summary_total(&summary).starts_with("Total  1/3 ok"));
+
+        let md = summary.markdown();
+        assert!(md.contains("| day01 | ok | 3 | 6 | 2.00ms |  |\n"));
+        assert!(md.contains(
+            "| day03 | failed |  |  |  | inputs/day03.txt:2:1: bad \\| input<br>expected a digit |\n"
+        ));
+        assert!(md.ends_with("| **Total** | 1/3 ok | | | 3.00ms | |\n"));
+    }
+}
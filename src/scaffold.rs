@@ -0,0 +1,132 @@
+//! `cargo run -- new dayNN`: generate a new day module from a template and
+//! add it to the `register_days!` list in `src/days/mod.rs`.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use aoc25::days::util;
+
+const DAYS_MOD: &str = "src/days/mod.rs";
+
+/// Module template; `NN` is replaced with the two-digit day number.
+const TEMPLATE: &str = r#"//! AoC Day NN
+
+use super::util::PartResult;
+use super::Solver;
+
+pub fn part1(_input: &str) -> PartResult {
+    Ok(String::new())
+}
+
+pub fn part2(_input: &str) -> PartResult {
+    Ok(String::new())
+}
+
+pub struct DayNN;
+
+impl Solver for DayNN {
+    fn part1(&self, input: &str) -> PartResult {
+        part1(input)
+    }
+
+    fn part2(&self, input: &str) -> Option<PartResult> {
+        Some(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED_PART1: Option<&str> = None;
+    const EXPECTED_PART2: Option<&str> = None;
+    const EXAMPLE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/inputs/dayNN_example.txt"
+    ));
+
+    #[test]
+    fn example_part1() {
+        let got = part1(EXAMPLE).unwrap();
+        if let Some(exp) = EXPECTED_PART1 {
+            assert_eq!(got, exp);
+        }
+    }
+
+    #[test]
+    fn example_part2() {
+        let got = part2(EXAMPLE).unwrap();
+        if let Some(exp) = EXPECTED_PART2 {
+            assert_eq!(got, exp);
+        }
+    }
+}
+"#;
+
+/// Create `src/days/dayNN/mod.rs`, empty `inputs/dayNN.txt` and
+/// `inputs/dayNN_example.txt` (unless present), and wire the module in.
+pub fn new_day(day: &str) -> Result<(), Box<dyn Error>> {
+    let id = util::normalize_day(day);
+    if id == "day00" {
+        return Err(format!("Invalid day: {}", day).into());
+    }
+    let dir = format!("src/days/{}", id);
+    if Path::new(&dir).exists() {
+        return Err(format!("{} already exists", dir).into());
+    }
+
+    let days_mod = fs::read_to_string(DAYS_MOD)?;
+    let wired = register_day(&days_mod, &id)?;
+
+    fs::create_dir_all(&dir)?;
+    fs::write(format!("{}/mod.rs", dir), TEMPLATE.replace("NN", &id[3..]))?;
+    fs::create_dir_all("inputs")?;
+    for path in [
+        format!("inputs/{}.txt", id),
+        format!("inputs/{}_example.txt", id),
+    ] {
+        if !Path::new(&path).exists() {
+            fs::write(&path, "")?;
+        }
+    }
+    fs::write(DAYS_MOD, wired)?;
+    println!(
+        "Created {}/mod.rs and registered {} in {}",
+        dir, id, DAYS_MOD
+    );
+    Ok(())
+}
+
+/// Append `id: DayNN,` to the `register_days!` invocation in the
+/// `days/mod.rs` source `src`.
+fn register_day(src: &str, id: &str) -> Result<String, Box<dyn Error>> {
+    let head = "register_days! {\n";
+    let start = src
+        .find(head)
+        .ok_or_else(|| format!("{} has no `{}` block", DAYS_MOD, head.trim()))?;
+    let end = start
+        + src[start..]
+            .find("\n}")
+            .ok_or("unterminated register_days!")?
+        + 1;
+    let entry = format!("    {}: Day{},\n", id, &id["day".len()..]);
+    let mut out = src.to_string();
+    out.insert_str(end, &entry);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_day_appends_to_macro_list() {
+        let src = "register_days! {\n    day01: Day01,\n}\n\npub fn run_day() {}\n";
+        let out = register_day(src, "day02").unwrap();
+        assert_eq!(
+            out,
+            "register_days! {\n    day01: Day01,\n    day02: Day02,\n}\n\npub fn run_day() {}\n"
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Minimum button presses: the smallest non-negative integer solution of
+//! `matrix · presses = target`, measured by total presses (the 1-norm).
+//!
+//! `matrix` is a 0/1 incidence matrix with one row per counter and one column
+//! per button; pressing button `c` adds 1 to every counter `r` with
+//! `matrix[r][c] != 0`. `min_presses` finds how to reach every target exactly
+//! with as few presses in total as possible, or None if no combination does.
+//!
+//! The solve runs in two stages. First, cheap deductions: rows already at zero
+//! rule out every button touching them, and a row covered by a single
+//! remaining button forces that button's count. What survives is row-reduced
+//! with exact rational arithmetic ([`rref`]), so each pivot button is an
+//! affine function of the free ones; a depth-first search enumerates the free
+//! counts within per-button bounds (no button can exceed the smallest target it
+//! touches), pruning partial assignments that overshoot a row or cannot beat
+//! the best total found so far.
+//!
+//! With the `z3` feature, `min_presses_z3` solves the same problem with the Z3
+//! optimizer, as an independent cross-check.
+
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::ds::linalg::{rational, rref, Rref};
+
+/// Minimum total presses solving `matrix · presses = target` over the
+/// non-negative integers, or None when the targets are unreachable or the
+/// total would not fit in a `u64`.
+///
+/// `matrix` has one row per entry of `target`; any non-zero entry counts as 1.
+/// Panics if the row count differs from `target.len()` or rows are ragged.
+///
+/// The search tries every count of each free button up to its bound, so it is
+/// pseudo-polynomial: the running time grows with the target values, not just
+/// with the size of the matrix.
+pub fn min_presses(matrix: &[Vec<u8>], target: &[u64]) -> Option<u64> {
+    assert_eq!(matrix.len(), target.len(), "one matrix row per target");
+    let cols = matrix.first().map_or(0, Vec::len);
+    assert!(
+        matrix.iter().all(|row| row.len() == cols),
+        "matrix rows must have equal length"
+    );
+    let Some((forced_total, reduced, reduced_target)) = reduce(matrix, target) else {
+        log::debug!("reduction proved the targets unreachable");
+        return None;
+    };
+    log::debug!(
+        "reduced {}x{} system to {}x{} ({} presses forced)",
+        matrix.len(),
+        cols,
+        reduced.len(),
+        reduced.first().map_or(0, Vec::len),
+        forced_total
+    );
+    if reduced.is_empty() {
+        return Some(forced_total);
+    }
+    let extra = solve_exact(&reduced, &reduced_target);
+    log::trace!("exact solve of the residual system: {:?}", extra);
+    forced_total.checked_add(extra?)
+}
+
+/// [`min_presses`] via Z3's optimizer (feature `z3`, needs libz3). Also None
+/// when Z3 gives up rather than proving the targets unreachable.
+#[cfg(feature = "z3")]
+pub fn min_presses_z3(matrix: &[Vec<u8>], target: &[u64]) -> Option<u64> {
+    use z3::ast::{Ast, Int};
+    use z3::{Config, Context, Optimize, SatResult};
+
+    assert_eq!(matrix.len(), target.len(), "one matrix row per target");
+    let cols = matrix.first().map_or(0, Vec::len);
+    let ctx = Context::new(&Config::new());
+    let optimizer = Optimize::new(&ctx);
+    let zero = Int::from_u64(&ctx, 0);
+    let presses: Vec<Int> = (0..cols)
+        .map(|col| Int::new_const(&ctx, format!("x{col}")))
+        .collect();
+    for x in &presses {
+        optimizer.assert(&x.ge(&zero));
+    }
+    for (row, &t) in matrix.iter().zip(target) {
+        let sum = row
+            .iter()
+            .zip(&presses)
+            .filter(|&(&entry, _)| entry != 0)
+            .fold(zero.clone(), |acc, (_, x)| acc + x.clone());
+        optimizer.assert(&sum._eq(&Int::from_u64(&ctx, t)));
+    }
+    let total = presses.iter().cloned().fold(zero.clone(), |acc, x| acc + x);
+    optimizer.minimize(&total);
+    match optimizer.check(&[]) {
+        SatResult::Sat => optimizer.get_model()?.eval(&total, true)?.as_u64(),
+        SatResult::Unsat | SatResult::Unknown => None,
+    }
+}
+
+/// Applies cheap logical deductions before the expensive solve.
+///
+/// Steps:
+/// 1. Drop any rows whose target is already zero (and columns that touch those rows).
+/// 2. Repeatedly locate rows that only have a single active column left; whatever demand remains on
+///    that row must come entirely from that column. We subtract the forced amount from every row the
+///    column touches and mark the column as fixed.
+/// 3. Remove columns that now only touch satisfied rows.
+///
+/// Returns the forced presses plus the residual system, which still captures every feasible
+/// solution but is usually much smaller; None when the system is already infeasible or the
+/// forced presses overflow.
+fn reduce(matrix: &[Vec<u8>], target: &[u64]) -> Option<(u64, Vec<Vec<u8>>, Vec<u64>)> {
+    let rows = target.len();
+    let cols = matrix.first().map_or(0, Vec::len);
+
+    // Rows with zero target can be ignored entirely: no column touching them may be pressed.
+    let mut button_rows: Vec<Vec<usize>> = vec![Vec::new(); cols];
+    let mut row_to_buttons: Vec<Vec<usize>> = vec![Vec::new(); rows];
+    let mut active = vec![true; cols];
+    for col in 0..cols {
+        for row in 0..rows {
+            if matrix[row][col] != 0 {
+                if target[row] == 0 {
+                    active[col] = false;
+                }
+                button_rows[col].push(row);
+            }
+        }
+        if button_rows[col].is_empty() {
+            active[col] = false;
+        }
+        if active[col] {
+            for &row in &button_rows[col] {
+                row_to_buttons[row].push(col);
+            }
+        }
+    }
+
+    let mut remaining = target.to_vec();
+    let mut forced_total = 0u64;
+    loop {
+        loop {
+            let mut progress = false;
+            for row in 0..rows {
+                let need = remaining[row];
+                if need == 0 {
+                    continue;
+                }
+                let covering: Vec<usize> = row_to_buttons[row]
+                    .iter()
+                    .copied()
+                    .filter(|&col| active[col])
+                    .collect();
+                if covering.is_empty() {
+                    return None;
+                }
+                if covering.len() == 1 {
+                    let col = covering[0];
+                    for &affected in &button_rows[col] {
+                        if remaining[affected] < need {
+                            return None;
+                        }
+                        remaining[affected] -= need;
+                    }
+                    forced_total = forced_total.checked_add(need)?;
+                    active[col] = false;
+                    progress = true;
+                    break;
+                }
+            }
+            if !progress {
+                break;
+            }
+        }
+
+        let mut removed = false;
+        for col in 0..cols {
+            if !active[col] {
+                continue;
+            }
+            if button_rows[col].iter().any(|&row| remaining[row] == 0) {
+                active[col] = false;
+                removed = true;
+            }
+        }
+        if !removed {
+            break;
+        }
+    }
+
+    // Double-check that the remaining demand is still reachable with the current column set.
+    for row in 0..rows {
+        if remaining[row] > 0 && !row_to_buttons[row].iter().any(|&col| active[col]) {
+            return None;
+        }
+    }
+
+    let remaining_rows: Vec<usize> = (0..rows).filter(|&row| remaining[row] > 0).collect();
+    if remaining_rows.is_empty() {
+        return Some((forced_total, Vec::new(), Vec::new()));
+    }
+
+    // The surviving rows and columns define the residual matrix we pass to the exact solver.
+    let kept_cols: Vec<usize> = (0..cols).filter(|&col| active[col]).collect();
+    let mut reduced_matrix = vec![vec![0u8; kept_cols.len()]; remaining_rows.len()];
+    for (col_idx, &col) in kept_cols.iter().enumerate() {
+        for (row_idx, &row) in remaining_rows.iter().enumerate() {
+            if button_rows[col].contains(&row) {
+                reduced_matrix[row_idx][col_idx] = 1;
+            }
+        }
+    }
+    let reduced_target: Vec<u64> = remaining_rows.iter().map(|&row| remaining[row]).collect();
+
+    Some((forced_total, reduced_matrix, reduced_target))
+}
+
+/// Solves the reduced `matrix * presses = target` exactly by searching the free columns of its RREF.
+fn solve_exact(matrix: &[Vec<u8>], target: &[u64]) -> Option<u64> {
+    if matrix.is_empty() {
+        if target.iter().all(|&v| v == 0) {
+            // Degenerate system: nothing left to solve.
+            return Some(0);
+        }
+        return None;
+    }
+    let system = ExactSystem {
+        matrix,
+        target,
+        rref: rref(matrix, target)?,
+        max_press: max_press_counts(matrix, target),
+        button_rows: build_button_rows(matrix),
+    };
+    if system.rref.free.is_empty() {
+        return system.evaluate(&[]);
+    }
+    let mut search = FreeSearch {
+        partial_rows: vec![0u64; matrix.len()],
+        free_counts: vec![0u64; system.rref.free.len()],
+        best: None,
+    };
+    system.search_free_assignments(0, 0, &mut search);
+    search.best
+}
+
+/// Pre-computes for each column which rows it touches to accelerate the DFS pruning.
+fn build_button_rows(matrix: &[Vec<u8>]) -> Vec<Vec<usize>> {
+    if matrix.is_empty() {
+        return Vec::new();
+    }
+    let cols = matrix[0].len();
+    let mut button_rows = vec![Vec::new(); cols];
+    for (row, entries) in matrix.iter().enumerate() {
+        for (col, &entry) in entries.iter().enumerate() {
+            if entry != 0 {
+                button_rows[col].push(row);
+            }
+        }
+    }
+    button_rows
+}
+
+/// Returns a per-column upper bound: a column cannot be pressed more often than the smallest target it touches.
+fn max_press_counts(matrix: &[Vec<u8>], target: &[u64]) -> Vec<u64> {
+    if matrix.is_empty() {
+        return Vec::new();
+    }
+    let cols = matrix[0].len();
+    let mut result = vec![0u64; cols];
+    for (col, bound) in result.iter_mut().enumerate() {
+        let mut min_val = u64::MAX;
+        for (row, entries) in matrix.iter().enumerate() {
+            if entries[col] != 0 {
+                min_val = min_val.min(target[row]);
+            }
+        }
+        if min_val == u64::MAX {
+            min_val = 0;
+        }
+        *bound = min_val;
+    }
+    result
+}
+
+/// The reduced system with its RREF and the per-column data the free-variable search prunes with.
+struct ExactSystem<'a> {
+    matrix: &'a [Vec<u8>],
+    target: &'a [u64],
+    rref: Rref,
+    max_press: Vec<u64>,
+    button_rows: Vec<Vec<usize>>,
+}
+
+/// Mutable state of the depth-first search over the free columns.
+struct FreeSearch {
+    /// Per-row total contributed by the free columns assigned so far.
+    partial_rows: Vec<u64>,
+    /// Press count chosen for each free column (in `rref.free` order).
+    free_counts: Vec<u64>,
+    /// Fewest total presses found so far.
+    best: Option<u64>,
+}
+
+impl ExactSystem<'_> {
+    /// Depth-first search over the free variables, tracking partial feasibility and pruning with bounds.
+    fn search_free_assignments(&self, idx: usize, partial_sum: u64, state: &mut FreeSearch) {
+        if let Some(best_val) = state.best {
+            if partial_sum >= best_val {
+                // Already worse than the best-known solution: no need to explore deeper.
+                return;
+            }
+        }
+        let free_cols = &self.rref.free;
+        if idx == free_cols.len() {
+            if let Some(total) = self.evaluate(&state.free_counts) {
+                if total < state.best.unwrap_or(u64::MAX) {
+                    state.best = Some(total);
+                }
+            }
+            return;
+        }
+        let col = free_cols[idx];
+        // Pseudo-polynomial: every count up to the bound is tried in turn.
+        for count in 0..=self.max_press[col] {
+            let Some(sum) = partial_sum.checked_add(count) else {
+                break;
+            };
+            if count > 0 && state.best.is_some_and(|best| sum >= best) {
+                continue;
+            }
+            // Rows never exceed their targets, so the headroom cannot underflow,
+            // and a count too big for one row is too big for every later count.
+            let fits = self.button_rows[col]
+                .iter()
+                .all(|&row| count <= self.target[row] - state.partial_rows[row]);
+            if !fits {
+                break;
+            }
+            for &row in &self.button_rows[col] {
+                state.partial_rows[row] += count;
+            }
+            state.free_counts[idx] = count;
+            self.search_free_assignments(idx + 1, sum, state);
+            for &row in &self.button_rows[col] {
+                state.partial_rows[row] -= count;
+            }
+        }
+    }
+
+    /// Glues the chosen free-variable counts with the RREF solution for the pivot columns.
+    fn evaluate(&self, free_counts: &[u64]) -> Option<u64> {
+        let Rref {
+            matrix: rref_matrix,
+            rhs: rref_rhs,
+            pivots: pivot_cols,
+            free: free_cols,
+        } = &self.rref;
+        let cols = self.matrix.first().map_or(0, Vec::len);
+        let mut presses = vec![0u64; cols];
+        for (idx, &col) in free_cols.iter().enumerate() {
+            presses[col] = free_counts[idx];
+        }
+        for (row_idx, &pivot_col) in pivot_cols.iter().enumerate() {
+            let mut value = rref_rhs[row_idx].clone();
+            for (free_idx, &col) in free_cols.iter().enumerate() {
+                let coeff = rref_matrix[row_idx][col].clone();
+                if coeff.is_zero() {
+                    continue;
+                }
+                let count_rat = rational(free_counts[free_idx]);
+                value -= coeff * count_rat;
+            }
+            if !value.is_integer() {
+                return None;
+            }
+            let integer = value.to_integer();
+            if integer.is_negative() {
+                return None;
+            }
+            let count = integer.to_u64()?;
+            if count > self.max_press[pivot_col] {
+                return None;
+            }
+            presses[pivot_col] = count;
+        }
+        if !verify_press_counts(self.matrix, self.target, &presses) {
+            return None;
+        }
+        presses.iter().try_fold(0u64, |sum, &p| sum.checked_add(p))
+    }
+}
+
+/// Replays the proposed press vector and ensures it hits every row target exactly.
+fn verify_press_counts(matrix: &[Vec<u8>], target: &[u64], presses: &[u64]) -> bool {
+    for (row_idx, row) in matrix.iter().enumerate() {
+        let mut sum = 0u64;
+        for (col_idx, &entry) in row.iter().enumerate() {
+            if entry != 0 {
+                let Some(next) = sum.checked_add(presses[col_idx]) else {
+                    return false;
+                };
+                sum = next;
+            }
+        }
+        if sum != target[row_idx] {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_presses;
+
+    #[test]
+    fn example_machine() {
+        // Buttons (3) (1,3) (2) (2,3) (0,2) (0,1) with joltage targets {3,5,4,7}.
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let matrix: Vec<Vec<u8>> = (0..4)
+            .map(|r| buttons.iter().map(|b| b.contains(&r) as u8).collect())
+            .collect();
+        assert_eq!(min_presses(&matrix, &[3, 5, 4, 7]), Some(10));
+    }
+
+    #[test]
+    fn trivial_and_unreachable_targets() {
+        assert_eq!(min_presses(&[], &[]), Some(0));
+        assert_eq!(min_presses(&[vec![1, 1]], &[0]), Some(0));
+        // x + y = 1 and x + y = 2 cannot both hold.
+        assert_eq!(min_presses(&[vec![1, 1], vec![1, 1]], &[1, 2]), None);
+        // A counter no button touches.
+        assert_eq!(min_presses(&[vec![1], vec![0]], &[2, 1]), None);
+    }
+
+    #[test]
+    fn totals_past_u64_are_none() {
+        // Forced presses: u64::MAX + 1.
+        assert_eq!(min_presses(&[vec![1, 0], vec![0, 1]], &[u64::MAX, 1]), None);
+        // Buttons {0,1}, {1,2}, {0,2} each pressed a times, with 3a > u64::MAX.
+        let a = u64::MAX / 3 + 1;
+        let matrix = vec![vec![1, 0, 1], vec![1, 1, 0], vec![0, 1, 1]];
+        assert_eq!(min_presses(&matrix, &[2 * a, 2 * a, 2 * a]), None);
+        assert_eq!(min_presses(&matrix, &[2, 2, 2]), Some(3));
+    }
+
+    #[cfg(feature = "z3")]
+    #[test]
+    fn z3_agrees() {
+        use super::min_presses_z3;
+        let matrix = vec![vec![1, 1, 0], vec![0, 1, 1], vec![1, 0, 1]];
+        for target in [[2, 3, 3], [1, 1, 1], [4, 0, 4]] {
+            assert_eq!(
+                min_presses_z3(&matrix, &target),
+                min_presses(&matrix, &target)
+            );
+        }
+    }
+}
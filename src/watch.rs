@@ -0,0 +1,95 @@
+//! `--watch`: re-run a day whenever its input changes.
+//!
+//! Files are polled (no extra dependencies). An edit to the input re-runs the
+//! day in place; an edit under `src/days/dayXX/` re-launches the runner via
+//! `cargo run` so the change is compiled in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::report::Table;
+use aoc25::days::{self, util};
+
+const POLL: Duration = Duration::from_millis(300);
+
+/// Run `day`, then keep re-running it on input changes until interrupted.
+pub fn watch(
+    day: &str,
+    opts: &util::RunOptions,
+    table: &Table,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let id = util::normalize_day(day);
+    days::solver(&id).ok_or_else(|| format!("Unknown day: {}", day))?;
+    let input = if opts.example {
+        PathBuf::from(format!("inputs/{}_example.txt", id))
+    } else {
+        PathBuf::from(format!("inputs/{}.txt", id))
+    };
+    let source = PathBuf::from(format!("src/days/{}", id));
+
+    let mut input_stamp = latest_mtime(&input);
+    let source_stamp = latest_mtime(&source);
+    run_once(&id, opts, table);
+    eprintln!(
+        "{}",
+        table.note(&format!("Watching {} (Ctrl-C to stop)", input.display()))
+    );
+    loop {
+        thread::sleep(POLL);
+        if latest_mtime(&source) != source_stamp {
+            eprintln!("{} changed, rebuilding...", source.display());
+            return Err(relaunch().into());
+        }
+        let stamp = latest_mtime(&input);
+        if stamp != input_stamp {
+            input_stamp = stamp;
+            println!();
+            run_once(&id, opts, table);
+        }
+    }
+}
+
+fn run_once(id: &str, opts: &util::RunOptions, table: &Table) {
+    match days::run_day(id, opts) {
+        Ok(result) => println!("{}\n{}", table.header(), table.row(&result)),
+        Err(e) => println!("{}", table.status(false, &format!("{}: error: {}", id, e))),
+    }
+}
+
+/// Newest modification time of `path`, or of any file below it when it is a
+/// directory; `None` when nothing is readable.
+fn latest_mtime(path: &Path) -> Option<SystemTime> {
+    let meta = fs::metadata(path).ok()?;
+    if !meta.is_dir() {
+        return meta.modified().ok();
+    }
+    fs::read_dir(path)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| latest_mtime(&e.path()))
+        .max()
+}
+
+/// Replace this process with `cargo run` of the same arguments, so the day
+/// is recompiled. Only returns if that could not be started.
+fn relaunch() -> String {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["run", "-q"]);
+    if !cfg!(debug_assertions) {
+        cmd.arg("--release");
+    }
+    cmd.arg("--").args(std::env::args().skip(1));
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        format!("could not relaunch via cargo: {}", cmd.exec())
+    }
+    #[cfg(not(unix))]
+    match cmd.status() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => format!("could not relaunch via cargo: {}", e),
+    }
+}
@@ -0,0 +1,124 @@
+//! Answer regressions listed in `regressions.toml`, one test per `[[case]]`.
+//!
+//! Each case names a day, an input file and the expected answers; adding a
+//! regression is a manifest edit. Cases run in-process through
+//! `aoc25::days`, and the tests are built at runtime with
+//! `libtest-mimic`, so the usual filters work:
+//! `cargo test --test regressions -- day07`.
+
+use std::fs;
+use std::process::ExitCode;
+
+use libtest_mimic::{Arguments, Failed, Trial};
+
+use aoc25::days::{
+    self,
+    answers::{mismatches, Expected},
+};
+
+const MANIFEST: &str = "regressions.toml";
+
+/// One manifest entry.
+#[derive(Debug, Default)]
+struct Case {
+    name: Option<String>,
+    day: String,
+    input: String,
+    expected: Expected,
+}
+
+impl Case {
+    /// `name`, or `dayXX::<input file stem>`.
+    fn test_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let stem = self.input.rsplit('/').next().unwrap_or(&self.input);
+            format!("{}::{}", self.day, stem.trim_end_matches(".txt"))
+        })
+    }
+
+    fn run(&self) -> Result<(), Failed> {
+        let solver = days::solver(&self.day).ok_or_else(|| format!("unknown day {}", self.day))?;
+        let input =
+            fs::read_to_string(&self.input).map_err(|e| format!("{}: {}", self.input, e))?;
+        let opts = days::util::RunOptions::default();
+        let result = days::solve(&self.day, solver, &input, &opts)
+            .map_err(|e| format!("{}:{}", self.input, e))?;
+        let wrong = mismatches(&result, &self.expected);
+        if wrong.is_empty() {
+            Ok(())
+        } else {
+            Err(wrong.join("; ").into())
+        }
+    }
+}
+
+/// Parse the manifest: the same TOML subset as `answers.toml`, with one
+/// `[[case]]` table per regression holding string keys.
+fn parse_manifest(src: &str) -> Result<Vec<Case>, String> {
+    let mut cases: Vec<Case> = Vec::new();
+    for (i, raw) in src.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: &str| format!("{}:{}: {}", MANIFEST, i + 1, msg);
+        if line == "[[case]]" {
+            cases.push(Case::default());
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `key = value` or `[[case]]`"))?;
+        let case = cases
+            .last_mut()
+            .ok_or_else(|| err("key outside a [[case]] table"))?;
+        let value = value
+            .trim()
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .map(str::to_string)
+            .ok_or_else(|| err("expected a quoted string"))?;
+        match key.trim() {
+            "name" => case.name = Some(value),
+            "day" => case.day = days::util::normalize_day(&value),
+            "input" => case.input = value,
+            "part1" => case.expected.part1 = Some(value),
+            "part2" => case.expected.part2 = Some(value),
+            other => return Err(err(&format!("unknown key `{}`", other))),
+        }
+    }
+    for (n, case) in cases.iter().enumerate() {
+        if case.day.is_empty() || case.input.is_empty() {
+            return Err(format!(
+                "{}: case {} needs `day` and `input`",
+                MANIFEST,
+                n + 1
+            ));
+        }
+    }
+    Ok(cases)
+}
+
+fn main() -> ExitCode {
+    let args = Arguments::from_args();
+    let manifest = match fs::read_to_string(MANIFEST).map_err(|e| e.to_string()) {
+        Ok(src) => parse_manifest(&src),
+        Err(e) => Err(format!("cannot read {}: {}", MANIFEST, e)),
+    };
+    let cases = match manifest {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let trials = cases
+        .into_iter()
+        .map(|case| {
+            // Inputs that are not checked out (e.g. real puzzle inputs) are skipped.
+            let missing = !std::path::Path::new(&case.input).exists();
+            Trial::test(case.test_name(), move || case.run()).with_ignored_flag(missing)
+        })
+        .collect();
+    libtest_mimic::run(&args, trials).exit_code()
+}